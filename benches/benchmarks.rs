@@ -35,6 +35,14 @@ fn benchmark_analise_completa(c: &mut Criterion) {
     });
 }
 
+fn benchmark_analise_completa_resumida(c: &mut Criterion) {
+    let dados: Vec<f64> = (0..1000).map(|x| x as f64 * 0.5 + 10.0 + (x as f64 * 0.01).sin()).collect();
+    
+    c.bench_function("analise_completa_resumida", |b| {
+        b.iter(|| analise_completa_resumida(black_box(&dados)))
+    });
+}
+
 fn benchmark_calcular_r2(c: &mut Criterion) {
     let y_real: Vec<f64> = (0..1000).map(|x| x as f64 * 0.5 + 10.0).collect();
     let y_prev: Vec<f64> = (0..1000).map(|x| x as f64 * 0.5 + 10.1).collect();
@@ -58,6 +66,7 @@ criterion_group!(
     benchmark_regressao_media,
     benchmark_regressao_grande,
     benchmark_analise_completa,
+    benchmark_analise_completa_resumida,
     benchmark_calcular_r2,
     benchmark_estatisticas
 );
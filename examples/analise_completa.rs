@@ -1,5 +1,6 @@
 //! Exemplo de análise completa com múltiplos datasets
 
+use regressao_linear::correlacao::{self, MetodoCorrelacao};
 use regressao_linear::*;
 
 fn main() {
@@ -46,6 +47,7 @@ fn analisar_dataset(nome: &str, dados: &[f64], unidade: &str) {
             println!("   Desvio Padrão: {:.2} {}", stats.desvio_padrao, unidade);
             println!("   Min/Max: {:.2} / {:.2} {}", stats.minimo, stats.maximo, unidade);
             println!("   Amplitude: {:.2} {}", stats.amplitude, unidade);
+            println!("   Quartis (Q1/Q3): {:.2} / {:.2} {}", stats.primeiro_quartil, stats.terceiro_quartil, unidade);
         }
         Err(e) => println!("Erro ao calcular estatísticas: {}", e),
     }
@@ -63,15 +65,20 @@ fn analisar_dataset(nome: &str, dados: &[f64], unidade: &str) {
             // Mostrar alguns valores previstos vs reais
             println!("\n🎯 Precisão das Previsões (primeiros 5 pontos):");
             for (i, (&real, &prev)) in dados.iter().zip(resultado.valores_previstos.iter()).take(5).enumerate() {
-                let erro_percentual = ((real - prev).abs() / real * 100.0).min(999.9);
-                println!("   Período {}: Real={:.1}, Prev={:.1}, Erro={:.1}%", 
-                         i, real, prev, erro_percentual);
+                match metricas::calcular_mape(&[real], &[prev]) {
+                    Ok(erro_percentual) => println!("   Período {}: Real={:.1}, Prev={:.1}, Erro={:.1}%", 
+                                                    i, real, prev, erro_percentual),
+                    Err(_) => println!("   Período {}: Real={:.1}, Prev={:.1}, Erro=indefinido (real nulo)", 
+                                       i, real, prev),
+                }
+            }
+            if let Ok(mape) = metricas::calcular_mape(dados, &resultado.valores_previstos) {
+                println!("   MAPE: {:.2}%", mape);
             }
             
             // Previsões futuras
             println!("\n🔮 Previsões para próximos 3 períodos:");
-            let previsoes = resultado.prever_proximos_periodos(dados.len(), 3);
-            for (i, &valor) in previsoes.iter().enumerate() {
+            for (i, valor) in resultado.prever_proximos_periodos_iter(dados.len(), 3).enumerate() {
                 println!("   Período {}: {:.2} {}", dados.len() + i, valor, unidade);
             }
             
@@ -103,13 +110,28 @@ fn analisar_dataset_xy(x: &[f64], y: &[f64], nome_x: &str, nome_y: &str) {
             
             if let Ok(r2) = calcular_r2(y, &y_prev) {
                 println!("   R²: {:.4} ({})", r2, interpretar_r2(r2));
-                println!("   Correlação: {}", interpretar_correlacao(inclinacao, r2));
             }
             
             if let Ok(mse) = calcular_mse(y, &y_prev) {
                 println!("   RMSE: {:.4}", mse.sqrt());
             }
             
+            // Coeficientes de correlação com teste de nulidade
+            println!("\n🔗 Correlação:");
+            for (nome, metodo) in [
+                ("Pearson r", MetodoCorrelacao::Pearson),
+                ("Spearman rho", MetodoCorrelacao::Spearman),
+                ("Kendall tau-b", MetodoCorrelacao::Kendall),
+            ] {
+                match correlacao::correlacao(x, y, metodo, 0.95) {
+                    Ok(c) => println!(
+                        "   {}: {:.4} (IC 95%: [{:.4}, {:.4}], valor-p {:.2e})",
+                        nome, c.coeficiente, c.limite_inferior, c.limite_superior, c.valor_p
+                    ),
+                    Err(e) => println!("   {}: erro ({})", nome, e),
+                }
+            }
+            
             // Interpretação prática
             if inclinacao > 0.0 {
                 println!("\n💡 Interpretação: Cada unidade adicional de {} resulta em +{:.2} de {}", 
@@ -132,13 +154,3 @@ fn interpretar_r2(r2: f64) -> &'static str {
         _ => "Ajuste muito fraco"
     }
 }
-
-fn interpretar_correlacao(inclinacao: f64, r2: f64) -> String {
-    let forca = if r2 >= 0.8 { "forte" } 
-               else if r2 >= 0.5 { "moderada" } 
-               else { "fraca" };
-    
-    let direcao = if inclinacao > 0.0 { "positiva" } else { "negativa" };
-    
-    format!("Correlação {} {}", forca, direcao)
-}
\ No newline at end of file
@@ -0,0 +1,470 @@
+//! # Estatísticas descritivas em fluxo
+//!
+//! [`AcumuladorEstatisticas`] processa uma observação por vez em memória
+//! constante, sem copiar nem ordenar a série. Contagem, média, variância,
+//! assimetria, curtose, extremos e as médias geométrica e harmônica são
+//! exatos (atualizações de Welford e Terriberry); mediana, quartis e médias
+//! aparadas vêm de um t-digest e são aproximados. Acumuladores de partes da
+//! série podem ser mesclados (fórmulas de Pébay), o que permite processar
+//! blocos em paralelo.
+
+use std::f64::consts::PI;
+
+use crate::descritivas::{momentos_padronizados, GrausLiberdade, PROPORCAO_APARADA};
+use crate::{EstatisticasDescritivas, RegressaoError, Resultado};
+
+/// Compressão padrão do t-digest (número aproximado de centroides)
+pub const COMPRESSAO_PADRAO: f64 = 100.0;
+
+/// Centroide do t-digest: média de `peso` observações vizinhas
+#[derive(Debug, Clone, Copy)]
+struct Centroide {
+    media: f64,
+    peso: f64,
+}
+
+/// t-digest com fusão (Dunning e Ertl, 2019), escala `k1`
+///
+/// Os centroides perto das caudas ficam pequenos, de modo que quantis
+/// extremos são mais precisos que os centrais.
+#[derive(Debug, Clone)]
+struct ResumoQuantis {
+    compressao: f64,
+    centroides: Vec<Centroide>,
+    pendentes: Vec<Centroide>,
+}
+
+impl ResumoQuantis {
+    fn new(compressao: f64) -> Self {
+        ResumoQuantis { compressao, centroides: Vec::new(), pendentes: Vec::new() }
+    }
+
+    fn adicionar(&mut self, centroide: Centroide) {
+        self.pendentes.push(centroide);
+        if self.pendentes.len() as f64 >= 5.0 * self.compressao {
+            self.comprimir();
+        }
+    }
+
+    fn escala(&self, q: f64) -> f64 {
+        self.compressao / (2.0 * PI) * (2.0 * q - 1.0).asin()
+    }
+
+    fn escala_inversa(&self, k: f64) -> f64 {
+        ((2.0 * PI * k / self.compressao).sin() + 1.0) / 2.0
+    }
+
+    /// Funde os pendentes aos centroides respeitando o limite de tamanho
+    fn comprimir(&mut self) {
+        if self.pendentes.is_empty() {
+            return;
+        }
+        let mut todos = std::mem::take(&mut self.centroides);
+        todos.append(&mut self.pendentes);
+        todos.sort_by(|a, b| a.media.total_cmp(&b.media));
+        let total: f64 = todos.iter().map(|c| c.peso).sum();
+
+        let mut fundidos = Vec::with_capacity(self.compressao as usize * 2);
+        let mut atual = todos[0];
+        let mut acumulado = 0.0;
+        let mut limite = self.escala_inversa(self.escala(0.0) + 1.0);
+        for proximo in todos.into_iter().skip(1) {
+            if (acumulado + atual.peso + proximo.peso) / total <= limite {
+                atual.peso += proximo.peso;
+                atual.media += (proximo.media - atual.media) * proximo.peso / atual.peso;
+            } else {
+                acumulado += atual.peso;
+                fundidos.push(atual);
+                limite = self.escala_inversa(self.escala(acumulado / total) + 1.0);
+                atual = proximo;
+            }
+        }
+        fundidos.push(atual);
+        self.centroides = fundidos;
+    }
+
+    /// Posição acumulada do centro de cada centroide
+    fn centros(&self) -> Vec<f64> {
+        let mut acumulado = 0.0;
+        self.centroides
+            .iter()
+            .map(|c| {
+                let centro = acumulado + c.peso / 2.0;
+                acumulado += c.peso;
+                centro
+            })
+            .collect()
+    }
+
+    /// Valor na posição acumulada `alvo` (em peso), interpolando entre os
+    /// centros dos centroides e usando os extremos exatos nas pontas
+    fn valor_na_posicao(&self, alvo: f64, minimo: f64, maximo: f64) -> f64 {
+        let centros = self.centros();
+        let ultimo = self.centroides.len() - 1;
+        if alvo <= centros[0] {
+            let fracao = if centros[0] > 0.0 { alvo / centros[0] } else { 1.0 };
+            return minimo + fracao * (self.centroides[0].media - minimo);
+        }
+        if alvo >= centros[ultimo] {
+            let restante = self.centroides[ultimo].peso / 2.0;
+            let fracao = (alvo - centros[ultimo]) / restante;
+            return self.centroides[ultimo].media + fracao.min(1.0) * (maximo - self.centroides[ultimo].media);
+        }
+        let i = centros.partition_point(|&c| c <= alvo) - 1;
+        let fracao = (alvo - centros[i]) / (centros[i + 1] - centros[i]);
+        self.centroides[i].media + fracao * (self.centroides[i + 1].media - self.centroides[i].media)
+    }
+
+    /// Soma das observações com posição acumulada em `[inicio, fim]` e a
+    /// média do centroide que contém cada limite
+    fn soma_entre(&self, inicio: f64, fim: f64) -> (f64, f64, f64) {
+        let mut soma = 0.0;
+        let mut acumulado = 0.0;
+        let mut no_inicio = self.centroides[0].media;
+        let mut no_fim = self.centroides[self.centroides.len() - 1].media;
+        for c in &self.centroides {
+            let (esquerda, direita) = (acumulado, acumulado + c.peso);
+            soma += c.media * (direita.min(fim) - esquerda.max(inicio)).max(0.0);
+            if esquerda <= inicio && inicio < direita {
+                no_inicio = c.media;
+            }
+            if esquerda < fim && fim <= direita {
+                no_fim = c.media;
+            }
+            acumulado = direita;
+        }
+        (soma, no_inicio, no_fim)
+    }
+}
+
+/// Acumulador de estatísticas descritivas em uma passada
+///
+/// Os quantis aproximados coincidem com o método 5 de Hyndman e Fan
+/// ([`crate::descritivas::MetodoQuantil::Tipo5`]) enquanto nenhum centroide
+/// foi fundido, isto é, para amostras pequenas.
+#[derive(Debug, Clone)]
+pub struct AcumuladorEstatisticas {
+    n: usize,
+    media: f64,
+    // Somas dos desvios em relação à média elevados a 2, 3 e 4
+    m2: f64,
+    m3: f64,
+    m4: f64,
+    minimo: f64,
+    maximo: f64,
+    soma_logaritmos: f64,
+    soma_inversos: f64,
+    nao_positivos: bool,
+    resumo: ResumoQuantis,
+}
+
+impl Default for AcumuladorEstatisticas {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AcumuladorEstatisticas {
+    /// Acumulador vazio com a compressão [`COMPRESSAO_PADRAO`]
+    pub fn new() -> Self {
+        Self::com_compressao(COMPRESSAO_PADRAO).expect("compressão padrão válida")
+    }
+
+    /// Acumulador vazio com compressão escolhida
+    ///
+    /// Compressões maiores guardam mais centroides (memória `O(compressao)`)
+    /// e dão quantis mais precisos.
+    pub fn com_compressao(compressao: f64) -> Resultado<Self> {
+        if !compressao.is_finite() || compressao < 10.0 {
+            return Err(RegressaoError::ParametroInvalido);
+        }
+        Ok(AcumuladorEstatisticas {
+            n: 0,
+            media: 0.0,
+            m2: 0.0,
+            m3: 0.0,
+            m4: 0.0,
+            minimo: f64::INFINITY,
+            maximo: f64::NEG_INFINITY,
+            soma_logaritmos: 0.0,
+            soma_inversos: 0.0,
+            nao_positivos: false,
+            resumo: ResumoQuantis::new(compressao),
+        })
+    }
+
+    /// Número de observações acumuladas
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Inclui uma observação
+    pub fn adicionar(&mut self, valor: f64) {
+        let anterior = self.n as f64;
+        self.n += 1;
+        let n = self.n as f64;
+        let delta = valor - self.media;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let termo = delta * delta_n * anterior;
+
+        self.media += delta_n;
+        self.m4 += termo * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2 - 4.0 * delta_n * self.m3;
+        self.m3 += termo * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += termo;
+
+        self.minimo = self.minimo.min(valor);
+        self.maximo = self.maximo.max(valor);
+        if valor > 0.0 {
+            self.soma_logaritmos += valor.ln();
+            self.soma_inversos += 1.0 / valor;
+        } else {
+            self.nao_positivos = true;
+        }
+        self.resumo.adicionar(Centroide { media: valor, peso: 1.0 });
+    }
+
+    /// Inclui todas as observações de um iterador
+    pub fn estender<I: IntoIterator<Item = f64>>(&mut self, valores: I) {
+        for valor in valores {
+            self.adicionar(valor);
+        }
+    }
+
+    /// Incorpora as observações de outro acumulador
+    ///
+    /// Os momentos resultantes são os mesmos (a menos de arredondamento) de
+    /// um único acumulador que tivesse visto as duas partes.
+    pub fn mesclar(&mut self, outro: &AcumuladorEstatisticas) {
+        if outro.n == 0 {
+            return;
+        }
+        if self.n == 0 {
+            let compressao = self.resumo.compressao;
+            *self = outro.clone();
+            self.resumo.compressao = compressao;
+            self.resumo.comprimir();
+            return;
+        }
+
+        let (na, nb) = (self.n as f64, outro.n as f64);
+        let n = na + nb;
+        let delta = outro.media - self.media;
+        let delta2 = delta * delta;
+
+        let m4 = self.m4 + outro.m4
+            + delta2 * delta2 * na * nb * (na * na - na * nb + nb * nb) / (n * n * n)
+            + 6.0 * delta2 * (na * na * outro.m2 + nb * nb * self.m2) / (n * n)
+            + 4.0 * delta * (na * outro.m3 - nb * self.m3) / n;
+        let m3 = self.m3 + outro.m3
+            + delta2 * delta * na * nb * (na - nb) / (n * n)
+            + 3.0 * delta * (na * outro.m2 - nb * self.m2) / n;
+        self.m2 += outro.m2 + delta2 * na * nb / n;
+        self.m3 = m3;
+        self.m4 = m4;
+        self.media += delta * nb / n;
+        self.n += outro.n;
+
+        self.minimo = self.minimo.min(outro.minimo);
+        self.maximo = self.maximo.max(outro.maximo);
+        self.soma_logaritmos += outro.soma_logaritmos;
+        self.soma_inversos += outro.soma_inversos;
+        self.nao_positivos |= outro.nao_positivos;
+        for centroide in outro.resumo.centroides.iter().chain(&outro.resumo.pendentes) {
+            self.resumo.adicionar(*centroide);
+        }
+    }
+
+    /// Média exata; `NaN` se vazio
+    pub fn media(&self) -> f64 {
+        if self.n == 0 { f64::NAN } else { self.media }
+    }
+
+    /// Variância exata com os graus de liberdade escolhidos
+    pub fn variancia(&self, graus_liberdade: GrausLiberdade) -> f64 {
+        self.m2 / graus_liberdade.divisor(self.n)
+    }
+
+    /// Quantil `p` aproximado pelo t-digest
+    pub fn quantil(&mut self, p: f64) -> Resultado<f64> {
+        if self.n == 0 {
+            return Err(RegressaoError::DadosVazios);
+        }
+        if !(0.0..=1.0).contains(&p) {
+            return Err(RegressaoError::ParametroInvalido);
+        }
+        self.resumo.comprimir();
+        Ok(self.resumo.valor_na_posicao(p * self.n as f64, self.minimo, self.maximo))
+    }
+
+    /// Resume o acumulado em [`EstatisticasDescritivas`]
+    ///
+    /// Diferenças em relação a [`crate::calcular_estatisticas_com_graus`]:
+    /// mediana, quartis e médias aparada e winsorizada são aproximados; a
+    /// moda não é acompanhada (fica vazia) e o desvio absoluto mediano, que
+    /// exige uma segunda passada, é `NaN`.
+    pub fn estatisticas(&mut self, graus_liberdade: GrausLiberdade) -> Resultado<EstatisticasDescritivas> {
+        if self.n == 0 {
+            return Err(RegressaoError::DadosVazios);
+        }
+        self.resumo.comprimir();
+        let n = self.n as f64;
+        let quantil = |p: f64| self.resumo.valor_na_posicao(p * n, self.minimo, self.maximo);
+        let primeiro_quartil = quantil(0.25);
+        let terceiro_quartil = quantil(0.75);
+        let mediana = quantil(0.5);
+
+        let variancia = self.variancia(graus_liberdade);
+        let desvio_padrao = variancia.sqrt();
+        let (assimetria, assimetria_ajustada, curtose, curtose_ajustada) = if self.m2 > 0.0 {
+            momentos_padronizados(n, self.m2 / n, self.m3 / n, self.m4 / n)
+        } else {
+            (f64::NAN, f64::NAN, f64::NAN, f64::NAN)
+        };
+
+        let k = (n * PROPORCAO_APARADA).floor();
+        let (soma_central, no_inicio, no_fim) = self.resumo.soma_entre(k, n - k);
+        let (media_geometrica, media_harmonica) = if self.nao_positivos {
+            (f64::NAN, f64::NAN)
+        } else {
+            ((self.soma_logaritmos / n).exp(), n / self.soma_inversos)
+        };
+
+        Ok(EstatisticasDescritivas {
+            media: self.media,
+            mediana,
+            desvio_padrao,
+            variancia,
+            graus_liberdade,
+            minimo: self.minimo,
+            maximo: self.maximo,
+            amplitude: self.maximo - self.minimo,
+            primeiro_quartil,
+            terceiro_quartil,
+            intervalo_interquartil: terceiro_quartil - primeiro_quartil,
+            assimetria,
+            assimetria_ajustada,
+            curtose,
+            curtose_ajustada,
+            moda: Vec::new(),
+            coeficiente_variacao: desvio_padrao / self.media.abs(),
+            media_aparada: soma_central / (n - 2.0 * k),
+            media_winsorizada: (soma_central + k * (no_inicio + no_fim)) / n,
+            media_geometrica,
+            media_harmonica,
+            desvio_absoluto_mediano: f64::NAN,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calcular_estatisticas_com_graus;
+    use crate::descritivas::{quantil, MetodoQuantil};
+    use crate::teste_util::Gerador;
+
+    fn proximo(a: f64, b: f64, tolerancia: f64) -> bool {
+        (a - b).abs() <= tolerancia * (1.0 + b.abs())
+    }
+
+    #[test]
+    fn test_momentos_exatos() {
+        let mut gerador = Gerador::new(31);
+        let dados: Vec<f64> = (0..5000).map(|_| 1e6 + gerador.normal().exp()).collect();
+        let mut acumulador = AcumuladorEstatisticas::new();
+        acumulador.estender(dados.iter().copied());
+
+        let fluxo = acumulador.estatisticas(GrausLiberdade::Amostral).unwrap();
+        let lote = calcular_estatisticas_com_graus(&dados, GrausLiberdade::Amostral).unwrap();
+        assert_eq!(acumulador.len(), 5000);
+        assert!(proximo(fluxo.media, lote.media, 1e-14));
+        assert!(proximo(fluxo.variancia, lote.variancia, 1e-9));
+        assert!(proximo(fluxo.assimetria, lote.assimetria, 1e-7));
+        assert!(proximo(fluxo.curtose_ajustada, lote.curtose_ajustada, 1e-7));
+        assert!(proximo(fluxo.media_geometrica, lote.media_geometrica, 1e-12));
+        assert!(proximo(fluxo.media_harmonica, lote.media_harmonica, 1e-12));
+        assert_eq!((fluxo.minimo, fluxo.maximo), (lote.minimo, lote.maximo));
+        assert!(fluxo.desvio_absoluto_mediano.is_nan());
+    }
+
+    #[test]
+    fn test_amostra_pequena_exata() {
+        let dados = [4.0, 1.0, 2.0, 2.0, 9.0, 3.0, 2.0, 5.0, 8.0, 4.0];
+        let mut acumulador = AcumuladorEstatisticas::new();
+        acumulador.estender(dados);
+
+        for p in [0.0, 0.1, 0.25, 0.5, 0.9, 1.0] {
+            assert_eq!(acumulador.quantil(p).unwrap(), quantil(&dados, p, MetodoQuantil::Tipo5).unwrap());
+        }
+        let fluxo = acumulador.estatisticas(GrausLiberdade::Populacional).unwrap();
+        let lote = calcular_estatisticas_com_graus(&dados, GrausLiberdade::Populacional).unwrap();
+        assert!(proximo(fluxo.media_aparada, lote.media_aparada, 1e-14));
+        assert!(proximo(fluxo.media_winsorizada, lote.media_winsorizada, 1e-14));
+        assert_eq!(fluxo.mediana, lote.mediana);
+    }
+
+    #[test]
+    fn test_quantis_aproximados() {
+        let mut gerador = Gerador::new(8);
+        let dados: Vec<f64> = (0..100_000).map(|_| gerador.normal()).collect();
+        let mut acumulador = AcumuladorEstatisticas::new();
+        acumulador.estender(dados.iter().copied());
+
+        // Erro medido na escala de postos, proporcional a √(p(1 - p)), com piso nas caudas
+        for p in [0.001, 0.01, 0.25, 0.5, 0.75, 0.99, 0.999] {
+            let aproximado = acumulador.quantil(p).unwrap();
+            let posto = dados.iter().filter(|&&v| v <= aproximado).count() as f64 / dados.len() as f64;
+            assert!((posto - p).abs() < 0.005 * (p * (1.0 - p)).sqrt().max(0.05), "p = {}: posto {}", p, posto);
+        }
+        assert!(acumulador.resumo.centroides.len() < 2 * COMPRESSAO_PADRAO as usize);
+    }
+
+    #[test]
+    fn test_mesclar_partes() {
+        let mut gerador = Gerador::new(12);
+        let dados: Vec<f64> = (0..20_000).map(|i| gerador.normal() + if i < 7000 { 5.0 } else { 0.0 }).collect();
+
+        let mut inteiro = AcumuladorEstatisticas::new();
+        inteiro.estender(dados.iter().copied());
+        let mut partes: Vec<AcumuladorEstatisticas> = dados
+            .chunks(3000)
+            .map(|bloco| {
+                let mut parte = AcumuladorEstatisticas::new();
+                parte.estender(bloco.iter().copied());
+                parte
+            })
+            .collect();
+        let mut mesclado = AcumuladorEstatisticas::new();
+        for parte in &mut partes {
+            mesclado.mesclar(parte);
+        }
+
+        let a = inteiro.estatisticas(GrausLiberdade::Amostral).unwrap();
+        let b = mesclado.estatisticas(GrausLiberdade::Amostral).unwrap();
+        assert_eq!(mesclado.len(), inteiro.len());
+        assert!(proximo(a.media, b.media, 1e-12));
+        assert!(proximo(a.variancia, b.variancia, 1e-12));
+        assert!(proximo(a.assimetria, b.assimetria, 1e-10));
+        assert!(proximo(a.curtose, b.curtose, 1e-10));
+        assert!((a.mediana - b.mediana).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_acumulador_erros() {
+        let mut vazio = AcumuladorEstatisticas::new();
+        assert!(vazio.is_empty() && vazio.media().is_nan());
+        assert!(matches!(vazio.estatisticas(GrausLiberdade::Amostral), Err(RegressaoError::DadosVazios)));
+        assert!(matches!(AcumuladorEstatisticas::com_compressao(1.0), Err(RegressaoError::ParametroInvalido)));
+
+        vazio.adicionar(-1.0);
+        assert!(matches!(vazio.quantil(2.0), Err(RegressaoError::ParametroInvalido)));
+        let unica = vazio.estatisticas(GrausLiberdade::Amostral).unwrap();
+        assert!(unica.variancia.is_nan() && unica.media_geometrica.is_nan());
+        assert_eq!(unica.mediana, -1.0);
+    }
+}
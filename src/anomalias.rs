@@ -0,0 +1,334 @@
+//! # Detecção de anomalias
+//!
+//! Detectores de leituras atípicas sobre `&[f64]`, para uso antes do ajuste
+//! (na série) ou depois dele (nos resíduos). Cada detector devolve os
+//! índices sinalizados e um escore por observação; [`reajustar_sem_anomalias`]
+//! refaz a análise completa sem os pontos sinalizados.
+
+use crate::descritivas::{quantil_ordenado, MetodoQuantil};
+use crate::distribuicoes::t_student_quantil;
+use crate::soma::{self, soma_compensada};
+use crate::{analise_completa, analise_completa_xy, RegressaoError, Resultado, ResultadoRegressao};
+
+/// Constante de consistência do MAD para a normal: `1 / Φ⁻¹(3/4)`
+const CONSISTENCIA_MAD: f64 = 1.482_602_218_505_602;
+
+/// Observações sinalizadas por um detector
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomalias {
+    /// Índices sinalizados, em ordem crescente
+    pub indices: Vec<usize>,
+    /// Escore de cada observação da entrada (mesmo comprimento); o
+    /// significado depende do detector
+    pub escores: Vec<f64>,
+}
+
+impl Anomalias {
+    fn por_limiar(escores: Vec<f64>, limiar: f64) -> Self {
+        let indices = escores.iter()
+            .enumerate()
+            .filter(|(_, e)| **e > limiar)
+            .map(|(i, _)| i)
+            .collect();
+        Anomalias { indices, escores }
+    }
+}
+
+fn validar(y: &[f64], limiar: f64) -> Resultado<()> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if limiar <= 0.0 || limiar.is_nan() {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    Ok(())
+}
+
+/// Escore z clássico: `|y - média| / desvio padrão amostral`
+///
+/// Sensível às próprias anomalias, que inflam a média e o desvio; valores
+/// usuais de `limiar` ficam entre 2.5 e 3.
+pub fn escore_z(y: &[f64], limiar: f64) -> Resultado<Anomalias> {
+    validar(y, limiar)?;
+    if y.len() < 2 {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let media = soma::media(y);
+    let soma_quadrados = soma_compensada(y.iter().map(|v| (v - media).powi(2)));
+    if soma::variancia_desprezivel(soma_quadrados, y.len(), soma::maior_absoluto(y)) {
+        return Err(RegressaoError::VarianciaZero);
+    }
+    let desvio = (soma_quadrados / (y.len() - 1) as f64).sqrt();
+
+    let escores = y.iter().map(|v| (v - media).abs() / desvio).collect();
+    Ok(Anomalias::por_limiar(escores, limiar))
+}
+
+/// Escore z modificado de Iglewicz e Hoaglin: `0.6745 |y - mediana| / MAD`
+///
+/// Robusto a até metade de observações contaminadas. Quando o MAD é zero,
+/// usa o desvio absoluto médio em torno da mediana (`1.2533 · DAM`).
+/// O limiar recomendado é 3.5.
+pub fn escore_z_modificado(y: &[f64], limiar: f64) -> Resultado<Anomalias> {
+    validar(y, limiar)?;
+    let escala = escala_robusta(y)?;
+    let mediana = mediana(y);
+    let escores = y.iter().map(|v| (v - mediana).abs() / escala).collect();
+    Ok(Anomalias::por_limiar(escores, limiar))
+}
+
+/// Cercas de Tukey: sinaliza valores fora de `[Q1 - k·IQR, Q3 + k·IQR]`
+///
+/// O escore é a distância ao quartil mais próximo em unidades de IQR (zero
+/// entre os quartis); com `k = 1.5` obtêm-se as cercas internas do boxplot e
+/// com `k = 3` as externas.
+pub fn cercas_iqr(y: &[f64], k: f64) -> Resultado<Anomalias> {
+    validar(y, k)?;
+    let mut ordenados = y.to_vec();
+    ordenados.sort_by(|a, b| a.total_cmp(b));
+    let q1 = quantil_ordenado(&ordenados, 0.25, MetodoQuantil::Tipo7);
+    let q3 = quantil_ordenado(&ordenados, 0.75, MetodoQuantil::Tipo7);
+    let iqr = q3 - q1;
+    if soma::variancia_desprezivel(iqr * iqr, 1, soma::maior_absoluto(&ordenados)) {
+        return Err(RegressaoError::VarianciaZero);
+    }
+
+    let escores = y.iter().map(|&v| ((q1 - v).max(v - q3)).max(0.0) / iqr).collect();
+    Ok(Anomalias::por_limiar(escores, k))
+}
+
+/// Filtro de Hampel: escore z modificado em janela móvel centrada
+///
+/// Cada ponto é comparado com a mediana dos vizinhos `[i - meia_janela,
+/// i + meia_janela]` (truncada nas bordas), na escala `1.4826 · MAD` da
+/// janela. Capta picos locais que os detectores globais não veem em séries
+/// com tendência. Se o MAD da janela é zero, qualquer desvio tem escore infinito.
+pub fn filtro_hampel(y: &[f64], meia_janela: usize, limiar: f64) -> Resultado<Anomalias> {
+    validar(y, limiar)?;
+    if meia_janela == 0 {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+
+    let mut janela = Vec::with_capacity(2 * meia_janela + 1);
+    let escores = (0..y.len())
+        .map(|i| {
+            janela.clear();
+            janela.extend_from_slice(&y[i.saturating_sub(meia_janela)..(i + meia_janela + 1).min(y.len())]);
+            let magnitude = soma::maior_absoluto(&janela);
+            let centro = mediana(&janela);
+            for v in janela.iter_mut() {
+                *v = (*v - centro).abs();
+            }
+            let escala = CONSISTENCIA_MAD * mediana(&janela);
+            let desvio = (y[i] - centro).abs();
+            if soma::variancia_desprezivel(desvio * desvio, 1, magnitude) { 0.0 } else { desvio / escala }
+        })
+        .collect();
+    Ok(Anomalias::por_limiar(escores, limiar))
+}
+
+/// ESD generalizado de Rosner para até `max_anomalias` anomalias
+///
+/// Remove iterativamente o ponto mais afastado da média, calculando
+/// `R_i = max |y - média| / s` na amostra restante, e compara com os valores
+/// críticos `λ_i` (baseados na t de Student). O número de anomalias é o maior
+/// `i` com `R_i > λ_i`. O escore de cada ponto removido é o seu `R_i`; os
+/// demais têm escore zero.
+///
+/// # Argumentos
+/// * `y` - Valores, supostos aproximadamente normais fora as anomalias
+/// * `max_anomalias` - Limite superior para o número de anomalias
+/// * `alfa` - Nível de significância, por exemplo 0.05
+pub fn esd_generalizado(y: &[f64], max_anomalias: usize, alfa: f64) -> Resultado<Anomalias> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if !(alfa > 0.0 && alfa < 1.0) || max_anomalias == 0 {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    let n = y.len();
+    if n < max_anomalias + 3 {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let mut restantes: Vec<usize> = (0..n).collect();
+    let mut escores = vec![0.0; n];
+    let mut removidos = Vec::with_capacity(max_anomalias);
+    let mut encontradas = 0;
+
+    for i in 1..=max_anomalias {
+        let valores: Vec<f64> = restantes.iter().map(|&j| y[j]).collect();
+        let media = soma::media(&valores);
+        let soma_quadrados = soma_compensada(valores.iter().map(|v| (v - media).powi(2)));
+        if soma::variancia_desprezivel(soma_quadrados, valores.len(), soma::maior_absoluto(&valores)) {
+            break;
+        }
+        let desvio = (soma_quadrados / (valores.len() - 1) as f64).sqrt();
+
+        let (posicao, &indice) = restantes.iter()
+            .enumerate()
+            .max_by(|a, b| (y[*a.1] - media).abs().total_cmp(&(y[*b.1] - media).abs()))
+            .expect("amostra restante não vazia");
+        let r = (y[indice] - media).abs() / desvio;
+
+        let graus = (n - i - 1) as f64;
+        let t = t_student_quantil(1.0 - alfa / (2.0 * (n - i + 1) as f64), graus);
+        let lambda = (n - i) as f64 * t / ((graus + t * t) * (n - i + 1) as f64).sqrt();
+
+        escores[indice] = r;
+        removidos.push(indice);
+        if r > lambda {
+            encontradas = i;
+        }
+        restantes.remove(posicao);
+    }
+
+    let mut indices = removidos[..encontradas].to_vec();
+    indices.sort_unstable();
+    Ok(Anomalias { indices, escores })
+}
+
+/// Anomalias nos resíduos de uma tendência linear
+///
+/// Ajusta [`analise_completa`] e aplica o escore z modificado aos resíduos,
+/// o que detecta pontos atípicos em relação à tendência mesmo quando estão
+/// dentro da faixa de valores da série.
+pub fn anomalias_residuos(y: &[f64], limiar: f64) -> Resultado<Anomalias> {
+    validar(y, limiar)?;
+    let resultado = analise_completa(y)?;
+    let residuos: Vec<f64> = y.iter().zip(&resultado.valores_previstos).map(|(v, p)| v - p).collect();
+    escore_z_modificado(&residuos, limiar)
+}
+
+/// Refaz [`analise_completa`] sem as observações sinalizadas
+///
+/// Os índices originais continuam sendo o `x` da regressão, de modo que as
+/// previsões seguem alinhadas com a série. As métricas usam apenas os pontos
+/// mantidos, mas `valores_previstos` cobre todos os índices `0..y.len()`.
+pub fn reajustar_sem_anomalias(y: &[f64], anomalias: &Anomalias) -> Resultado<ResultadoRegressao> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if anomalias.indices.iter().any(|&i| i >= y.len()) {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+
+    let mut excluido = vec![false; y.len()];
+    for &i in &anomalias.indices {
+        excluido[i] = true;
+    }
+    let (x, mantidos): (Vec<f64>, Vec<f64>) = y.iter()
+        .enumerate()
+        .filter(|(i, _)| !excluido[*i])
+        .map(|(i, &v)| (i as f64, v))
+        .unzip();
+
+    let mut resultado = analise_completa_xy(&x, &mantidos)?;
+    resultado.valores_previstos = resultado.prever_proximos_periodos(0, y.len());
+    Ok(resultado)
+}
+
+/// Desvio robusto `1.4826 · MAD`, ou `1.2533 · DAM` quando o MAD é zero
+fn escala_robusta(y: &[f64]) -> Resultado<f64> {
+    let centro = mediana(y);
+    let desvios: Vec<f64> = y.iter().map(|v| (v - centro).abs()).collect();
+    let mad = mediana(&desvios);
+    if mad > 0.0 {
+        return Ok(CONSISTENCIA_MAD * mad);
+    }
+    let dam = desvios.iter().sum::<f64>() / y.len() as f64;
+    if dam > 0.0 {
+        Ok(1.253_314_137_315_500_3 * dam)
+    } else {
+        Err(RegressaoError::VarianciaZero)
+    }
+}
+
+fn mediana(valores: &[f64]) -> f64 {
+    let mut ordenados = valores.to_vec();
+    ordenados.sort_by(|a, b| a.total_cmp(b));
+    quantil_ordenado(&ordenados, 0.5, MetodoQuantil::Tipo7)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teste_util::Gerador;
+
+    fn com_picos() -> Vec<f64> {
+        let mut gerador = Gerador::new(10);
+        let mut y: Vec<f64> = (0..100).map(|_| 10.0 + gerador.normal()).collect();
+        y[17] = 25.0;
+        y[60] = -4.0;
+        y
+    }
+
+    #[test]
+    fn test_detectores_globais() {
+        let y = com_picos();
+        for anomalias in [
+            escore_z(&y, 3.0).unwrap(),
+            escore_z_modificado(&y, 3.5).unwrap(),
+            cercas_iqr(&y, 3.0).unwrap(),
+            esd_generalizado(&y, 10, 0.05).unwrap(),
+        ] {
+            assert_eq!(anomalias.indices, vec![17, 60]);
+            assert_eq!(anomalias.escores.len(), 100);
+        }
+    }
+
+    #[test]
+    fn test_esd_referencia_rosner() {
+        // Exemplo do NIST/SEMATECH e-Handbook (1.3.5.17.3): 3 anomalias
+        let y = [
+            -0.25, 0.68, 0.94, 1.15, 1.20, 1.26, 1.26, 1.34, 1.38, 1.43, 1.49, 1.49, 1.55, 1.56,
+            1.58, 1.65, 1.69, 1.70, 1.76, 1.77, 1.81, 1.91, 1.94, 1.96, 1.99, 2.06, 2.09, 2.10,
+            2.14, 2.15, 2.23, 2.24, 2.26, 2.35, 2.37, 2.40, 2.47, 2.54, 2.62, 2.64, 2.90, 2.92,
+            2.92, 2.93, 3.21, 3.26, 3.30, 3.59, 3.68, 4.30, 4.64, 5.34, 5.42, 6.01,
+        ];
+        let anomalias = esd_generalizado(&y, 10, 0.05).unwrap();
+        assert_eq!(anomalias.indices, vec![51, 52, 53]);
+        assert!((anomalias.escores[53] - 3.118).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_hampel_e_residuos_com_tendencia() {
+        // Tendência forte: um pico local fica dentro da faixa global de valores
+        let mut y: Vec<f64> = (0..60).map(|t| 2.0 * t as f64 + (t as f64 * 1.3).sin()).collect();
+        y[30] += 30.0;
+
+        assert!(escore_z(&y, 3.0).unwrap().indices.is_empty());
+        assert_eq!(filtro_hampel(&y, 3, 3.0).unwrap().indices, vec![30]);
+        assert_eq!(anomalias_residuos(&y, 3.5).unwrap().indices, vec![30]);
+    }
+
+    #[test]
+    fn test_reajuste_sem_anomalias() {
+        let mut y: Vec<f64> = (0..30).map(|t| 3.0 + 0.5 * t as f64).collect();
+        y[5] = 40.0;
+        y[22] = -20.0;
+
+        let anomalias = anomalias_residuos(&y, 3.5).unwrap();
+        assert_eq!(anomalias.indices, vec![5, 22]);
+        let resultado = reajustar_sem_anomalias(&y, &anomalias).unwrap();
+        assert!((resultado.inclinacao - 0.5).abs() < 1e-12);
+        assert!((resultado.intercepto - 3.0).abs() < 1e-12);
+        assert_eq!(resultado.valores_previstos.len(), 30);
+        assert!((resultado.valores_previstos[5] - 5.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_anomalias_erros() {
+        assert!(matches!(escore_z(&[], 3.0), Err(RegressaoError::DadosVazios)));
+        assert!(matches!(escore_z(&[1.0, 2.0], 0.0), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(escore_z_modificado(&[2.0; 5], 3.5), Err(RegressaoError::VarianciaZero)));
+        assert!(matches!(escore_z(&[1.7e9 + 0.3; 9], 3.0), Err(RegressaoError::VarianciaZero)));
+        assert!(matches!(cercas_iqr(&[1.7e9 + 0.3; 9], 1.5), Err(RegressaoError::VarianciaZero)));
+        assert!(matches!(filtro_hampel(&[1.0, 2.0], 0, 3.0), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(esd_generalizado(&[1.0, 2.0, 3.0], 5, 0.05), Err(RegressaoError::DadosInsuficientes)));
+
+        let sinalizadas = Anomalias { indices: vec![7], escores: vec![0.0; 3] };
+        assert!(matches!(reajustar_sem_anomalias(&[1.0, 2.0, 3.0], &sinalizadas), Err(RegressaoError::ParametroInvalido)));
+    }
+}
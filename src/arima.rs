@@ -0,0 +1,779 @@
+//! # Modelos ARIMA e SARIMA
+//!
+//! Ajuste de modelos AR, MA, ARMA, ARIMA(p, d, q) e SARIMA(p, d, q)(P, D, Q)s
+//! por soma de quadrados condicional (CSS) ou máxima verossimilhança exata
+//! (filtro de Kalman), previsão com intervalos e seleção de ordem por AIC.
+//!
+//! Convenção de sinais (a mesma do R):
+//!
+//! ```text
+//! w_t - μ = Σ φ_i (w_{t-i} - μ) + e_t + Σ θ_j e_{t-j}
+//! ```
+//!
+//! onde `w` é a série após as diferenciações simples e sazonais.
+
+use std::fmt;
+
+use crate::distribuicoes::normal_quantil;
+use crate::otimizacao::nelder_mead;
+use crate::{RegressaoError, Resultado};
+
+/// Parte sazonal (P, D, Q) de um modelo SARIMA com período `periodo`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrdemSazonal {
+    pub p: usize,
+    pub d: usize,
+    pub q: usize,
+    pub periodo: usize,
+}
+
+/// Ordem completa de um modelo ARIMA, opcionalmente sazonal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EspecificacaoArima {
+    pub p: usize,
+    pub d: usize,
+    pub q: usize,
+    pub sazonal: Option<OrdemSazonal>,
+}
+
+impl EspecificacaoArima {
+    /// Modelo autorregressivo AR(p)
+    pub fn ar(p: usize) -> Self {
+        Self::arima(p, 0, 0)
+    }
+
+    /// Modelo de médias móveis MA(q)
+    pub fn ma(q: usize) -> Self {
+        Self::arima(0, 0, q)
+    }
+
+    /// Modelo ARMA(p, q)
+    pub fn arma(p: usize, q: usize) -> Self {
+        Self::arima(p, 0, q)
+    }
+
+    /// Modelo ARIMA(p, d, q)
+    pub fn arima(p: usize, d: usize, q: usize) -> Self {
+        EspecificacaoArima { p, d, q, sazonal: None }
+    }
+
+    /// Acrescenta a parte sazonal (P, D, Q) com o período informado
+    pub fn com_sazonalidade(mut self, p: usize, d: usize, q: usize, periodo: usize) -> Self {
+        self.sazonal = Some(OrdemSazonal { p, d, q, periodo });
+        self
+    }
+
+    fn sazonal_ou_vazia(&self) -> OrdemSazonal {
+        self.sazonal.unwrap_or(OrdemSazonal { p: 0, d: 0, q: 0, periodo: 1 })
+    }
+
+    /// A média só é estimada quando não há diferenciação
+    fn inclui_media(&self) -> bool {
+        self.d + self.sazonal_ou_vazia().d == 0
+    }
+
+    fn n_coeficientes(&self) -> usize {
+        let s = self.sazonal_ou_vazia();
+        self.p + self.q + s.p + s.q
+    }
+}
+
+impl fmt::Display for EspecificacaoArima {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ARIMA({}, {}, {})", self.p, self.d, self.q)?;
+        if let Some(s) = self.sazonal {
+            write!(f, "({}, {}, {})[{}]", s.p, s.d, s.q, s.periodo)?;
+        }
+        Ok(())
+    }
+}
+
+/// Método de estimação dos coeficientes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetodoEstimacao {
+    /// Soma de quadrados condicional: resíduos iniciais fixados em zero
+    SomaQuadradosCondicional,
+    /// Máxima verossimilhança exata via filtro de Kalman, iniciada pelo CSS
+    MaximaVerossimilhanca,
+}
+
+/// Modelo ARIMA ajustado
+#[derive(Debug, Clone)]
+pub struct ModeloArima {
+    pub especificacao: EspecificacaoArima,
+    pub metodo: MetodoEstimacao,
+    pub ar: Vec<f64>,
+    pub ma: Vec<f64>,
+    pub ar_sazonal: Vec<f64>,
+    pub ma_sazonal: Vec<f64>,
+    /// Média da série (apenas quando `d = D = 0`)
+    pub media: Option<f64>,
+    /// Variância das inovações
+    pub sigma2: f64,
+    pub log_verossimilhanca: f64,
+    pub aic: f64,
+    pub bic: f64,
+    /// Resíduos na escala original, com o mesmo tamanho da série.
+    /// Observações perdidas na diferenciação (e, no CSS, as condicionais)
+    /// têm resíduo zero.
+    pub residuos: Vec<f64>,
+    /// Previsões um passo à frente (`serie - residuos`)
+    pub valores_ajustados: Vec<f64>,
+    serie: Vec<f64>,
+}
+
+/// Previsões de um modelo ARIMA com intervalos de previsão
+#[derive(Debug, Clone)]
+pub struct PrevisaoArima {
+    pub previsoes: Vec<f64>,
+    pub erro_padrao: Vec<f64>,
+    pub limite_inferior: Vec<f64>,
+    pub limite_superior: Vec<f64>,
+    /// Nível de confiança dos intervalos (ex.: 0.95)
+    pub nivel: f64,
+}
+
+/// Coeficientes desempacotados do vetor de parâmetros do otimizador
+struct Coeficientes {
+    ar: Vec<f64>,
+    ma: Vec<f64>,
+    ar_sazonal: Vec<f64>,
+    ma_sazonal: Vec<f64>,
+    media: f64,
+}
+
+impl Coeficientes {
+    fn de_vetor(theta: &[f64], espec: &EspecificacaoArima) -> Self {
+        let s = espec.sazonal_ou_vazia();
+        let mut resto = theta;
+        let mut tomar = |k: usize| {
+            let (cabeca, cauda) = resto.split_at(k);
+            resto = cauda;
+            cabeca.to_vec()
+        };
+        let ar = tomar(espec.p);
+        let ma = tomar(espec.q);
+        let ar_sazonal = tomar(s.p);
+        let ma_sazonal = tomar(s.q);
+        let media = if espec.inclui_media() { tomar(1)[0] } else { 0.0 };
+        Coeficientes { ar, ma, ar_sazonal, ma_sazonal, media }
+    }
+
+    /// Coeficientes AR e MA expandidos do produto dos polinômios simples e sazonais
+    fn expandir(&self, periodo: usize) -> (Vec<f64>, Vec<f64>) {
+        let ar = multiplicar_polinomios(
+            &polinomio_ar(&self.ar, 1),
+            &polinomio_ar(&self.ar_sazonal, periodo),
+        );
+        let ma = multiplicar_polinomios(
+            &polinomio_ma(&self.ma, 1),
+            &polinomio_ma(&self.ma_sazonal, periodo),
+        );
+        (ar[1..].iter().map(|c| -c).collect(), ma[1..].to_vec())
+    }
+}
+
+/// Ajusta um modelo ARIMA/SARIMA à série `y`
+///
+/// # Argumentos
+/// * `y` - Série temporal na escala original
+/// * `espec` - Ordens do modelo
+/// * `metodo` - CSS ou máxima verossimilhança exata
+///
+/// # Retorna
+/// * `Ok(ModeloArima)` - Coeficientes, critérios de informação e resíduos
+/// * `Err(RegressaoError)` - Em caso de dados insuficientes ou ordem inválida
+pub fn ajustar_arima(
+    y: &[f64],
+    espec: &EspecificacaoArima,
+    metodo: MetodoEstimacao,
+) -> Resultado<ModeloArima> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+
+    let sazonal = espec.sazonal_ou_vazia();
+    if espec.sazonal.is_some() && sazonal.periodo < 2 {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+
+    let poli_dif = polinomio_diferenciacao(espec);
+    let ordem_dif = poli_dif.len() - 1;
+    if y.len() <= ordem_dif {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+    let w = aplicar_polinomio(y, &poli_dif);
+
+    let ordem_ar = espec.p + sazonal.p * sazonal.periodo;
+    let n_parametros = espec.n_coeficientes() + usize::from(espec.inclui_media());
+    if w.len() < ordem_ar + n_parametros + 2 {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let mut inicio = vec![0.0; espec.n_coeficientes()];
+    if espec.inclui_media() {
+        inicio.push(w.iter().sum::<f64>() / w.len() as f64);
+    }
+
+    let objetivo_css = |theta: &[f64]| {
+        let c = Coeficientes::de_vetor(theta, espec);
+        let (ar, ma) = c.expandir(sazonal.periodo);
+        let e = residuos_css(&w, c.media, &ar, &ma);
+        e[ar.len()..].iter().map(|v| v * v).sum::<f64>() / (w.len() - ar.len()) as f64
+    };
+    let mut theta = minimizar(objetivo_css, &inicio);
+
+    if metodo == MetodoEstimacao::MaximaVerossimilhanca {
+        let c = Coeficientes::de_vetor(&theta, espec);
+        let (ar, ma) = c.expandir(sazonal.periodo);
+        if !eh_estacionario(&ar) || !eh_invertivel(&ma) {
+            theta = inicio.clone();
+        }
+
+        let objetivo_mv = |theta: &[f64]| {
+            let c = Coeficientes::de_vetor(theta, espec);
+            let (ar, ma) = c.expandir(sazonal.periodo);
+            if !eh_invertivel(&ma) {
+                return f64::INFINITY;
+            }
+            match filtro_kalman(&w, c.media, &ar, &ma) {
+                Some(k) => {
+                    let n = w.len() as f64;
+                    0.5 * ((k.soma_quadrados / n).ln() + k.soma_log_f / n)
+                }
+                None => f64::INFINITY,
+            }
+        };
+        theta = minimizar(objetivo_mv, &theta);
+    }
+
+    let c = Coeficientes::de_vetor(&theta, espec);
+    let (ar, ma) = c.expandir(sazonal.periodo);
+
+    let (residuos_w, sigma2, log_verossimilhanca, n_efetivo) = match metodo {
+        MetodoEstimacao::SomaQuadradosCondicional => {
+            let e = residuos_css(&w, c.media, &ar, &ma);
+            let n_efetivo = w.len() - ar.len();
+            let sigma2 = e.iter().map(|v| v * v).sum::<f64>() / n_efetivo as f64;
+            let ll = -0.5 * n_efetivo as f64 * ((2.0 * std::f64::consts::PI * sigma2).ln() + 1.0);
+            (e, sigma2, ll, n_efetivo)
+        }
+        MetodoEstimacao::MaximaVerossimilhanca => {
+            let k = filtro_kalman(&w, c.media, &ar, &ma).ok_or(RegressaoError::NaoConvergiu)?;
+            let n = w.len() as f64;
+            let sigma2 = k.soma_quadrados / n;
+            let ll = -0.5 * (n * (2.0 * std::f64::consts::PI * sigma2).ln() + n + k.soma_log_f);
+            (k.residuos, sigma2, ll, w.len())
+        }
+    };
+
+    if !sigma2.is_finite() || !log_verossimilhanca.is_finite() {
+        return Err(RegressaoError::NaoConvergiu);
+    }
+
+    let mut residuos = vec![0.0; ordem_dif];
+    residuos.extend(residuos_w);
+    let valores_ajustados = y.iter().zip(residuos.iter()).map(|(v, e)| v - e).collect();
+
+    let k = (n_parametros + 1) as f64;
+    Ok(ModeloArima {
+        especificacao: *espec,
+        metodo,
+        ar: c.ar,
+        ma: c.ma,
+        ar_sazonal: c.ar_sazonal,
+        ma_sazonal: c.ma_sazonal,
+        media: espec.inclui_media().then_some(c.media),
+        sigma2,
+        log_verossimilhanca,
+        aic: -2.0 * log_verossimilhanca + 2.0 * k,
+        bic: -2.0 * log_verossimilhanca + (n_efetivo as f64).ln() * k,
+        residuos,
+        valores_ajustados,
+        serie: y.to_vec(),
+    })
+}
+
+/// Seleciona as ordens (p, q) — e (P, Q), se houver parte sazonal — pelo menor AIC
+///
+/// As ordens de diferenciação `d` e `D` são fixas, pois o AIC não é comparável
+/// entre séries diferenciadas de modos distintos. Em `sazonal`, os campos `p`
+/// e `q` são interpretados como ordens máximas.
+pub fn selecionar_ordem_arima(
+    y: &[f64],
+    d: usize,
+    max_p: usize,
+    max_q: usize,
+    sazonal: Option<OrdemSazonal>,
+    metodo: MetodoEstimacao,
+) -> Resultado<ModeloArima> {
+    let (max_ps, max_qs) = sazonal.map_or((0, 0), |s| (s.p, s.q));
+    let mut melhor: Option<ModeloArima> = None;
+    let mut ultimo_erro = RegressaoError::DadosInsuficientes;
+
+    for p in 0..=max_p {
+        for q in 0..=max_q {
+            for ps in 0..=max_ps {
+                for qs in 0..=max_qs {
+                    let mut espec = EspecificacaoArima::arima(p, d, q);
+                    if let Some(s) = sazonal {
+                        espec = espec.com_sazonalidade(ps, s.d, qs, s.periodo);
+                    }
+                    match ajustar_arima(y, &espec, metodo) {
+                        Ok(modelo) => {
+                            if melhor.as_ref().is_none_or(|m| modelo.aic < m.aic) {
+                                melhor = Some(modelo);
+                            }
+                        }
+                        Err(e) => ultimo_erro = e,
+                    }
+                }
+            }
+        }
+    }
+
+    melhor.ok_or(ultimo_erro)
+}
+
+impl ModeloArima {
+    /// Prevê os próximos `horizonte` valores com intervalos de previsão ao `nivel` dado
+    ///
+    /// A variância do erro de previsão usa os pesos ψ do modelo integrado,
+    /// de modo que os intervalos crescem com o horizonte quando há raiz unitária.
+    pub fn prever(&self, horizonte: usize, nivel: f64) -> Resultado<PrevisaoArima> {
+        if !(nivel > 0.0 && nivel < 1.0) {
+            return Err(RegressaoError::ParametroInvalido);
+        }
+
+        let periodo = self.especificacao.sazonal_ou_vazia().periodo;
+        let coef = Coeficientes {
+            ar: self.ar.clone(),
+            ma: self.ma.clone(),
+            ar_sazonal: self.ar_sazonal.clone(),
+            ma_sazonal: self.ma_sazonal.clone(),
+            media: self.media.unwrap_or(0.0),
+        };
+        let (ar, ma) = coef.expandir(periodo);
+
+        // AR generalizado: φ(B)Φ(B^s)(1-B)^d(1-B^s)^D
+        let mut poli_ar = vec![1.0];
+        poli_ar.extend(ar.iter().map(|c| -c));
+        let poli_ar = multiplicar_polinomios(&poli_ar, &polinomio_diferenciacao(&self.especificacao));
+        let ar_total: Vec<f64> = poli_ar[1..].iter().map(|c| -c).collect();
+
+        let n = self.serie.len();
+        let mut z: Vec<f64> = self.serie.iter().map(|v| v - coef.media).collect();
+        let mut e = self.residuos.clone();
+
+        for t in n..n + horizonte {
+            let parte_ar: f64 = ar_total.iter().enumerate()
+                .filter(|(i, _)| t > *i)
+                .map(|(i, c)| c * z[t - i - 1])
+                .sum();
+            let parte_ma: f64 = ma.iter().enumerate()
+                .filter(|(j, _)| t > *j)
+                .map(|(j, c)| c * e[t - j - 1])
+                .sum();
+            z.push(parte_ar + parte_ma);
+            e.push(0.0);
+        }
+
+        // Pesos ψ da representação MA(∞)
+        let mut psi = vec![1.0];
+        for j in 1..horizonte {
+            let mut valor = ma.get(j - 1).copied().unwrap_or(0.0);
+            for i in 1..=j.min(ar_total.len()) {
+                valor += ar_total[i - 1] * psi[j - i];
+            }
+            psi.push(valor);
+        }
+
+        let quantil = normal_quantil(0.5 + nivel / 2.0);
+        let previsoes: Vec<f64> = z[n..].iter().map(|v| v + coef.media).collect();
+        let mut acumulado = 0.0;
+        let erro_padrao: Vec<f64> = psi.iter()
+            .map(|p| {
+                acumulado += p * p;
+                (self.sigma2 * acumulado).sqrt()
+            })
+            .collect();
+
+        Ok(PrevisaoArima {
+            limite_inferior: previsoes.iter().zip(&erro_padrao).map(|(p, s)| p - quantil * s).collect(),
+            limite_superior: previsoes.iter().zip(&erro_padrao).map(|(p, s)| p + quantil * s).collect(),
+            previsoes,
+            erro_padrao,
+            nivel,
+        })
+    }
+}
+
+impl fmt::Display for ModeloArima {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "=== Modelo {} ===", self.especificacao)?;
+        let grupos = [
+            ("ar", &self.ar),
+            ("ma", &self.ma),
+            ("sar", &self.ar_sazonal),
+            ("sma", &self.ma_sazonal),
+        ];
+        for (nome, coefs) in grupos {
+            for (i, c) in coefs.iter().enumerate() {
+                writeln!(f, "{}{}: {:.6}", nome, i + 1, c)?;
+            }
+        }
+        if let Some(media) = self.media {
+            writeln!(f, "Média: {:.6}", media)?;
+        }
+        writeln!(f, "σ²: {:.6}", self.sigma2)?;
+        writeln!(f, "Log-verossimilhança: {:.4}", self.log_verossimilhanca)?;
+        writeln!(f, "AIC: {:.4}", self.aic)?;
+        writeln!(f, "BIC: {:.4}", self.bic)?;
+        Ok(())
+    }
+}
+
+/// Minimiza com Nelder–Mead, reiniciando uma vez a partir do melhor ponto
+fn minimizar<F: Fn(&[f64]) -> f64>(f: F, inicio: &[f64]) -> Vec<f64> {
+    let max_iter = 400 * (inicio.len() + 1);
+    let (x, _) = nelder_mead(&f, inicio, 0.1, max_iter);
+    let (x, _) = nelder_mead(&f, &x, 0.05, max_iter);
+    x
+}
+
+/// Polinômio `1 - c_1 B^s - c_2 B^{2s} - ...`
+fn polinomio_ar(coef: &[f64], s: usize) -> Vec<f64> {
+    let mut poli = vec![0.0; coef.len() * s + 1];
+    poli[0] = 1.0;
+    for (i, c) in coef.iter().enumerate() {
+        poli[(i + 1) * s] = -c;
+    }
+    poli
+}
+
+/// Polinômio `1 + c_1 B^s + c_2 B^{2s} + ...`
+fn polinomio_ma(coef: &[f64], s: usize) -> Vec<f64> {
+    let mut poli = vec![0.0; coef.len() * s + 1];
+    poli[0] = 1.0;
+    for (i, c) in coef.iter().enumerate() {
+        poli[(i + 1) * s] = *c;
+    }
+    poli
+}
+
+fn multiplicar_polinomios(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut produto = vec![0.0; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            produto[i + j] += x * y;
+        }
+    }
+    produto
+}
+
+/// Polinômio `(1 - B)^d (1 - B^s)^D`
+fn polinomio_diferenciacao(espec: &EspecificacaoArima) -> Vec<f64> {
+    let s = espec.sazonal_ou_vazia();
+    let mut poli = vec![1.0];
+    for _ in 0..espec.d {
+        poli = multiplicar_polinomios(&poli, &[1.0, -1.0]);
+    }
+    for _ in 0..s.d {
+        poli = multiplicar_polinomios(&poli, &polinomio_ar(&[1.0], s.periodo));
+    }
+    poli
+}
+
+/// Aplica o filtro `Σ poli_i y_{t-i}`, descartando as primeiras observações
+fn aplicar_polinomio(y: &[f64], poli: &[f64]) -> Vec<f64> {
+    let ordem = poli.len() - 1;
+    (ordem..y.len())
+        .map(|t| poli.iter().enumerate().map(|(i, c)| c * y[t - i]).sum())
+        .collect()
+}
+
+/// Resíduos condicionais: os `ar.len()` primeiros são zero
+fn residuos_css(w: &[f64], media: f64, ar: &[f64], ma: &[f64]) -> Vec<f64> {
+    let mut e = vec![0.0; w.len()];
+    for t in ar.len()..w.len() {
+        let mut v = w[t] - media;
+        for (i, c) in ar.iter().enumerate() {
+            v -= c * (w[t - i - 1] - media);
+        }
+        for (j, c) in ma.iter().enumerate().take(t) {
+            v -= c * e[t - j - 1];
+        }
+        e[t] = v;
+    }
+    e
+}
+
+/// Testa a estacionariedade de `x_t = Σ c_i x_{t-i}` pela recursão de
+/// Durbin–Levinson reversa: todas as autocorrelações parciais em (-1, 1)
+pub(crate) fn eh_estacionario(coef: &[f64]) -> bool {
+    let mut a = coef.to_vec();
+    for k in (1..=a.len()).rev() {
+        let kappa = a[k - 1];
+        if kappa.abs() >= 1.0 || kappa.is_nan() {
+            return false;
+        }
+        let divisor = 1.0 - kappa * kappa;
+        a = (1..k).map(|j| (a[j - 1] + kappa * a[k - j - 1]) / divisor).collect();
+    }
+    true
+}
+
+fn eh_invertivel(ma: &[f64]) -> bool {
+    let negado: Vec<f64> = ma.iter().map(|c| -c).collect();
+    eh_estacionario(&negado)
+}
+
+struct SaidaKalman {
+    soma_quadrados: f64,
+    soma_log_f: f64,
+    residuos: Vec<f64>,
+}
+
+/// Verossimilhança exata (com σ² = 1) na representação em espaço de estados
+/// de Harvey. Retorna `None` se o modelo não for estacionário.
+fn filtro_kalman(w: &[f64], media: f64, ar: &[f64], ma: &[f64]) -> Option<SaidaKalman> {
+    if !eh_estacionario(ar) {
+        return None;
+    }
+
+    let r = ar.len().max(ma.len() + 1);
+    let mut phi = vec![0.0; r];
+    phi[..ar.len()].copy_from_slice(ar);
+    let mut g = vec![0.0; r];
+    g[0] = 1.0;
+    g[1..=ma.len()].copy_from_slice(ma);
+
+    // Covariância inicial: solução de P = T P T' + g g' pelo método da duplicação
+    let mut transicao = vec![vec![0.0; r]; r];
+    for i in 0..r {
+        transicao[i][0] = phi[i];
+        if i + 1 < r {
+            transicao[i][i + 1] = 1.0;
+        }
+    }
+    let mut p: Vec<Vec<f64>> = (0..r).map(|i| (0..r).map(|j| g[i] * g[j]).collect()).collect();
+    let mut a_pot = transicao;
+    let mut convergiu = false;
+    for _ in 0..64 {
+        let apa = produto_matrizes(&produto_matrizes(&a_pot, &p), &transposta(&a_pot));
+        for i in 0..r {
+            for j in 0..r {
+                p[i][j] += apa[i][j];
+            }
+        }
+        a_pot = produto_matrizes(&a_pot, &a_pot);
+        let norma = a_pot.iter().flatten().fold(0.0_f64, |m, v| m.max(v.abs()));
+        if norma < 1e-14 {
+            convergiu = true;
+            break;
+        }
+    }
+    if !convergiu {
+        return None;
+    }
+
+    let mut a = vec![0.0; r];
+    let mut m = vec![vec![0.0; r]; r];
+    let mut saida = SaidaKalman {
+        soma_quadrados: 0.0,
+        soma_log_f: 0.0,
+        residuos: Vec::with_capacity(w.len()),
+    };
+
+    for &wt in w {
+        let f = p[0][0];
+        if f <= 0.0 || !f.is_finite() {
+            return None;
+        }
+        let v = wt - media - a[0];
+        saida.soma_quadrados += v * v / f;
+        saida.soma_log_f += f.ln();
+        saida.residuos.push(v);
+
+        // Atualização
+        let linha0 = p[0].clone();
+        for i in 0..r {
+            let ganho = p[i][0] / f;
+            a[i] += ganho * v;
+            for j in 0..r {
+                p[i][j] -= ganho * linha0[j];
+            }
+        }
+
+        // Predição: a = T a, P = T P T' + g g'
+        let a0 = a[0];
+        for i in 0..r {
+            a[i] = phi[i] * a0 + if i + 1 < r { a[i + 1] } else { 0.0 };
+        }
+        for i in 0..r {
+            for j in 0..r {
+                m[i][j] = phi[i] * p[0][j] + if i + 1 < r { p[i + 1][j] } else { 0.0 };
+            }
+        }
+        for i in 0..r {
+            for j in 0..r {
+                p[i][j] = phi[j] * m[i][0] + if j + 1 < r { m[i][j + 1] } else { 0.0 } + g[i] * g[j];
+            }
+        }
+    }
+
+    Some(saida)
+}
+
+fn produto_matrizes(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = a.len();
+    let mut c = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for k in 0..n {
+            let aik = a[i][k];
+            if aik != 0.0 {
+                for j in 0..n {
+                    c[i][j] += aik * b[k][j];
+                }
+            }
+        }
+    }
+    c
+}
+
+fn transposta(a: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = a.len();
+    (0..n).map(|i| (0..n).map(|j| a[j][i]).collect()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teste_util::Gerador;
+    use crate::calcular_mse;
+
+    /// Simula um ARMA com aquecimento de 200 observações descartadas
+    fn simular_arma(ar: &[f64], ma: &[f64], n: usize, semente: u64) -> Vec<f64> {
+        let mut gerador = Gerador::new(semente);
+        let total = n + 200;
+        let e = gerador.ruido(total, 1.0);
+        let mut y = vec![0.0; total];
+        for t in 0..total {
+            let mut v = e[t];
+            for (i, c) in ar.iter().enumerate() {
+                if t > i { v += c * y[t - i - 1]; }
+            }
+            for (j, c) in ma.iter().enumerate() {
+                if t > j { v += c * e[t - j - 1]; }
+            }
+            y[t] = v;
+        }
+        y[200..].to_vec()
+    }
+
+    #[test]
+    fn test_ar1_css_e_verossimilhanca() {
+        let y = simular_arma(&[0.7], &[], 500, 7);
+
+        let css = ajustar_arima(&y, &EspecificacaoArima::ar(1), MetodoEstimacao::SomaQuadradosCondicional).unwrap();
+        let mv = ajustar_arima(&y, &EspecificacaoArima::ar(1), MetodoEstimacao::MaximaVerossimilhanca).unwrap();
+
+        assert!((css.ar[0] - 0.7).abs() < 0.08, "CSS: {}", css.ar[0]);
+        assert!((mv.ar[0] - 0.7).abs() < 0.08, "MV: {}", mv.ar[0]);
+        assert!((mv.sigma2 - 1.0).abs() < 0.15);
+        assert!(mv.media.unwrap().abs() < 0.5);
+    }
+
+    #[test]
+    fn test_ma1_verossimilhanca() {
+        let y = simular_arma(&[], &[0.5], 600, 11);
+        let modelo = ajustar_arima(&y, &EspecificacaoArima::ma(1), MetodoEstimacao::MaximaVerossimilhanca).unwrap();
+
+        assert!((modelo.ma[0] - 0.5).abs() < 0.1, "θ = {}", modelo.ma[0]);
+    }
+
+    #[test]
+    fn test_sarima_ar_sazonal() {
+        let mut ar = vec![0.0; 4];
+        ar[3] = 0.8;
+        let y = simular_arma(&ar, &[], 400, 3);
+        let espec = EspecificacaoArima::arima(0, 0, 0).com_sazonalidade(1, 0, 0, 4);
+        let modelo = ajustar_arima(&y, &espec, MetodoEstimacao::MaximaVerossimilhanca).unwrap();
+
+        assert!((modelo.ar_sazonal[0] - 0.8).abs() < 0.08, "Φ = {}", modelo.ar_sazonal[0]);
+    }
+
+    #[test]
+    fn test_passeio_aleatorio_previsao() {
+        let incrementos = Gerador::new(5).ruido(300, 1.0);
+        let y: Vec<f64> = incrementos.iter()
+            .scan(0.0, |s, e| { *s += e; Some(*s) })
+            .collect();
+        let modelo = ajustar_arima(&y, &EspecificacaoArima::arima(0, 1, 0), MetodoEstimacao::MaximaVerossimilhanca).unwrap();
+        let previsao = modelo.prever(4, 0.95).unwrap();
+
+        for p in &previsao.previsoes {
+            assert!((p - y[y.len() - 1]).abs() < 1e-9);
+        }
+        // Variância cresce linearmente com o horizonte
+        let razao = previsao.erro_padrao[3] / previsao.erro_padrao[0];
+        assert!((razao - 2.0).abs() < 1e-9);
+        let largura = previsao.limite_superior[0] - previsao.limite_inferior[0];
+        assert!((largura - 2.0 * 1.959_964 * modelo.sigma2.sqrt()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_residuos_compativeis_com_metricas() {
+        let y = simular_arma(&[0.5], &[0.3], 200, 21);
+        let modelo = ajustar_arima(&y, &EspecificacaoArima::arma(1, 1), MetodoEstimacao::SomaQuadradosCondicional).unwrap();
+
+        assert_eq!(modelo.residuos.len(), y.len());
+        let mse = calcular_mse(&y, &modelo.valores_ajustados).unwrap();
+        let esperado = modelo.residuos.iter().map(|e| e * e).sum::<f64>() / y.len() as f64;
+        assert!((mse - esperado).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_residuos_mv_na_escala_original() {
+        // Sem passado, a previsão um passo à frente da primeira observação é a
+        // média, e a do segundo passo é a recursão AR(1) a partir dela
+        let y: Vec<f64> = simular_arma(&[0.7], &[], 300, 5).iter().map(|v| 10.0 + 5.0 * v).collect();
+        let modelo = ajustar_arima(&y, &EspecificacaoArima::arma(1, 0), MetodoEstimacao::MaximaVerossimilhanca).unwrap();
+        let media = modelo.media.unwrap();
+        let phi = modelo.ar[0];
+
+        assert!((modelo.valores_ajustados[0] - media).abs() < 1e-9);
+        assert!((modelo.residuos[0] - (y[0] - media)).abs() < 1e-9);
+        assert!((modelo.valores_ajustados[1] - (media + phi * (y[0] - media))).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_selecao_ordem_por_aic() {
+        let y = simular_arma(&[0.6, -0.3], &[], 400, 13);
+        let melhor = selecionar_ordem_arima(&y, 0, 2, 1, None, MetodoEstimacao::MaximaVerossimilhanca).unwrap();
+        let ruido_branco = ajustar_arima(&y, &EspecificacaoArima::arma(0, 0), MetodoEstimacao::MaximaVerossimilhanca).unwrap();
+
+        assert!(melhor.aic <= ruido_branco.aic);
+        assert!(melhor.especificacao.p >= 1);
+    }
+
+    #[test]
+    fn test_arima_erros() {
+        let y = vec![1.0, 2.0, 3.0];
+        assert!(matches!(
+            ajustar_arima(&y, &EspecificacaoArima::arma(2, 1), MetodoEstimacao::SomaQuadradosCondicional),
+            Err(RegressaoError::DadosInsuficientes)
+        ));
+
+        let y = simular_arma(&[0.5], &[], 50, 1);
+        let modelo = ajustar_arima(&y, &EspecificacaoArima::ar(1), MetodoEstimacao::SomaQuadradosCondicional).unwrap();
+        assert!(matches!(modelo.prever(3, 1.5), Err(RegressaoError::ParametroInvalido)));
+    }
+
+    #[test]
+    fn test_estacionariedade() {
+        assert!(eh_estacionario(&[1.5, -0.75]));
+        assert!(!eh_estacionario(&[0.5, 0.6]));
+        assert!(!eh_estacionario(&[1.0]));
+    }
+}
@@ -0,0 +1,381 @@
+//! # Funções de autocorrelação
+//!
+//! Autocorrelação (ACF), autocorrelação parcial (PACF, recursão de
+//! Durbin–Levinson) e correlação cruzada (CCF), com limites de confiança
+//! aproximados. Para séries longas as autocovariâncias são obtidas por FFT.
+
+use crate::distribuicoes::normal_quantil;
+use crate::soma;
+use crate::{RegressaoError, Resultado};
+
+/// Acima deste custo (n × número de defasagens) a FFT substitui a soma direta
+const LIMIAR_FFT: usize = 1 << 16;
+
+/// Correlações estimadas por defasagem
+#[derive(Debug, Clone)]
+pub struct Correlograma {
+    /// Defasagens correspondentes a cada valor (negativas apenas na CCF)
+    pub defasagens: Vec<isize>,
+    pub valores: Vec<f64>,
+    /// Número de observações usadas na estimação
+    pub n: usize,
+}
+
+impl Correlograma {
+    /// Limite ±z/√n sob a hipótese de ruído branco
+    pub fn limite_ruido_branco(&self, nivel: f64) -> f64 {
+        normal_quantil(0.5 + nivel / 2.0) / (self.n as f64).sqrt()
+    }
+
+    /// Limites de Bartlett para a ACF, supondo um MA(k-1) na defasagem k
+    ///
+    /// `se_k = sqrt((1 + 2 Σ_{j<k} r_j²) / n)`. Só faz sentido para o
+    /// correlograma retornado por [`autocorrelacao`].
+    pub fn limites_bartlett(&self, nivel: f64) -> Vec<f64> {
+        let z = normal_quantil(0.5 + nivel / 2.0);
+        let n = self.n as f64;
+        let mut soma = 0.0;
+        self.defasagens.iter().zip(&self.valores)
+            .map(|(&k, &r)| {
+                let limite = if k == 0 { 0.0 } else { z * ((1.0 + 2.0 * soma) / n).sqrt() };
+                if k > 0 {
+                    soma += r * r;
+                }
+                limite
+            })
+            .collect()
+    }
+
+    /// Defasagens cujo valor excede o limite de ruído branco (exceto a defasagem 0)
+    pub fn significativas(&self, nivel: f64) -> Vec<isize> {
+        let limite = self.limite_ruido_branco(nivel);
+        self.defasagens.iter().zip(&self.valores)
+            .filter(|(&k, r)| k != 0 && r.abs() > limite)
+            .map(|(&k, _)| k)
+            .collect()
+    }
+}
+
+/// Calcula a função de autocorrelação para as defasagens `0..=max_defasagem`
+///
+/// # Argumentos
+/// * `x` - Série temporal
+/// * `max_defasagem` - Maior defasagem (deve ser menor que o tamanho da série)
+///
+/// # Retorna
+/// * `Ok(Correlograma)` - Com `valores[0] = 1`
+/// * `Err(RegressaoError)` - Em caso de série vazia, curta ou constante
+pub fn autocorrelacao(x: &[f64], max_defasagem: usize) -> Resultado<Correlograma> {
+    validar(x, max_defasagem)?;
+    let gama = autocovariancias(x, max_defasagem);
+    if soma::variancia_desprezivel(gama[0] * x.len() as f64, x.len(), soma::maior_absoluto(x)) {
+        return Err(RegressaoError::VarianciaZero);
+    }
+
+    Ok(Correlograma {
+        defasagens: (0..=max_defasagem as isize).collect(),
+        valores: gama.iter().map(|g| g / gama[0]).collect(),
+        n: x.len(),
+    })
+}
+
+/// Calcula a autocorrelação parcial para as defasagens `1..=max_defasagem`
+/// pela recursão de Durbin–Levinson sobre a ACF amostral
+pub fn autocorrelacao_parcial(x: &[f64], max_defasagem: usize) -> Resultado<Correlograma> {
+    if max_defasagem == 0 {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    let acf = autocorrelacao(x, max_defasagem)?;
+    let r = &acf.valores;
+
+    let mut pacf = Vec::with_capacity(max_defasagem);
+    let mut phi: Vec<f64> = Vec::with_capacity(max_defasagem);
+    for k in 1..=max_defasagem {
+        let num = r[k] - phi.iter().enumerate().map(|(j, p)| p * r[k - j - 1]).sum::<f64>();
+        let den = 1.0 - phi.iter().enumerate().map(|(j, p)| p * r[j + 1]).sum::<f64>();
+        let kappa = num / den;
+        let anterior = phi.clone();
+        for j in 0..phi.len() {
+            phi[j] = anterior[j] - kappa * anterior[k - j - 2];
+        }
+        phi.push(kappa);
+        pacf.push(kappa);
+    }
+
+    Ok(Correlograma {
+        defasagens: (1..=max_defasagem as isize).collect(),
+        valores: pacf,
+        n: x.len(),
+    })
+}
+
+/// Calcula a correlação cruzada entre `x` e `y` para as defasagens
+/// `-max_defasagem..=max_defasagem`
+///
+/// O valor na defasagem `k` estima `cor(x_{t+k}, y_t)`, a mesma convenção
+/// da função `ccf` do R.
+pub fn correlacao_cruzada(x: &[f64], y: &[f64], max_defasagem: usize) -> Resultado<Correlograma> {
+    if x.len() != y.len() {
+        return Err(RegressaoError::TamanhosDiferentes);
+    }
+    validar(x, max_defasagem)?;
+
+    let n = x.len();
+    let cx = centralizar(x);
+    let cy = centralizar(y);
+    let var_x = cx.iter().map(|v| v * v).sum::<f64>();
+    let var_y = cy.iter().map(|v| v * v).sum::<f64>();
+    if soma::variancia_desprezivel(var_x, n, soma::maior_absoluto(x))
+        || soma::variancia_desprezivel(var_y, n, soma::maior_absoluto(y))
+    {
+        return Err(RegressaoError::VarianciaZero);
+    }
+    let escala = (var_x * var_y).sqrt();
+
+    let (positivas, negativas) = if usar_fft(n, max_defasagem) {
+        let cruzada = covariancias_cruzadas_fft(&cx, &cy);
+        let tamanho = cruzada.len();
+        let positivas: Vec<f64> = (0..=max_defasagem).map(|k| cruzada[k]).collect();
+        let negativas: Vec<f64> = (1..=max_defasagem).map(|k| cruzada[tamanho - k]).collect();
+        (positivas, negativas)
+    } else {
+        let soma = |a: &[f64], b: &[f64], k: usize| -> f64 {
+            (0..n - k).map(|t| a[t + k] * b[t]).sum()
+        };
+        let positivas = (0..=max_defasagem).map(|k| soma(&cx, &cy, k)).collect();
+        let negativas = (1..=max_defasagem).map(|k| soma(&cy, &cx, k)).collect();
+        (positivas, negativas)
+    };
+
+    let mut defasagens = Vec::with_capacity(2 * max_defasagem + 1);
+    let mut valores = Vec::with_capacity(2 * max_defasagem + 1);
+    for k in (1..=max_defasagem).rev() {
+        defasagens.push(-(k as isize));
+        valores.push(negativas[k - 1] / escala);
+    }
+    for (k, v) in positivas.iter().enumerate() {
+        defasagens.push(k as isize);
+        valores.push(v / escala);
+    }
+
+    Ok(Correlograma { defasagens, valores, n })
+}
+
+fn validar(x: &[f64], max_defasagem: usize) -> Resultado<()> {
+    if x.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if x.len() < 2 {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+    if max_defasagem >= x.len() {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    Ok(())
+}
+
+fn centralizar(x: &[f64]) -> Vec<f64> {
+    let media = soma::media(x);
+    x.iter().map(|v| v - media).collect()
+}
+
+fn usar_fft(n: usize, max_defasagem: usize) -> bool {
+    n.saturating_mul(max_defasagem + 1) > LIMIAR_FFT
+}
+
+/// Autocovariâncias amostrais (divisor n) para as defasagens `0..=max_defasagem`
+pub(crate) fn autocovariancias(x: &[f64], max_defasagem: usize) -> Vec<f64> {
+    let n = x.len();
+    let c = centralizar(x);
+    let somas: Vec<f64> = if usar_fft(n, max_defasagem) {
+        covariancias_cruzadas_fft(&c, &c)[..=max_defasagem].to_vec()
+    } else {
+        (0..=max_defasagem)
+            .map(|k| (0..n - k).map(|t| c[t] * c[t + k]).sum())
+            .collect()
+    };
+    somas.iter().map(|s| s / n as f64).collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Complexo {
+    re: f64,
+    im: f64,
+}
+
+impl Complexo {
+    fn mul(self, outro: Complexo) -> Complexo {
+        Complexo {
+            re: self.re * outro.re - self.im * outro.im,
+            im: self.re * outro.im + self.im * outro.re,
+        }
+    }
+}
+
+/// Somas `Σ_t a_{t+k} b_t` (defasagens positivas nos primeiros índices,
+/// negativas a partir do fim) com preenchimento de zeros contra aliasing
+fn covariancias_cruzadas_fft(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let tamanho = (2 * a.len()).next_power_of_two();
+    let preencher = |x: &[f64]| -> Vec<Complexo> {
+        let mut v = vec![Complexo { re: 0.0, im: 0.0 }; tamanho];
+        for (c, &valor) in v.iter_mut().zip(x) {
+            c.re = valor;
+        }
+        v
+    };
+
+    let mut fa = preencher(a);
+    let mut fb = preencher(b);
+    fft(&mut fa, false);
+    fft(&mut fb, false);
+    let mut produto: Vec<Complexo> = fa.iter().zip(&fb)
+        .map(|(x, y)| x.mul(Complexo { re: y.re, im: -y.im }))
+        .collect();
+    fft(&mut produto, true);
+
+    produto.iter().map(|c| c.re / tamanho as f64).collect()
+}
+
+/// FFT radix-2 iterativa in-place (tamanho potência de 2, inversa sem normalizar)
+fn fft(dados: &mut [Complexo], inversa: bool) {
+    let n = dados.len();
+
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            dados.swap(i, j);
+        }
+    }
+
+    let sinal = if inversa { 1.0 } else { -1.0 };
+    let mut comprimento = 2;
+    while comprimento <= n {
+        let angulo = sinal * 2.0 * std::f64::consts::PI / comprimento as f64;
+        let raiz = Complexo { re: angulo.cos(), im: angulo.sin() };
+        for inicio in (0..n).step_by(comprimento) {
+            let mut w = Complexo { re: 1.0, im: 0.0 };
+            for k in 0..comprimento / 2 {
+                let u = dados[inicio + k];
+                let v = dados[inicio + k + comprimento / 2].mul(w);
+                dados[inicio + k] = Complexo { re: u.re + v.re, im: u.im + v.im };
+                dados[inicio + k + comprimento / 2] = Complexo { re: u.re - v.re, im: u.im - v.im };
+                w = w.mul(raiz);
+            }
+        }
+        comprimento <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teste_util::Gerador;
+
+    #[test]
+    fn test_acf_valores_conhecidos() {
+        let x = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let acf = autocorrelacao(&x, 2).unwrap();
+
+        // Valores de referência do R: acf(1:5, lag.max = 2)
+        assert!((acf.valores[0] - 1.0).abs() < 1e-12);
+        assert!((acf.valores[1] - 0.4).abs() < 1e-12);
+        assert!((acf.valores[2] - (-0.1)).abs() < 1e-12);
+        assert!((acf.limite_ruido_branco(0.95) - 1.959_964 / 5f64.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_acf_fft_igual_direta() {
+        let x = Gerador::new(9).ruido(3000, 1.0);
+        let direta = autocovariancias(&x, 10);
+        let via_fft = covariancias_cruzadas_fft(&centralizar(&x), &centralizar(&x));
+
+        for k in 0..=10 {
+            assert!((direta[k] - via_fft[k] / x.len() as f64).abs() < 1e-10);
+        }
+        assert!(usar_fft(x.len(), 40));
+        let acf = autocorrelacao(&x, 40).unwrap();
+        assert!(acf.valores[1..].iter().all(|r| r.abs() < 0.1));
+    }
+
+    #[test]
+    fn test_pacf_ar1() {
+        let mut gerador = Gerador::new(4);
+        let mut x = vec![0.0; 2000];
+        for t in 1..x.len() {
+            x[t] = 0.6 * x[t - 1] + gerador.normal();
+        }
+        let pacf = autocorrelacao_parcial(&x, 5).unwrap();
+
+        assert!((pacf.valores[0] - 0.6).abs() < 0.05);
+        assert_eq!(pacf.significativas(0.99), vec![1]);
+    }
+
+    #[test]
+    fn test_ccf_defasagem_detectada() {
+        let y = Gerador::new(8).ruido(500, 1.0);
+        // x_{t+3} = y_t
+        let mut x = vec![0.0; 500];
+        x[3..].copy_from_slice(&y[..497]);
+        let ccf = correlacao_cruzada(&x, &y, 5).unwrap();
+
+        let indice = ccf.defasagens.iter().position(|&k| k == 3).unwrap();
+        assert!(ccf.valores[indice] > 0.9);
+        assert_eq!(ccf.defasagens.len(), 11);
+        assert!(ccf.valores.iter().enumerate().all(|(i, r)| i == indice || r.abs() < 0.2));
+    }
+
+    #[test]
+    fn test_ccf_fft_igual_direta() {
+        // x_t = y_{t-3} + 0.5·y_{t+2} + ruído: picos nas defasagens 3 e -2
+        let mut gerador = Gerador::new(10);
+        let y = gerador.ruido(3000, 1.0);
+        let x: Vec<f64> = (0..y.len())
+            .map(|t| {
+                let atrasado = if t >= 3 { y[t - 3] } else { 0.0 };
+                let adiantado = if t + 2 < y.len() { y[t + 2] } else { 0.0 };
+                atrasado + 0.5 * adiantado + 0.3 * gerador.normal()
+            })
+            .collect();
+        let max_defasagem = 40;
+        assert!(usar_fft(x.len(), max_defasagem));
+        let ccf = correlacao_cruzada(&x, &y, max_defasagem).unwrap();
+
+        let (cx, cy) = (centralizar(&x), centralizar(&y));
+        let escala = (cx.iter().map(|v| v * v).sum::<f64>() * cy.iter().map(|v| v * v).sum::<f64>()).sqrt();
+        for (&k, &valor) in ccf.defasagens.iter().zip(&ccf.valores) {
+            let (a, b) = if k >= 0 { (&cx, &cy) } else { (&cy, &cx) };
+            let atraso = k.unsigned_abs();
+            let direta = (0..x.len() - atraso).map(|t| a[t + atraso] * b[t]).sum::<f64>() / escala;
+            assert!((valor - direta).abs() < 1e-10, "defasagem {}: {} contra {}", k, valor, direta);
+        }
+        let em = |k: isize| ccf.valores[ccf.defasagens.iter().position(|&d| d == k).unwrap()];
+        assert!(em(3) > 0.7 && em(-2) > 0.3);
+    }
+
+    #[test]
+    fn test_autocorrelacao_erros() {
+        assert!(matches!(autocorrelacao(&[], 1), Err(RegressaoError::DadosVazios)));
+        assert!(matches!(autocorrelacao(&[1.0, 2.0], 2), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(autocorrelacao(&[3.0; 4], 1), Err(RegressaoError::VarianciaZero)));
+        assert!(matches!(correlacao_cruzada(&[1.0, 2.0, 3.0], &[1.7e9; 3], 1), Err(RegressaoError::VarianciaZero)));
+    }
+
+    #[test]
+    fn test_escala_minuscula_nao_e_variancia_zero() {
+        // Variância ~1e-18 é pequena em termos absolutos, não relativa aos dados
+        let x: Vec<f64> = [1.0, 2.0, 3.0, 4.0, 5.0].iter().map(|v| v * 1e-9).collect();
+        let acf = autocorrelacao(&x, 2).unwrap();
+        assert!((acf.valores[1] - 0.4).abs() < 1e-12);
+        assert!((acf.valores[2] + 0.1).abs() < 1e-12);
+
+        let y = Gerador::new(8).ruido(200, 1e-9);
+        let ccf = correlacao_cruzada(&y, &y, 2).unwrap();
+        assert!((ccf.valores[2] - 1.0).abs() < 1e-12);
+    }
+}
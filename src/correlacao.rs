@@ -0,0 +1,407 @@
+//! # Correlação e covariância
+//!
+//! Coeficientes de Pearson, Spearman e tau-b de Kendall com valor-p e
+//! intervalo de confiança pela transformação z de Fisher, além de matrizes
+//! de correlação e de covariância para várias colunas. O tau-b usa o
+//! algoritmo de Knight, O(n log n), com correção para empates.
+
+use std::fmt;
+
+use crate::descritivas::{postos, GrausLiberdade};
+use crate::distribuicoes::{beta_incompleta_regularizada, normal_cdf, normal_quantil};
+use crate::numerico::Flutuante;
+use crate::soma;
+use crate::{RegressaoError, Resultado};
+
+/// Coeficiente de correlação
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetodoCorrelacao {
+    /// Associação linear
+    Pearson,
+    /// Pearson entre os postos: associação monotônica
+    Spearman,
+    /// Tau-b de Kendall: pares concordantes menos discordantes, com empates
+    Kendall,
+}
+
+/// Coeficiente de correlação com teste de nulidade e intervalo de confiança
+#[derive(Debug, Clone)]
+pub struct Correlacao {
+    pub metodo: MetodoCorrelacao,
+    pub coeficiente: f64,
+    /// `t` com `n - 2` graus de liberdade (Pearson e Spearman) ou `z` (Kendall)
+    pub estatistica: f64,
+    /// Valor-p bilateral de `H0: coeficiente = 0`
+    pub valor_p: f64,
+    /// Limites pela transformação z de Fisher; `NaN` se a amostra for pequena
+    pub limite_inferior: f64,
+    pub limite_superior: f64,
+    /// Nível de confiança do intervalo (ex.: 0.95)
+    pub nivel: f64,
+    pub n: usize,
+}
+
+impl fmt::Display for Correlacao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nome = match self.metodo {
+            MetodoCorrelacao::Pearson => "Pearson (r)",
+            MetodoCorrelacao::Spearman => "Spearman (rho)",
+            MetodoCorrelacao::Kendall => "Kendall (tau-b)",
+        };
+        writeln!(f, "=== Correlação de {} ===", nome)?;
+        writeln!(f, "Coeficiente: {:.6}", self.coeficiente)?;
+        writeln!(f, "Estatística: {:.6}", self.estatistica)?;
+        writeln!(f, "Valor-p: {:.6}", self.valor_p)?;
+        writeln!(
+            f,
+            "Intervalo de {:.0}%: [{:.6}, {:.6}]",
+            self.nivel * 100.0, self.limite_inferior, self.limite_superior
+        )?;
+        writeln!(f, "Observações: {}", self.n)?;
+        Ok(())
+    }
+}
+
+fn validar_pares<X: Flutuante, Y: Flutuante>(x: &[X], y: &[Y]) -> Resultado<()> {
+    if x.is_empty() || y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if x.len() != y.len() {
+        return Err(RegressaoError::TamanhosDiferentes);
+    }
+    if x.len() < 3 {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+    Ok(())
+}
+
+fn pearson<X: Flutuante, Y: Flutuante>(x: &[X], y: &[Y]) -> Resultado<f64> {
+    let (_, _, sxx, sxy, syy) = soma::somas_centradas(x, y);
+    if soma::variancia_desprezivel(sxx, x.len(), soma::maior_absoluto(x))
+        || soma::variancia_desprezivel(syy, y.len(), soma::maior_absoluto(y))
+    {
+        return Err(RegressaoError::VarianciaZero);
+    }
+    Ok((sxy / (sxx * syy).sqrt()).clamp(-1.0, 1.0))
+}
+
+/// Soma de `t(t-1)`, `t(t-1)(t-2)`, `t(t-1)(2t+5)` e dos pares empatados
+/// `t(t-1)/2` sobre os grupos de valores iguais de uma sequência ordenada
+#[derive(Default)]
+struct Empates {
+    pares: f64,
+    v1: f64,
+    v2: f64,
+    v5: f64,
+}
+
+impl Empates {
+    fn de_ordenados(ordenados: &[f64]) -> Self {
+        let mut empates = Empates::default();
+        for grupo in ordenados.chunk_by(|a, b| a == b) {
+            let t = grupo.len() as f64;
+            empates.pares += t * (t - 1.0) / 2.0;
+            empates.v1 += t * (t - 1.0);
+            empates.v2 += t * (t - 1.0) * (t - 2.0);
+            empates.v5 += t * (t - 1.0) * (2.0 * t + 5.0);
+        }
+        empates
+    }
+}
+
+/// Ordena `v` por intercalação e conta as inversões estritas
+fn ordenar_contando_trocas(v: &mut [f64], auxiliar: &mut [f64]) -> u64 {
+    let n = v.len();
+    if n < 2 {
+        return 0;
+    }
+    let meio = n / 2;
+    let mut trocas = ordenar_contando_trocas(&mut v[..meio], &mut auxiliar[..meio])
+        + ordenar_contando_trocas(&mut v[meio..], &mut auxiliar[meio..]);
+
+    let (mut i, mut j) = (0, meio);
+    for destino in auxiliar[..n].iter_mut() {
+        if j >= n || (i < meio && v[i] <= v[j]) {
+            *destino = v[i];
+            i += 1;
+        } else {
+            *destino = v[j];
+            trocas += (meio - i) as u64;
+            j += 1;
+        }
+    }
+    v.copy_from_slice(&auxiliar[..n]);
+    trocas
+}
+
+/// Tau-b de Kendall, `S = C - D` e a variância de `S` sob independência
+fn kendall<X: Flutuante, Y: Flutuante>(x: &[X], y: &[Y]) -> Resultado<(f64, f64, f64)> {
+    let n = x.len();
+    let (valor_x, valor_y) = (|i: usize| x[i].para_f64(), |i: usize| y[i].para_f64());
+    let mut ordem: Vec<usize> = (0..n).collect();
+    ordem.sort_by(|&a, &b| valor_x(a).total_cmp(&valor_x(b)).then(valor_y(a).total_cmp(&valor_y(b))));
+
+    let x_ordenado: Vec<f64> = ordem.iter().map(|&i| valor_x(i)).collect();
+    let empates_x = Empates::de_ordenados(&x_ordenado);
+    let mut empates_conjuntos = 0.0;
+    for grupo in ordem.chunk_by(|&a, &b| valor_x(a) == valor_x(b) && valor_y(a) == valor_y(b)) {
+        let t = grupo.len() as f64;
+        empates_conjuntos += t * (t - 1.0) / 2.0;
+    }
+
+    let mut y_ordenado: Vec<f64> = ordem.iter().map(|&i| valor_y(i)).collect();
+    let mut auxiliar = vec![0.0; n];
+    let trocas = ordenar_contando_trocas(&mut y_ordenado, &mut auxiliar) as f64;
+    let empates_y = Empates::de_ordenados(&y_ordenado);
+
+    let nf = n as f64;
+    let pares = nf * (nf - 1.0) / 2.0;
+    let denominador = ((pares - empates_x.pares) * (pares - empates_y.pares)).sqrt();
+    if denominador == 0.0 {
+        return Err(RegressaoError::VarianciaZero);
+    }
+    let s = pares - empates_x.pares - empates_y.pares + empates_conjuntos - 2.0 * trocas;
+
+    let variancia = (nf * (nf - 1.0) * (2.0 * nf + 5.0) - empates_x.v5 - empates_y.v5) / 18.0
+        + empates_x.v1 * empates_y.v1 / (2.0 * nf * (nf - 1.0))
+        + empates_x.v2 * empates_y.v2 / (9.0 * nf * (nf - 1.0) * (nf - 2.0));
+    Ok(((s / denominador).clamp(-1.0, 1.0), s, variancia))
+}
+
+/// Calcula apenas o coeficiente de correlação entre `x` e `y`
+pub fn coeficiente_correlacao<X: Flutuante, Y: Flutuante>(x: &[X], y: &[Y], metodo: MetodoCorrelacao) -> Resultado<f64> {
+    validar_pares(x, y)?;
+    match metodo {
+        MetodoCorrelacao::Pearson => pearson(x, y),
+        MetodoCorrelacao::Spearman => pearson(&postos(x), &postos(y)),
+        MetodoCorrelacao::Kendall => Ok(kendall(x, y)?.0),
+    }
+}
+
+/// Correlação entre `x` e `y` com valor-p e intervalo de confiança
+///
+/// Pearson e Spearman usam `t = r √((n-2)/(1-r²))`; Kendall usa a
+/// aproximação normal de `S` com variância corrigida para empates. Os
+/// intervalos aplicam a transformação z de Fisher com erro padrão
+/// `1/√(n-3)` (Pearson), `√(1.06/(n-3))` (Spearman) e `√(0.437/(n-4))`
+/// (Kendall), as aproximações de Fieller, Hartley e Pearson (1957).
+///
+/// # Argumentos
+/// * `x`, `y` - Amostras pareadas
+/// * `metodo` - Coeficiente de correlação
+/// * `nivel` - Nível de confiança do intervalo, em (0, 1)
+pub fn correlacao<X: Flutuante, Y: Flutuante>(
+    x: &[X],
+    y: &[Y],
+    metodo: MetodoCorrelacao,
+    nivel: f64,
+) -> Resultado<Correlacao> {
+    validar_pares(x, y)?;
+    if !(nivel > 0.0 && nivel < 1.0) {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    let n = x.len();
+    let nf = n as f64;
+
+    let (coeficiente, estatistica, valor_p) = match metodo {
+        MetodoCorrelacao::Kendall => {
+            let (tau, s, variancia) = kendall(x, y)?;
+            let z = if variancia > 0.0 { s / variancia.sqrt() } else { 0.0 };
+            (tau, z, 2.0 * (1.0 - normal_cdf(z.abs())))
+        }
+        _ => {
+            let r = coeficiente_correlacao(x, y, metodo)?;
+            let gl = nf - 2.0;
+            let t = r * (gl / (1.0 - r * r)).sqrt();
+            // P(|T| > |t|) = I_{gl/(gl+t²)}(gl/2, 1/2)
+            let valor_p = if t.is_finite() { beta_incompleta_regularizada(gl / (gl + t * t), gl / 2.0, 0.5) } else { 0.0 };
+            (r, t, valor_p)
+        }
+    };
+
+    let (variancia_z, minimo) = match metodo {
+        MetodoCorrelacao::Pearson => (1.0, 4),
+        MetodoCorrelacao::Spearman => (1.06, 4),
+        MetodoCorrelacao::Kendall => (0.437, 5),
+    };
+    let (limite_inferior, limite_superior) = if n >= minimo {
+        let erro = (variancia_z / (nf - (minimo - 1) as f64)).sqrt();
+        let meia_largura = normal_quantil(0.5 + nivel / 2.0) * erro;
+        let z = coeficiente.atanh();
+        ((z - meia_largura).tanh(), (z + meia_largura).tanh())
+    } else {
+        (f64::NAN, f64::NAN)
+    };
+
+    Ok(Correlacao { metodo, coeficiente, estatistica, valor_p, limite_inferior, limite_superior, nivel, n })
+}
+
+fn validar_colunas(colunas: &[Vec<f64>]) -> Resultado<()> {
+    if colunas.is_empty() || colunas[0].is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if colunas.iter().any(|c| c.len() != colunas[0].len()) {
+        return Err(RegressaoError::TamanhosDiferentes);
+    }
+    Ok(())
+}
+
+/// Matriz de correlação entre colunas de mesmo comprimento
+///
+/// A entrada `[i][j]` é o coeficiente entre as colunas `i` e `j`; a
+/// diagonal vale 1. Colunas constantes produzem [`RegressaoError::VarianciaZero`].
+pub fn matriz_correlacao(colunas: &[Vec<f64>], metodo: MetodoCorrelacao) -> Resultado<Vec<Vec<f64>>> {
+    validar_colunas(colunas)?;
+    // Spearman é Pearson sobre os postos: ordena cada coluna uma única vez
+    let transformadas: Vec<Vec<f64>>;
+    let (colunas, metodo) = if metodo == MetodoCorrelacao::Spearman {
+        transformadas = colunas.iter().map(|c| postos(c)).collect();
+        (transformadas.as_slice(), MetodoCorrelacao::Pearson)
+    } else {
+        (colunas, metodo)
+    };
+
+    let k = colunas.len();
+    let mut matriz = vec![vec![1.0; k]; k];
+    for i in 0..k {
+        for j in i + 1..k {
+            let r = coeficiente_correlacao(&colunas[i], &colunas[j], metodo)?;
+            matriz[i][j] = r;
+            matriz[j][i] = r;
+        }
+    }
+    Ok(matriz)
+}
+
+/// Matriz de covariância entre colunas de mesmo comprimento
+///
+/// # Argumentos
+/// * `colunas` - Variáveis, uma por vetor
+/// * `graus_liberdade` - Divisor das somas de produtos (amostral: `n - 1`)
+pub fn matriz_covariancia(colunas: &[Vec<f64>], graus_liberdade: GrausLiberdade) -> Resultado<Vec<Vec<f64>>> {
+    validar_colunas(colunas)?;
+    let n = colunas[0].len();
+    let divisor = graus_liberdade.divisor(n);
+    let centradas: Vec<Vec<f64>> = colunas
+        .iter()
+        .map(|c| {
+            let media = soma::media(c);
+            c.iter().map(|v| v - media).collect()
+        })
+        .collect();
+
+    let k = colunas.len();
+    let mut matriz = vec![vec![0.0; k]; k];
+    for i in 0..k {
+        for j in i..k {
+            let produtos = soma::soma_compensada(centradas[i].iter().zip(&centradas[j]).map(|(a, b)| a * b));
+            matriz[i][j] = produtos / divisor;
+            matriz[j][i] = matriz[i][j];
+        }
+    }
+    Ok(matriz)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teste_util::Gerador;
+
+    const HORAS: [f64; 8] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+    const NOTAS: [f64; 8] = [5.2, 6.1, 6.8, 7.5, 8.1, 8.7, 9.2, 9.5];
+
+    /// Tau-b pela definição, em O(n²)
+    fn kendall_ingenuo(x: &[f64], y: &[f64]) -> f64 {
+        let (mut s, mut empates_x, mut empates_y) = (0.0, 0.0, 0.0);
+        let n = x.len();
+        for i in 0..n {
+            for j in i + 1..n {
+                let (dx, dy) = (x[j] - x[i], y[j] - y[i]);
+                s += (dx * dy).signum() * if dx * dy == 0.0 { 0.0 } else { 1.0 };
+                empates_x += if dx == 0.0 { 1.0 } else { 0.0 };
+                empates_y += if dy == 0.0 { 1.0 } else { 0.0 };
+            }
+        }
+        let pares = (n * (n - 1) / 2) as f64;
+        s / ((pares - empates_x) * (pares - empates_y)).sqrt()
+    }
+
+    #[test]
+    fn test_pearson() {
+        // Referências calculadas com precisão estendida (mpmath)
+        let r = correlacao(&HORAS, &NOTAS, MetodoCorrelacao::Pearson, 0.95).unwrap();
+        assert!((r.coeficiente - 0.992_435_270_011_467_3).abs() < 1e-12);
+        assert!((r.estatistica - 19.801_085_056_943_58).abs() < 1e-9);
+        assert!((r.valor_p - 1.076_101_023_804_499_4e-6).abs() < 1e-15);
+        assert!((r.limite_inferior - 0.957_109_400_324_790_3).abs() < 1e-9);
+        assert!((r.limite_superior - 0.998_685_332_792_132_1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_spearman_e_kendall_monotonos() {
+        // Relação monotônica não linear: postos idênticos
+        let cubos: Vec<f64> = HORAS.iter().map(|x| x * x * x).collect();
+        let rho = correlacao(&HORAS, &cubos, MetodoCorrelacao::Spearman, 0.95).unwrap();
+        let tau = correlacao(&HORAS, &cubos, MetodoCorrelacao::Kendall, 0.95).unwrap();
+        assert_eq!(rho.coeficiente, 1.0);
+        assert_eq!(tau.coeficiente, 1.0);
+        assert_eq!(rho.valor_p, 0.0);
+        // S = 28, Var(S) = 8·7·21/18
+        assert!((tau.estatistica - 28.0 / (8.0f64 * 7.0 * 21.0 / 18.0).sqrt()).abs() < 1e-12);
+        assert!(correlacao(&HORAS, &cubos, MetodoCorrelacao::Pearson, 0.95).unwrap().coeficiente < 1.0);
+
+        let invertidas: Vec<f64> = NOTAS.iter().rev().copied().collect();
+        assert_eq!(coeficiente_correlacao(&HORAS, &invertidas, MetodoCorrelacao::Kendall).unwrap(), -1.0);
+    }
+
+    #[test]
+    fn test_kendall_com_empates() {
+        let mut gerador = Gerador::new(17);
+        for n in [3, 10, 57, 200] {
+            let x: Vec<f64> = (0..n).map(|_| (gerador.uniforme() * 6.0).floor()).collect();
+            let y: Vec<f64> = x.iter().map(|v| (v + gerador.normal() * 2.0).round()).collect();
+            let rapido = coeficiente_correlacao(&x, &y, MetodoCorrelacao::Kendall).unwrap();
+            assert!((rapido - kendall_ingenuo(&x, &y)).abs() < 1e-12, "n = {}", n);
+        }
+
+        // S = 20; estatística sem correção de continuidade
+        let x = [1.0, 2.0, 2.0, 3.0, 4.0, 4.0, 5.0, 6.0];
+        let y = [2.0, 1.0, 3.0, 3.0, 5.0, 4.0, 4.0, 7.0];
+        let tau = correlacao(&x, &y, MetodoCorrelacao::Kendall, 0.95).unwrap();
+        assert!((tau.coeficiente - 10.0 / 13.0).abs() < 1e-12);
+        assert!((tau.estatistica - 2.550_800_667_119_488_3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_matrizes() {
+        let colunas = vec![HORAS.to_vec(), NOTAS.to_vec(), HORAS.iter().map(|h| 10.0 - 2.0 * h).collect()];
+        let correlacoes = matriz_correlacao(&colunas, MetodoCorrelacao::Pearson).unwrap();
+        assert_eq!(correlacoes[0][0], 1.0);
+        assert!((correlacoes[0][2] + 1.0).abs() < 1e-12);
+        assert_eq!(correlacoes[0][1], correlacoes[1][0]);
+        assert!((correlacoes[1][2] + correlacoes[0][1]).abs() < 1e-12);
+        assert!((matriz_correlacao(&colunas, MetodoCorrelacao::Spearman).unwrap()[0][1] - 1.0).abs() < 1e-12);
+
+        let covariancias = matriz_covariancia(&colunas, GrausLiberdade::Amostral).unwrap();
+        assert!((covariancias[0][0] - 6.0).abs() < 1e-12);
+        assert!((covariancias[0][2] + 12.0).abs() < 1e-12);
+        let populacional = matriz_covariancia(&colunas, GrausLiberdade::Populacional).unwrap();
+        assert!((populacional[2][2] - 4.0 * 5.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_correlacao_erros() {
+        let metodo = MetodoCorrelacao::Pearson;
+        assert!(matches!(correlacao::<f64, f64>(&[], &[], metodo, 0.95), Err(RegressaoError::DadosVazios)));
+        assert!(matches!(correlacao(&HORAS, &NOTAS[..5], metodo, 0.95), Err(RegressaoError::TamanhosDiferentes)));
+        assert!(matches!(correlacao(&HORAS[..2], &NOTAS[..2], metodo, 0.95), Err(RegressaoError::DadosInsuficientes)));
+        assert!(matches!(correlacao(&HORAS, &[1.0; 8], metodo, 0.95), Err(RegressaoError::VarianciaZero)));
+        assert!(matches!(correlacao(&HORAS, &NOTAS, metodo, 1.0), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(
+            matriz_covariancia(&[vec![1.0, 2.0], vec![1.0]], GrausLiberdade::Amostral),
+            Err(RegressaoError::TamanhosDiferentes)
+        ));
+        let curta = correlacao(&HORAS[..3], &NOTAS[..3], metodo, 0.95).unwrap();
+        assert!(curta.limite_inferior.is_nan());
+    }
+}
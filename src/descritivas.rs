@@ -0,0 +1,417 @@
+//! # Estatísticas descritivas
+//!
+//! Quantis pelos nove métodos de Hyndman e Fan (1996), médias robustas
+//! (aparada e winsorizada), médias geométrica e harmônica, momentos de
+//! terceira e quarta ordem, moda, desvio absoluto mediano e postos. As
+//! funções que recebem `ordenados` supõem a amostra já ordenada, para que
+//! [`crate::calcular_estatisticas`] ordene os dados uma única vez.
+
+use alloc::vec::Vec;
+
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::matematica::Real as _;
+use crate::numerico::{para_vec_f64, Flutuante};
+use crate::soma::{self, soma_compensada, SomaCompensada};
+use crate::{RegressaoError, Resultado};
+
+/// Método de interpolação de quantis, na numeração de Hyndman e Fan
+///
+/// Os tipos 1 a 3 são descontínuos (devolvem observações ou a média de
+/// duas); os tipos 4 a 9 interpolam linearmente entre estatísticas de ordem
+/// vizinhas, diferindo na posição `h` atribuída ao quantil `p`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetodoQuantil {
+    /// Inversa da função de distribuição empírica
+    Tipo1,
+    /// Inversa da distribuição empírica com média nas descontinuidades
+    Tipo2,
+    /// Observação mais próxima de `np`, com empates para o índice par (SAS)
+    Tipo3,
+    /// `h = np`
+    Tipo4,
+    /// `h = np + 1/2` (Hazen)
+    Tipo5,
+    /// `h = (n + 1)p` (Weibull, Minitab, SPSS)
+    Tipo6,
+    /// `h = (n - 1)p + 1`; padrão do R, do NumPy e das planilhas
+    Tipo7,
+    /// `h = (n + 1/3)p + 1/3`; aproximadamente não viesado para a mediana
+    Tipo8,
+    /// `h = (n + 1/4)p + 3/8`; aproximadamente não viesado para dados normais
+    Tipo9,
+}
+
+/// Graus de liberdade descontados no divisor da variância
+///
+/// O padrão é [`GrausLiberdade::Amostral`], o mesmo de `VAR.A`/`DESVPAD.A`
+/// das planilhas e de `var`/`sd` do R.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GrausLiberdade {
+    /// Divide por `n`: variância da população observada (`VAR.P`)
+    Populacional,
+    /// Divide por `n - 1`: estimador não viesado da variância populacional
+    #[default]
+    Amostral,
+    /// Divide por `n - ddof`
+    Personalizado(usize),
+}
+
+impl GrausLiberdade {
+    /// Graus de liberdade descontados (`ddof`)
+    pub fn descontados(&self) -> usize {
+        match *self {
+            GrausLiberdade::Populacional => 0,
+            GrausLiberdade::Amostral => 1,
+            GrausLiberdade::Personalizado(ddof) => ddof,
+        }
+    }
+
+    /// Divisor da soma de quadrados para `n` observações; `NaN` se `n <= ddof`
+    pub fn divisor(&self, n: usize) -> f64 {
+        match n.checked_sub(self.descontados()) {
+            Some(divisor) if divisor > 0 => divisor as f64,
+            _ => f64::NAN,
+        }
+    }
+}
+
+/// Proporção descartada em cada cauda pelas médias aparada e winsorizada de
+/// [`crate::EstatisticasDescritivas`]
+pub const PROPORCAO_APARADA: f64 = 0.1;
+
+/// Quantil `p` de uma amostra já ordenada em ordem crescente
+///
+/// Segue a formulação do R (`quantile`), com a mesma tolerância para
+/// decidir se `np` é inteiro nos tipos descontínuos.
+pub(crate) fn quantil_ordenado(ordenados: &[f64], p: f64, metodo: MetodoQuantil) -> f64 {
+    let n = ordenados.len();
+    let nf = n as f64;
+    // Estatística de ordem x_k com k em base 1, limitada à amostra
+    let x = |k: f64| ordenados[(k.max(1.0).min(nf) as usize) - 1];
+
+    let descontinuo = |m: f64| {
+        let tolerancia = 4.0 * f64::EPSILON;
+        let j = (nf * p + m + tolerancia).floor();
+        let g = nf * p + m - j;
+        (j, if g.abs() < tolerancia { 0.0 } else { g })
+    };
+    match metodo {
+        MetodoQuantil::Tipo1 | MetodoQuantil::Tipo2 => {
+            let (j, g) = descontinuo(0.0);
+            if g > 0.0 {
+                x(j + 1.0)
+            } else if metodo == MetodoQuantil::Tipo1 {
+                x(j)
+            } else {
+                (x(j) + x(j + 1.0)) / 2.0
+            }
+        }
+        MetodoQuantil::Tipo3 => {
+            let (j, g) = descontinuo(-0.5);
+            if g == 0.0 && j % 2.0 == 0.0 {
+                x(j)
+            } else {
+                x(j + 1.0)
+            }
+        }
+        _ => {
+            let h = match metodo {
+                MetodoQuantil::Tipo4 => nf * p,
+                MetodoQuantil::Tipo5 => nf * p + 0.5,
+                MetodoQuantil::Tipo6 => (nf + 1.0) * p,
+                MetodoQuantil::Tipo7 => (nf - 1.0) * p + 1.0,
+                MetodoQuantil::Tipo8 => (nf + 1.0 / 3.0) * p + 1.0 / 3.0,
+                _ => (nf + 0.25) * p + 0.375,
+            }
+            .clamp(1.0, nf);
+            let j = h.floor();
+            x(j) + (h - j) * (x(j + 1.0) - x(j))
+        }
+    }
+}
+
+fn ordenar<T: Flutuante>(dados: &[T]) -> Resultado<Vec<f64>> {
+    if dados.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    let mut ordenados = para_vec_f64(dados);
+    ordenados.sort_by(|a, b| a.total_cmp(b));
+    Ok(ordenados)
+}
+
+/// Calcula o quantil `p` de `dados`
+///
+/// # Argumentos
+/// * `dados` - Amostra (não precisa estar ordenada)
+/// * `p` - Probabilidade em [0, 1]
+/// * `metodo` - Método de Hyndman e Fan
+pub fn quantil<T: Flutuante>(dados: &[T], p: f64, metodo: MetodoQuantil) -> Resultado<f64> {
+    Ok(quantis(dados, &[p], metodo)?[0])
+}
+
+/// Calcula vários quantis de `dados` com uma única ordenação
+pub fn quantis<T: Flutuante>(dados: &[T], probabilidades: &[f64], metodo: MetodoQuantil) -> Resultado<Vec<f64>> {
+    if probabilidades.iter().any(|p| !(0.0..=1.0).contains(p)) {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    let ordenados = ordenar(dados)?;
+    Ok(probabilidades.iter().map(|&p| quantil_ordenado(&ordenados, p, metodo)).collect())
+}
+
+/// Número de observações descartadas em cada cauda
+fn descartadas(n: usize, proporcao: f64) -> Resultado<usize> {
+    if !(0.0..0.5).contains(&proporcao) {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    Ok((n as f64 * proporcao).floor() as usize)
+}
+
+pub(crate) fn media_aparada_ordenada(ordenados: &[f64], k: usize) -> f64 {
+    let centrais = &ordenados[k..ordenados.len() - k];
+    soma::media(centrais)
+}
+
+pub(crate) fn media_winsorizada_ordenada(ordenados: &[f64], k: usize) -> f64 {
+    let n = ordenados.len();
+    let caudas = k as f64 * (ordenados[k] + ordenados[n - 1 - k]);
+    (caudas + soma_compensada(ordenados[k..n - k].iter().copied())) / n as f64
+}
+
+/// Média aparada: descarta `⌊n·proporcao⌋` observações em cada cauda
+///
+/// # Argumentos
+/// * `dados` - Amostra
+/// * `proporcao` - Proporção descartada em cada cauda, em [0, 0.5)
+pub fn media_aparada<T: Flutuante>(dados: &[T], proporcao: f64) -> Resultado<f64> {
+    let ordenados = ordenar(dados)?;
+    let k = descartadas(ordenados.len(), proporcao)?;
+    Ok(media_aparada_ordenada(&ordenados, k))
+}
+
+/// Média winsorizada: substitui as `⌊n·proporcao⌋` observações de cada
+/// cauda pela observação mais extrema que não foi substituída
+pub fn media_winsorizada<T: Flutuante>(dados: &[T], proporcao: f64) -> Resultado<f64> {
+    let ordenados = ordenar(dados)?;
+    let k = descartadas(ordenados.len(), proporcao)?;
+    Ok(media_winsorizada_ordenada(&ordenados, k))
+}
+
+/// Proporção das médias de [`crate::EstatisticasDescritivas`] convertida em
+/// observações descartadas por cauda
+pub(crate) fn descartadas_padrao(n: usize) -> usize {
+    (n as f64 * PROPORCAO_APARADA).floor() as usize
+}
+
+/// Média geométrica; `NaN` se algum valor não for positivo
+pub(crate) fn media_geometrica(dados: &[f64]) -> f64 {
+    if dados.iter().any(|&v| v <= 0.0) {
+        return f64::NAN;
+    }
+    (soma_compensada(dados.iter().map(|v| v.ln())) / dados.len() as f64).exp()
+}
+
+/// Média harmônica; `NaN` se algum valor não for positivo
+pub(crate) fn media_harmonica(dados: &[f64]) -> f64 {
+    if dados.iter().any(|&v| v <= 0.0) {
+        return f64::NAN;
+    }
+    dados.len() as f64 / soma_compensada(dados.iter().map(|v| 1.0 / v))
+}
+
+/// Valores de frequência máxima em uma amostra ordenada
+///
+/// Vazia quando nenhum valor se repete, pois então todos seriam modas.
+pub(crate) fn moda_ordenada(ordenados: &[f64]) -> Vec<f64> {
+    let maxima = ordenados.chunk_by(|a, b| a == b).map(|g| g.len()).max().unwrap_or(0);
+    if maxima < 2 {
+        return Vec::new();
+    }
+    ordenados.chunk_by(|a, b| a == b).filter(|g| g.len() == maxima).map(|g| g[0]).collect()
+}
+
+/// Desvio absoluto mediano (sem a constante de consistência da normal)
+///
+/// Os desvios à esquerda e à direita da mediana já estão ordenados, de modo
+/// que basta intercalá-los até a posição central, sem nova ordenação.
+pub(crate) fn desvio_absoluto_mediano_ordenado(ordenados: &[f64], mediana: f64) -> f64 {
+    let n = ordenados.len();
+    let divisao = ordenados.partition_point(|&v| v < mediana);
+    let mut esquerda = (0..divisao).rev().map(|i| mediana - ordenados[i]).peekable();
+    let mut direita = (divisao..n).map(|i| ordenados[i] - mediana).peekable();
+
+    let mut proximo = || match (esquerda.peek(), direita.peek()) {
+        (Some(e), Some(d)) if e <= d => esquerda.next(),
+        (Some(_), Some(_)) | (None, Some(_)) => direita.next(),
+        _ => esquerda.next(),
+    };
+    let antes_do_meio = (n - 1) / 2;
+    for _ in 0..antes_do_meio {
+        proximo();
+    }
+    let central = proximo().expect("amostra não vazia");
+    if n.is_multiple_of(2) {
+        (central + proximo().expect("n par")) / 2.0
+    } else {
+        central
+    }
+}
+
+/// Assimetria e curtose em excesso, com e sem correção de viés
+///
+/// Devolve `(g1, G1, g2, G2)`: `g1 = m3/m2^{3/2}` e `g2 = m4/m2² - 3` são os
+/// estimadores de momentos; `G1` e `G2` são as versões ajustadas usadas por
+/// planilhas e pelo SAS. Valores indefinidos (amostra constante, `n < 3`
+/// para `G1`, `n < 4` para `G2`) são `NaN`.
+pub(crate) fn assimetria_curtose(dados: &[f64], media: f64) -> (f64, f64, f64, f64) {
+    let n = dados.len() as f64;
+    let (mut m2, mut m3, mut m4) = (SomaCompensada::new(), SomaCompensada::new(), SomaCompensada::new());
+    for v in dados {
+        let d = v - media;
+        let d2 = d * d;
+        m2.adicionar(d2);
+        m3.adicionar(d2 * d);
+        m4.adicionar(d2 * d2);
+    }
+    if soma::variancia_desprezivel(m2.total(), dados.len(), soma::maior_absoluto(dados)) {
+        return (f64::NAN, f64::NAN, f64::NAN, f64::NAN);
+    }
+    momentos_padronizados(n, m2.total() / n, m3.total() / n, m4.total() / n)
+}
+
+/// `(g1, G1, g2, G2)` a partir dos momentos centrais `m2`, `m3`, `m4`
+pub(crate) fn momentos_padronizados(n: f64, m2: f64, m3: f64, m4: f64) -> (f64, f64, f64, f64) {
+    let g1 = m3 / m2.powf(1.5);
+    let g2 = m4 / (m2 * m2) - 3.0;
+    let g1_ajustada = if n >= 3.0 { g1 * (n * (n - 1.0)).sqrt() / (n - 2.0) } else { f64::NAN };
+    let g2_ajustada = if n >= 4.0 {
+        ((n + 1.0) * g2 + 6.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0))
+    } else {
+        f64::NAN
+    };
+    (g1, g1_ajustada, g2, g2_ajustada)
+}
+
+/// Postos médios (empates recebem a média das posições), a partir de 1
+#[cfg(feature = "std")]
+pub(crate) fn postos<T: Flutuante>(y: &[T]) -> Vec<f64> {
+    let valor = |i: usize| y[i].para_f64();
+    let mut ordem: Vec<usize> = (0..y.len()).collect();
+    ordem.sort_by(|&a, &b| valor(a).total_cmp(&valor(b)));
+
+    let mut postos = vec![0.0; y.len()];
+    let mut inicio = 0;
+    while inicio < ordem.len() {
+        let mut fim = inicio + 1;
+        while fim < ordem.len() && valor(ordem[fim]) == valor(ordem[inicio]) {
+            fim += 1;
+        }
+        let medio = (inicio + fim + 1) as f64 / 2.0;
+        for &i in &ordem[inicio..fim] {
+            postos[i] = medio;
+        }
+        inicio = fim;
+    }
+    postos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    const TIPOS: [MetodoQuantil; 9] = [
+        MetodoQuantil::Tipo1,
+        MetodoQuantil::Tipo2,
+        MetodoQuantil::Tipo3,
+        MetodoQuantil::Tipo4,
+        MetodoQuantil::Tipo5,
+        MetodoQuantil::Tipo6,
+        MetodoQuantil::Tipo7,
+        MetodoQuantil::Tipo8,
+        MetodoQuantil::Tipo9,
+    ];
+
+    #[test]
+    fn test_nove_tipos_de_quantil() {
+        // quantile(1:10, 0.25, type = 1:9) no R
+        let dados: Vec<f64> = (1..=10).rev().map(|v| v as f64).collect();
+        let esperados = [3.0, 3.0, 2.0, 2.5, 3.0, 2.75, 3.25, 2.916_666_666_666_667, 2.9375];
+        for (metodo, esperado) in TIPOS.iter().zip(esperados) {
+            assert!((quantil(&dados, 0.25, *metodo).unwrap() - esperado).abs() < 1e-12, "{:?}", metodo);
+        }
+
+        // Descontinuidade em np = 5
+        assert_eq!(quantil(&dados, 0.5, MetodoQuantil::Tipo1).unwrap(), 5.0);
+        assert_eq!(quantil(&dados, 0.5, MetodoQuantil::Tipo2).unwrap(), 5.5);
+        for metodo in TIPOS {
+            assert_eq!(quantis(&dados, &[0.0, 1.0], metodo).unwrap(), vec![1.0, 10.0]);
+        }
+        assert!(matches!(quantil(&dados, 1.5, MetodoQuantil::Tipo7), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(quantil::<f64>(&[], 0.5, MetodoQuantil::Tipo7), Err(RegressaoError::DadosVazios)));
+    }
+
+    #[test]
+    fn test_divisor_por_graus_de_liberdade() {
+        assert_eq!(GrausLiberdade::default(), GrausLiberdade::Amostral);
+        assert_eq!(GrausLiberdade::Populacional.divisor(5), 5.0);
+        assert_eq!(GrausLiberdade::Amostral.divisor(5), 4.0);
+        assert_eq!(GrausLiberdade::Personalizado(2).divisor(5), 3.0);
+        assert!(GrausLiberdade::Amostral.divisor(1).is_nan());
+        assert!(GrausLiberdade::Personalizado(7).divisor(5).is_nan());
+    }
+
+    #[test]
+    fn test_medias_robustas() {
+        let dados = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 100.0];
+        assert_eq!(media_aparada(&dados, 0.1).unwrap(), 5.5);
+        assert_eq!(media_winsorizada(&dados, 0.1).unwrap(), (2.0 + 2.0 + 3.0 + 4.0 + 5.0 + 6.0 + 7.0 + 8.0 + 9.0 + 9.0) / 10.0);
+        assert_eq!(media_aparada(&dados, 0.0).unwrap(), 14.5);
+        assert!(matches!(media_aparada(&dados, 0.5), Err(RegressaoError::ParametroInvalido)));
+
+        assert!((media_geometrica(&[1.0, 4.0, 16.0]) - 4.0).abs() < 1e-12);
+        assert!((media_harmonica(&[1.0, 2.0, 4.0]) - 12.0 / 7.0).abs() < 1e-12);
+        assert!(media_geometrica(&[1.0, 0.0]).is_nan());
+    }
+
+    #[test]
+    fn test_moda_e_desvio_absoluto_mediano() {
+        assert_eq!(moda_ordenada(&[1.0, 2.0, 2.0, 3.0, 3.0, 4.0]), vec![2.0, 3.0]);
+        assert!(moda_ordenada(&[1.0, 2.0, 3.0]).is_empty());
+
+        // Comparação com a definição: mediana dos desvios ordenados
+        let mut dados: [f64; 9] = [7.0, 1.0, 2.0, 2.0, 4.0, 6.0, 9.0, 10.0, 3.5];
+        for n in 1..=dados.len() {
+            let amostra = &mut dados[..n];
+            amostra.sort_by(|a, b| a.total_cmp(b));
+            let mediana = quantil_ordenado(amostra, 0.5, MetodoQuantil::Tipo7);
+            let mut desvios: Vec<f64> = amostra.iter().map(|v| (v - mediana).abs()).collect();
+            desvios.sort_by(|a, b| a.total_cmp(b));
+            let esperado = quantil_ordenado(&desvios, 0.5, MetodoQuantil::Tipo7);
+            assert_eq!(desvio_absoluto_mediano_ordenado(amostra, mediana), esperado, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_assimetria_e_curtose() {
+        // Referências calculadas em aritmética racional
+        let dados = [2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0];
+        let media = 33.0 / 8.0;
+        let (g1, g1_ajustada, g2, g2_ajustada) = assimetria_curtose(&dados, media);
+        assert!((g1 - 0.265_055_412_269_857_3).abs() < 1e-12);
+        assert!((g1_ajustada - 0.330_582_180_407_974_66).abs() < 1e-12);
+        assert!((g2 + 1.666_001_075_283_850_8).abs() < 1e-12);
+        assert!((g2_ajustada + 2.098_602_258_096_087).abs() < 1e-12);
+
+        assert!(assimetria_curtose(&[3.0; 5], 3.0).0.is_nan());
+        // Média com erro de arredondamento: desvios de um ulp não são variância
+        let constantes = [1.7e9 + 0.3; 5];
+        assert!(assimetria_curtose(&constantes, constantes[0] * (1.0 + f64::EPSILON)).0.is_nan());
+        assert!(assimetria_curtose(&[1.0, 2.0, 4.0], 7.0 / 3.0).3.is_nan());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_postos_com_empates() {
+        assert_eq!(postos(&[3.0, 1.0, 3.0, 2.0]), vec![3.5, 1.0, 3.5, 2.0]);
+    }
+}
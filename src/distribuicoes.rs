@@ -0,0 +1,284 @@
+//! Funções de distribuição de probabilidade usadas em intervalos e testes
+
+use std::f64::consts::PI;
+
+/// Função de distribuição acumulada da normal padrão
+///
+/// Usa o algoritmo 5666 de Hart (implementação de West), com precisão
+/// próxima da dupla precisão em toda a reta.
+pub fn normal_cdf(x: f64) -> f64 {
+    let x_abs = x.abs();
+
+    let cauda = if x_abs > 37.0 {
+        0.0
+    } else {
+        let exponencial = (-x_abs * x_abs / 2.0).exp();
+
+        if x_abs < 7.071_067_811_865_47 {
+            let mut num = 3.526_249_659_989_11e-2 * x_abs + 0.700_383_064_443_688;
+            num = num * x_abs + 6.373_962_203_531_65;
+            num = num * x_abs + 33.912_866_078_383;
+            num = num * x_abs + 112.079_291_497_871;
+            num = num * x_abs + 221.213_596_169_931;
+            num = num * x_abs + 220.206_867_912_376;
+
+            let mut den = 8.838_834_764_831_84e-2 * x_abs + 1.755_667_163_182_64;
+            den = den * x_abs + 16.064_177_579_207;
+            den = den * x_abs + 86.780_732_202_946_1;
+            den = den * x_abs + 296.564_248_779_674;
+            den = den * x_abs + 637.333_633_378_831;
+            den = den * x_abs + 793.826_512_519_948;
+            den = den * x_abs + 440.413_735_824_752;
+
+            exponencial * num / den
+        } else {
+            let mut fracao = x_abs + 0.65;
+            fracao = x_abs + 4.0 / fracao;
+            fracao = x_abs + 3.0 / fracao;
+            fracao = x_abs + 2.0 / fracao;
+            fracao = x_abs + 1.0 / fracao;
+            exponencial / fracao / (2.0 * PI).sqrt()
+        }
+    };
+
+    if x > 0.0 {
+        1.0 - cauda
+    } else {
+        cauda
+    }
+}
+
+/// Função quantil (inversa da acumulada) da normal padrão
+///
+/// Aproximação racional de Acklam seguida de um passo de Halley.
+/// Retorna `NaN` para `p` fora de (0, 1).
+pub fn normal_quantil(p: f64) -> f64 {
+    if !(p > 0.0 && p < 1.0) {
+        return f64::NAN;
+    }
+
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_BAIXO: f64 = 0.024_25;
+
+    let x = if p < P_BAIXO {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_BAIXO {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        let q = (-2.0 * (1.0 - p).ln()).sqrt();
+        -(((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    // Refinamento de Halley
+    let e = normal_cdf(x) - p;
+    let u = e * (2.0 * PI).sqrt() * (x * x / 2.0).exp();
+    x - u / (1.0 + x * u / 2.0)
+}
+
+/// Logaritmo da função gama para `x > 0` (aproximação de Lanczos, g = 7)
+pub(crate) fn ln_gama(x: f64) -> f64 {
+    const COEFICIENTES: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // Reflexão de Euler
+        return (PI / (PI * x).sin()).ln() - ln_gama(1.0 - x);
+    }
+    let x = x - 1.0;
+    let mut soma = COEFICIENTES[0];
+    for (i, c) in COEFICIENTES.iter().enumerate().skip(1) {
+        soma += c / (x + i as f64);
+    }
+    let t = x + 7.5;
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + soma.ln()
+}
+
+/// Função beta incompleta regularizada `I_x(a, b)`
+///
+/// Fração contínua avaliada pelo método de Lentz modificado, usando a
+/// simetria `I_x(a, b) = 1 - I_{1-x}(b, a)` para garantir convergência rápida.
+pub fn beta_incompleta_regularizada(x: f64, a: f64, b: f64) -> f64 {
+    if x.is_nan() || a <= 0.0 || b <= 0.0 {
+        return f64::NAN;
+    }
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let ln_frente = ln_gama(a + b) - ln_gama(a) - ln_gama(b) + a * x.ln() + b * (1.0 - x).ln();
+    if x > (a + 1.0) / (a + b + 2.0) {
+        return 1.0 - ln_frente.exp() * fracao_beta(1.0 - x, b, a) / b;
+    }
+    ln_frente.exp() * fracao_beta(x, a, b) / a
+}
+
+fn fracao_beta(x: f64, a: f64, b: f64) -> f64 {
+    const MINUSCULO: f64 = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < MINUSCULO {
+        d = MINUSCULO;
+    }
+    d = 1.0 / d;
+    let mut resultado = d;
+
+    for m in 1..=300 {
+        let m = m as f64;
+        for numerador in [
+            m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0)),
+        ] {
+            d = 1.0 + numerador * d;
+            if d.abs() < MINUSCULO {
+                d = MINUSCULO;
+            }
+            c = 1.0 + numerador / c;
+            if c.abs() < MINUSCULO {
+                c = MINUSCULO;
+            }
+            d = 1.0 / d;
+            resultado *= d * c;
+        }
+        if (d * c - 1.0).abs() < 1e-15 {
+            break;
+        }
+    }
+    resultado
+}
+
+/// Função de distribuição acumulada da F de Snedecor com `(d1, d2)` graus de liberdade
+pub fn f_cdf(x: f64, d1: f64, d2: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    beta_incompleta_regularizada(d1 * x / (d1 * x + d2), d1 / 2.0, d2 / 2.0)
+}
+
+/// Função de distribuição acumulada da t de Student com `gl` graus de liberdade
+pub fn t_student_cdf(t: f64, gl: f64) -> f64 {
+    if t.is_nan() || gl <= 0.0 {
+        return f64::NAN;
+    }
+    let cauda = 0.5 * beta_incompleta_regularizada(gl / (gl + t * t), gl / 2.0, 0.5);
+    if t > 0.0 { 1.0 - cauda } else { cauda }
+}
+
+/// Função quantil da t de Student com `gl` graus de liberdade
+///
+/// Parte do quantil normal e refina por bissecção sobre a acumulada.
+/// Retorna `NaN` para `p` fora de (0, 1).
+pub fn t_student_quantil(p: f64, gl: f64) -> f64 {
+    if !(p > 0.0 && p < 1.0) || gl <= 0.0 {
+        return f64::NAN;
+    }
+    if p == 0.5 {
+        return 0.0;
+    }
+
+    // Intervalo inicial a partir do quantil normal, expandido até conter a raiz
+    let z = normal_quantil(p);
+    let (mut baixo, mut alto) = if z > 0.0 { (0.0, 2.0 * z) } else { (2.0 * z, 0.0) };
+    while t_student_cdf(alto, gl) < p {
+        alto *= 2.0;
+    }
+    while t_student_cdf(baixo, gl) > p {
+        baixo *= 2.0;
+    }
+    for _ in 0..200 {
+        let meio = 0.5 * (baixo + alto);
+        if t_student_cdf(meio, gl) < p {
+            baixo = meio;
+        } else {
+            alto = meio;
+        }
+        if alto - baixo <= 1e-14 * meio.abs().max(1.0) {
+            break;
+        }
+    }
+    0.5 * (baixo + alto)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normal_cdf_valores_conhecidos() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-15);
+        assert!((normal_cdf(1.959_963_984_540_054) - 0.975).abs() < 1e-12);
+        assert!((normal_cdf(-1.0) - 0.158_655_253_931_457_05).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_normal_quantil_inversa() {
+        assert!((normal_quantil(0.975) - 1.959_963_984_540_054).abs() < 1e-9);
+        assert!((normal_quantil(0.5)).abs() < 1e-12);
+        assert!((normal_quantil(1e-6) + 4.753_424_308_822_899).abs() < 1e-8);
+        assert!(normal_quantil(1.0).is_nan());
+    }
+
+    #[test]
+    fn test_distribuicao_f() {
+        // F(2, d2) tem forma fechada: 1 - (1 + 2x/d2)^(-d2/2)
+        assert!((f_cdf(3.0, 2.0, 10.0) - (1.0 - 1.6f64.powi(-5))).abs() < 1e-12);
+        assert!((f_cdf(1.5, 5.0, 20.0) - 0.765_713_341_097_061).abs() < 1e-12);
+        assert!((f_cdf(0.8, 7.0, 3.0) - (1.0 - f_cdf(1.0 / 0.8, 3.0, 7.0))).abs() < 1e-12);
+        assert!((ln_gama(5.0) - 24f64.ln()).abs() < 1e-12);
+        assert!((beta_incompleta_regularizada(0.3, 1.0, 1.0) - 0.3).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_distribuicao_t() {
+        // t com 1 grau de liberdade é a Cauchy: F(t) = 1/2 + atan(t)/π
+        assert!((t_student_cdf(2.0, 1.0) - (0.5 + 2f64.atan() / PI)).abs() < 1e-12);
+        assert!((t_student_quantil(0.975, 10.0) - 2.228_138_851_986_274).abs() < 1e-9);
+        assert!((t_student_quantil(0.05, 3.0) + 2.353_363_434_801_823).abs() < 1e-9);
+        assert!((t_student_quantil(0.975, 1e6) - 1.959_966).abs() < 1e-5);
+    }
+}
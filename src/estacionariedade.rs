@@ -0,0 +1,445 @@
+//! # Testes de estacionariedade
+//!
+//! Teste aumentado de Dickey–Fuller (ADF), com hipótese nula de raiz unitária,
+//! e teste KPSS, com hipótese nula de estacionariedade. Ajustar uma tendência
+//! linear a um passeio aleatório produz R² alto e espúrio; estes testes
+//! indicam quando a série deve ser diferenciada antes do ajuste.
+
+use std::fmt;
+
+use crate::distribuicoes::normal_cdf;
+use crate::mmq::{ajustar_mmq, AjusteMmq};
+use crate::soma::{self, soma_compensada};
+use crate::{RegressaoError, Resultado};
+
+/// Termos determinísticos incluídos na regressão do teste
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TermoDeterministico {
+    /// Sem constante nem tendência (apenas ADF)
+    Nenhum,
+    Constante,
+    ConstanteETendencia,
+}
+
+/// Escolha do número de defasagens
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Defasagens {
+    /// ADF: menor AIC até o máximo de Schwert `12 (n/100)^{1/4}`;
+    /// KPSS: regra automática de Hobijn et al. (1998)
+    Automaticas,
+    Fixas(usize),
+}
+
+/// Hipótese nula de um teste de estacionariedade
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HipoteseNula {
+    RaizUnitaria,
+    Estacionariedade,
+}
+
+/// Conclusão do teste a um nível de significância
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Veredito {
+    Estacionaria,
+    NaoEstacionaria,
+}
+
+/// Resultado de um teste ADF ou KPSS
+#[derive(Debug, Clone)]
+pub struct TesteEstacionariedade {
+    pub estatistica: f64,
+    pub valor_p: f64,
+    /// Defasagens usadas (ADF) ou largura de banda de Newey–West (KPSS)
+    pub defasagens: usize,
+    pub n_observacoes: usize,
+    /// Pares (nível de significância, valor crítico)
+    pub valores_criticos: Vec<(f64, f64)>,
+    pub hipotese_nula: HipoteseNula,
+}
+
+impl TesteEstacionariedade {
+    /// Conclusão ao nível de significância `alfa` (ex.: 0.05)
+    pub fn veredito(&self, alfa: f64) -> Veredito {
+        let rejeita = self.valor_p < alfa;
+        match (self.hipotese_nula, rejeita) {
+            (HipoteseNula::RaizUnitaria, true) | (HipoteseNula::Estacionariedade, false) => Veredito::Estacionaria,
+            _ => Veredito::NaoEstacionaria,
+        }
+    }
+}
+
+impl fmt::Display for TesteEstacionariedade {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nome = match self.hipotese_nula {
+            HipoteseNula::RaizUnitaria => "Dickey–Fuller Aumentado",
+            HipoteseNula::Estacionariedade => "KPSS",
+        };
+        writeln!(f, "=== Teste {} ===", nome)?;
+        writeln!(f, "Estatística: {:.6}", self.estatistica)?;
+        writeln!(f, "Valor-p: {:.6}", self.valor_p)?;
+        writeln!(f, "Defasagens: {}", self.defasagens)?;
+        writeln!(f, "Observações: {}", self.n_observacoes)?;
+        for (nivel, valor) in &self.valores_criticos {
+            writeln!(f, "Valor crítico ({:.1}%): {:.4}", nivel * 100.0, valor)?;
+        }
+        let conclusao = match self.veredito(0.05) {
+            Veredito::Estacionaria => "estacionária",
+            Veredito::NaoEstacionaria => "não estacionária",
+        };
+        writeln!(f, "Conclusão (5%): série {}", conclusao)?;
+        Ok(())
+    }
+}
+
+/// Teste aumentado de Dickey–Fuller
+///
+/// Regressão `Δy_t = α + βt + γ y_{t-1} + Σ δ_i Δy_{t-i} + e_t`; a estatística
+/// é o t de `γ`. Valores-p pela superfície de resposta de MacKinnon (1994) e
+/// valores críticos de MacKinnon (2010).
+///
+/// # Argumentos
+/// * `y` - Série temporal
+/// * `termo` - Termos determinísticos da regressão
+/// * `defasagens` - Número de diferenças defasadas ou seleção por AIC
+pub fn teste_adf(y: &[f64], termo: TermoDeterministico, defasagens: Defasagens) -> Resultado<TesteEstacionariedade> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    let n = y.len();
+    let n_deterministicos = match termo {
+        TermoDeterministico::Nenhum => 0,
+        TermoDeterministico::Constante => 1,
+        TermoDeterministico::ConstanteETendencia => 2,
+    };
+
+    let dy: Vec<f64> = y.windows(2).map(|w| w[1] - w[0]).collect();
+
+    let k = match defasagens {
+        Defasagens::Fixas(k) => k,
+        Defasagens::Automaticas => {
+            let schwert = (12.0 * (n as f64 / 100.0).powf(0.25)).floor() as usize;
+            let maximo = schwert.min((n / 2).saturating_sub(n_deterministicos + 2));
+            let mut melhor = (f64::INFINITY, 0);
+            for k in 0..=maximo {
+                if let Ok(ajuste) = regressao_adf(y, &dy, k, maximo, termo) {
+                    let n_obs = ajuste.residuos.len() as f64;
+                    let n_param = ajuste.coeficientes.len() as f64;
+                    let aic = n_obs * (ajuste.soma_quadrados_residuos / n_obs).ln() + 2.0 * n_param;
+                    if aic < melhor.0 {
+                        melhor = (aic, k);
+                    }
+                }
+            }
+            melhor.1
+        }
+    };
+
+    if dy.len() < k + n_deterministicos + 3 {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+    let ajuste = regressao_adf(y, &dy, k, k, termo)?;
+    let estatistica = ajuste.coeficientes[0] / ajuste.erros_padrao[0];
+    let n_observacoes = ajuste.residuos.len();
+
+    let valores_criticos = [(0.01, 0), (0.05, 1), (0.10, 2)]
+        .iter()
+        .map(|&(nivel, i)| (nivel, valor_critico_mackinnon(termo, i, n_observacoes)))
+        .collect();
+
+    Ok(TesteEstacionariedade {
+        estatistica,
+        valor_p: valor_p_mackinnon(estatistica, termo),
+        defasagens: k,
+        n_observacoes,
+        valores_criticos,
+        hipotese_nula: HipoteseNula::RaizUnitaria,
+    })
+}
+
+/// Teste KPSS (Kwiatkowski–Phillips–Schmidt–Shin)
+///
+/// A variância de longo prazo usa o núcleo de Bartlett. O valor-p é
+/// interpolado na tabela original e, portanto, limitado a [0.01, 0.10].
+///
+/// # Argumentos
+/// * `y` - Série temporal
+/// * `termo` - `Constante` (estacionariedade em nível) ou `ConstanteETendencia`
+/// * `defasagens` - Largura de banda fixa ou automática
+pub fn teste_kpss(y: &[f64], termo: TermoDeterministico, defasagens: Defasagens) -> Resultado<TesteEstacionariedade> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    let n = y.len();
+    if n < 3 {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let (residuos, tabela) = match termo {
+        TermoDeterministico::Nenhum => return Err(RegressaoError::ParametroInvalido),
+        TermoDeterministico::Constante => {
+            let media = soma::media(y);
+            (y.iter().map(|v| v - media).collect::<Vec<f64>>(), [0.347, 0.463, 0.574, 0.739])
+        }
+        TermoDeterministico::ConstanteETendencia => {
+            let t: Vec<f64> = (1..=n).map(|i| i as f64).collect();
+            (ajustar_mmq(&[vec![1.0; n], t], y)?.residuos, [0.119, 0.146, 0.176, 0.216])
+        }
+    };
+
+    let l = match defasagens {
+        Defasagens::Fixas(l) => l,
+        Defasagens::Automaticas => defasagens_hobijn(&residuos),
+    }
+    .min(n - 1);
+
+    let autocov = |s: usize| -> f64 {
+        soma_compensada(residuos[s..].iter().zip(&residuos[..n - s]).map(|(a, b)| a * b))
+    };
+    let soma_quadrados_residuos = autocov(0);
+    if soma::variancia_desprezivel(soma_quadrados_residuos, n, soma::maior_absoluto(y)) {
+        return Err(RegressaoError::VarianciaZero);
+    }
+    let mut variancia_longa = soma_quadrados_residuos;
+    for s in 1..=l {
+        variancia_longa += 2.0 * (1.0 - s as f64 / (l as f64 + 1.0)) * autocov(s);
+    }
+    variancia_longa /= n as f64;
+
+    let mut soma_parcial = 0.0;
+    let soma_quadrados: f64 = residuos.iter()
+        .map(|e| {
+            soma_parcial += e;
+            soma_parcial * soma_parcial
+        })
+        .sum();
+    let estatistica = soma_quadrados / (n as f64 * n as f64 * variancia_longa);
+
+    // Interpolação linear na tabela de Kwiatkowski et al. (1992)
+    let niveis = [0.10, 0.05, 0.025, 0.01];
+    let valor_p = if estatistica <= tabela[0] {
+        niveis[0]
+    } else if estatistica >= tabela[3] {
+        niveis[3]
+    } else {
+        let i = (0..3).find(|&i| estatistica <= tabela[i + 1]).unwrap_or(2);
+        let fracao = (estatistica - tabela[i]) / (tabela[i + 1] - tabela[i]);
+        niveis[i] + fracao * (niveis[i + 1] - niveis[i])
+    };
+
+    Ok(TesteEstacionariedade {
+        estatistica,
+        valor_p,
+        defasagens: l,
+        n_observacoes: n,
+        valores_criticos: niveis.iter().copied().zip(tabela).collect(),
+        hipotese_nula: HipoteseNula::Estacionariedade,
+    })
+}
+
+/// Regressão ADF com `k` defasagens, usando a amostra comum a `k_amostra` defasagens
+fn regressao_adf(
+    y: &[f64],
+    dy: &[f64],
+    k: usize,
+    k_amostra: usize,
+    termo: TermoDeterministico,
+) -> Resultado<AjusteMmq> {
+    let indices = k_amostra..dy.len();
+    let mut colunas = vec![indices.clone().map(|t| y[t]).collect::<Vec<f64>>()];
+    for i in 1..=k {
+        colunas.push(indices.clone().map(|t| dy[t - i]).collect());
+    }
+    if termo != TermoDeterministico::Nenhum {
+        colunas.push(vec![1.0; indices.len()]);
+    }
+    if termo == TermoDeterministico::ConstanteETendencia {
+        colunas.push(indices.clone().map(|t| (t + 1) as f64).collect());
+    }
+    let resposta: Vec<f64> = indices.map(|t| dy[t]).collect();
+    ajustar_mmq(&colunas, &resposta)
+}
+
+/// Largura de banda automática de Hobijn, Franses e Ooms (1998)
+fn defasagens_hobijn(residuos: &[f64]) -> usize {
+    let n = residuos.len();
+    let nf = n as f64;
+    let lags_cov = nf.powf(2.0 / 9.0) as usize;
+    let mut s0 = residuos.iter().map(|e| e * e).sum::<f64>() / nf;
+    let mut s1 = 0.0;
+    for i in 1..=lags_cov.min(n - 1) {
+        let produto = residuos[i..].iter().zip(residuos).map(|(a, b)| a * b).sum::<f64>() / (nf / 2.0);
+        s0 += produto;
+        s1 += i as f64 * produto;
+    }
+    let s = s1 / s0;
+    let gama = 1.1447 * (s * s).powf(1.0 / 3.0);
+    (gama * nf.powf(1.0 / 3.0)) as usize
+}
+
+/// Valor-p aproximado de MacKinnon (1994) para uma única série
+fn valor_p_mackinnon(estatistica: f64, termo: TermoDeterministico) -> f64 {
+    let (tau_max, tau_min, tau_estrela, pequeno, grande): (f64, f64, f64, [f64; 3], [f64; 4]) = match termo {
+        TermoDeterministico::Nenhum => (
+            f64::INFINITY, -19.04, -1.04,
+            [0.6344, 1.2378, 3.2496e-2],
+            [0.4797, 9.3557e-1, -0.6999e-1, 3.3066e-2],
+        ),
+        TermoDeterministico::Constante => (
+            2.74, -18.83, -1.61,
+            [2.1659, 1.4412, 3.8269e-2],
+            [1.7339, 9.3202e-1, -1.2745e-1, -1.0368e-2],
+        ),
+        TermoDeterministico::ConstanteETendencia => (
+            0.7, -16.18, -2.89,
+            [3.2512, 1.6047, 4.9588e-2],
+            [2.5261, 6.1654e-1, -3.7956e-1, -6.0285e-2],
+        ),
+    };
+
+    if estatistica > tau_max {
+        return 1.0;
+    }
+    if estatistica < tau_min {
+        return 0.0;
+    }
+    let polinomio = |coef: &[f64]| coef.iter().rev().fold(0.0, |acc, c| acc * estatistica + c);
+    if estatistica <= tau_estrela {
+        normal_cdf(polinomio(&pequeno))
+    } else {
+        normal_cdf(polinomio(&grande))
+    }
+}
+
+/// Valores críticos de MacKinnon (2010): `b0 + b1/n + b2/n² + b3/n³`
+fn valor_critico_mackinnon(termo: TermoDeterministico, indice_nivel: usize, n: usize) -> f64 {
+    let tabela: [[f64; 4]; 3] = match termo {
+        TermoDeterministico::Nenhum => [
+            [-2.56574, -2.2358, -3.627, 0.0],
+            [-1.94100, -0.2686, -3.365, 31.223],
+            [-1.61682, 0.2656, -2.714, 25.364],
+        ],
+        TermoDeterministico::Constante => [
+            [-3.43035, -6.5393, -16.786, -79.433],
+            [-2.86154, -2.8903, -4.234, -40.040],
+            [-2.56677, -1.5384, -2.809, 0.0],
+        ],
+        TermoDeterministico::ConstanteETendencia => [
+            [-3.95877, -9.0531, -28.428, -134.155],
+            [-3.41049, -4.3904, -9.036, -45.374],
+            [-3.12705, -2.5856, -3.925, -22.380],
+        ],
+    };
+    let inverso = 1.0 / n as f64;
+    tabela[indice_nivel].iter().rev().fold(0.0, |acc, c| acc * inverso + c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teste_util::Gerador;
+
+    fn passeio_aleatorio(n: usize, semente: u64) -> Vec<f64> {
+        Gerador::new(semente).ruido(n, 1.0).iter()
+            .scan(0.0, |s, e| { *s += e; Some(*s) })
+            .collect()
+    }
+
+    #[test]
+    fn test_adf_ruido_branco_estacionario() {
+        let y = Gerador::new(2).ruido(300, 1.0);
+        let teste = teste_adf(&y, TermoDeterministico::Constante, Defasagens::Automaticas).unwrap();
+
+        assert!(teste.valor_p < 0.01);
+        assert_eq!(teste.veredito(0.05), Veredito::Estacionaria);
+        assert!(teste.estatistica < teste.valores_criticos[0].1);
+    }
+
+    #[test]
+    fn test_adf_passeio_aleatorio() {
+        let y = passeio_aleatorio(300, 6);
+        let teste = teste_adf(&y, TermoDeterministico::ConstanteETendencia, Defasagens::Automaticas).unwrap();
+
+        assert!(teste.valor_p > 0.05);
+        assert_eq!(teste.veredito(0.05), Veredito::NaoEstacionaria);
+    }
+
+    #[test]
+    fn test_adf_defasagens_fixas() {
+        let y = passeio_aleatorio(100, 1);
+        let teste = teste_adf(&y, TermoDeterministico::Nenhum, Defasagens::Fixas(3)).unwrap();
+
+        assert_eq!(teste.defasagens, 3);
+        assert_eq!(teste.n_observacoes, 100 - 1 - 3);
+    }
+
+    #[test]
+    fn test_mackinnon_consistente_com_valores_criticos() {
+        // O valor crítico assintótico de 5% deve ter valor-p próximo de 0.05
+        for (termo, critico) in [
+            (TermoDeterministico::Nenhum, -1.94100),
+            (TermoDeterministico::Constante, -2.86154),
+            (TermoDeterministico::ConstanteETendencia, -3.41049),
+        ] {
+            assert!((valor_p_mackinnon(critico, termo) - 0.05).abs() < 0.005);
+        }
+    }
+
+    #[test]
+    fn test_mackinnon_regiao_de_valor_p_grande() {
+        // Os polinômios pequeno e grande se encontram em τ* = -1.61
+        let termo = TermoDeterministico::Constante;
+        let abaixo = valor_p_mackinnon(-1.61 - 1e-9, termo);
+        let acima = valor_p_mackinnon(-1.61 + 1e-9, termo);
+        assert!((abaixo - acima).abs() < 1e-3);
+
+        // Referência: statsmodels `mackinnonp(tau, regression="c")`
+        assert!((valor_p_mackinnon(-1.0, termo) - 0.753264).abs() < 1e-5);
+        assert!((valor_p_mackinnon(-1.5, termo) - 0.533511).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_kpss_serie_constante_deslocada() {
+        let constante = vec![1.7e9 + 0.3; 50];
+        assert!(matches!(
+            teste_kpss(&constante, TermoDeterministico::Constante, Defasagens::Fixas(2)),
+            Err(RegressaoError::VarianciaZero)
+        ));
+    }
+
+    /// Fração das séries `gerar(semente)` em que o KPSS rejeita a 5%
+    fn taxa_rejeicao_kpss(termo: TermoDeterministico, defasagens: Defasagens, gerar: impl Fn(u64) -> Vec<f64>) -> f64 {
+        let repeticoes = 400;
+        let rejeicoes = (1..=repeticoes)
+            .filter(|&semente| {
+                let teste = teste_kpss(&gerar(1000 + semente), termo, defasagens).unwrap();
+                teste.veredito(0.05) == Veredito::NaoEstacionaria
+            })
+            .count();
+        rejeicoes as f64 / repeticoes as f64
+    }
+
+    #[test]
+    fn test_kpss_nivel_e_tendencia() {
+        // Sob a nula a taxa de rejeição fica perto de α (desvio binomial ~0.011)
+        let ruido = |semente| Gerador::new(semente).ruido(300, 1.0);
+        let taxa = taxa_rejeicao_kpss(TermoDeterministico::Constante, Defasagens::Automaticas, ruido);
+        assert!((taxa - 0.05).abs() < 0.035, "nível: {}", taxa);
+
+        let tendencia = |semente| -> Vec<f64> {
+            ruido(semente).iter().enumerate().map(|(i, e)| 0.5 * i as f64 + e).collect()
+        };
+        let taxa = taxa_rejeicao_kpss(TermoDeterministico::ConstanteETendencia, Defasagens::Fixas(4), tendencia);
+        assert!((taxa - 0.05).abs() < 0.035, "tendência: {}", taxa);
+
+        let passeio = |semente| passeio_aleatorio(300, semente);
+        let poder = taxa_rejeicao_kpss(TermoDeterministico::Constante, Defasagens::Automaticas, passeio);
+        assert!(poder > 0.75, "poder: {}", poder);
+
+        // Estatística além da tabela: valor-p no piso de 0.01
+        let reta: Vec<f64> = (0..300).map(|i| i as f64).collect();
+        let teste = teste_kpss(&reta, TermoDeterministico::Constante, Defasagens::Fixas(4)).unwrap();
+        assert_eq!(teste.veredito(0.05), Veredito::NaoEstacionaria);
+        assert!((teste.valor_p - 0.01).abs() < 1e-12);
+        assert_eq!(teste.defasagens, 4);
+    }
+}
@@ -0,0 +1,293 @@
+//! # Regressão em janelas móveis e expansivas
+//!
+//! Acompanha a evolução da tendência ao longo da série. As somas centradas
+//! de cada janela são atualizadas incrementalmente (inclusão e remoção de
+//! um ponto por vez, no estilo de Welford), de modo que todas as janelas
+//! custam O(n) no total, em vez de O(n·w) chamando
+//! [`crate::regressao_linear_xy`] em laço.
+
+use crate::soma;
+use crate::{RegressaoError, Resultado};
+
+/// Resultados por janela, em vetores paralelos
+///
+/// A posição `i` de cada vetor corresponde à janela que termina na
+/// observação `fim[i]` (inclusive). Janelas em que `x` é constante têm
+/// coeficientes `NaN`; janelas em que `y` é constante têm R² `NaN`; o erro
+/// padrão exige ao menos três pontos na janela.
+#[derive(Debug, Clone)]
+pub struct RegressaoJanelas {
+    /// Índice da última observação de cada janela
+    pub fim: Vec<usize>,
+    /// Número de observações de cada janela
+    pub tamanho: Vec<usize>,
+    pub inclinacao: Vec<f64>,
+    pub intercepto: Vec<f64>,
+    pub r_quadrado: Vec<f64>,
+    /// Erro padrão da inclinação
+    pub erro_padrao: Vec<f64>,
+}
+
+impl RegressaoJanelas {
+    /// Número de janelas
+    pub fn len(&self) -> usize {
+        self.fim.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fim.is_empty()
+    }
+}
+
+/// Somas centradas mantidas incrementalmente
+#[derive(Default)]
+struct Acumulador {
+    n: usize,
+    media_x: f64,
+    media_y: f64,
+    sxx: f64,
+    syy: f64,
+    sxy: f64,
+    /// Maiores valores absolutos já vistos (limitam o erro das atualizações)
+    maior_x: f64,
+    maior_y: f64,
+}
+
+impl Acumulador {
+    fn incluir(&mut self, x: f64, y: f64) {
+        self.maior_x = self.maior_x.max(x.abs());
+        self.maior_y = self.maior_y.max(y.abs());
+        self.n += 1;
+        let dx = x - self.media_x;
+        let dy = y - self.media_y;
+        self.media_x += dx / self.n as f64;
+        self.media_y += dy / self.n as f64;
+        self.sxx += dx * (x - self.media_x);
+        self.syy += dy * (y - self.media_y);
+        self.sxy += dx * (y - self.media_y);
+    }
+
+    fn remover(&mut self, x: f64, y: f64) {
+        if self.n == 1 {
+            *self = Acumulador { maior_x: self.maior_x, maior_y: self.maior_y, ..Acumulador::default() };
+            return;
+        }
+        let dx = x - self.media_x;
+        let dy = y - self.media_y;
+        self.n -= 1;
+        self.media_x -= dx / self.n as f64;
+        self.media_y -= dy / self.n as f64;
+        self.sxx -= dx * (x - self.media_x);
+        self.syy -= dy * (y - self.media_y);
+        self.sxy -= dx * (y - self.media_y);
+    }
+
+    /// Registra a janela `x`, `y` (os pontos acumulados), que termina em `fim`
+    fn registrar(&self, x: &[f64], y: &[f64], fim: usize, saida: &mut RegressaoJanelas) {
+        // Somas perto do erro das atualizações são refeitas em duas passadas
+        // sobre a janela, e só então comparadas com a escala dos dados
+        let incerta_x = incerta(self.sxx, self.n, self.maior_x);
+        let incerta_y = incerta(self.syy, self.n, self.maior_y);
+        let (media_x, media_y, sxx, sxy, syy) = if incerta_x || incerta_y {
+            soma::somas_centradas(x, y)
+        } else {
+            (self.media_x, self.media_y, self.sxx, self.sxy, self.syy)
+        };
+        let x_constante = incerta_x && soma::variancia_desprezivel(sxx, self.n, soma::maior_absoluto(x));
+        let y_constante = incerta_y && soma::variancia_desprezivel(syy, self.n, soma::maior_absoluto(y));
+
+        let (inclinacao, intercepto, r_quadrado, erro_padrao) = if x_constante {
+            (f64::NAN, f64::NAN, f64::NAN, f64::NAN)
+        } else {
+            let inclinacao = sxy / sxx;
+            let intercepto = media_y - inclinacao * media_x;
+            let ssr = (syy - inclinacao * sxy).max(0.0);
+            let r_quadrado = if y_constante { f64::NAN } else { 1.0 - ssr / syy };
+            let erro_padrao = if self.n > 2 {
+                (ssr / (self.n - 2) as f64 / sxx).sqrt()
+            } else {
+                f64::NAN
+            };
+            (inclinacao, intercepto, r_quadrado, erro_padrao)
+        };
+
+        saida.fim.push(fim);
+        saida.tamanho.push(self.n);
+        saida.inclinacao.push(inclinacao);
+        saida.intercepto.push(intercepto);
+        saida.r_quadrado.push(r_quadrado);
+        saida.erro_padrao.push(erro_padrao);
+    }
+}
+
+/// Soma de quadrados centrada dentro do erro de arredondamento acumulado
+/// pelas inclusões e remoções, proporcional a `n·escala²`
+fn incerta(soma: f64, n: usize, escala: f64) -> bool {
+    soma <= 8.0 * f64::EPSILON * n as f64 * escala * escala
+}
+
+fn validar(x: &[f64], y: &[f64], minimo: usize) -> Resultado<RegressaoJanelas> {
+    if x.is_empty() || y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if x.len() != y.len() {
+        return Err(RegressaoError::TamanhosDiferentes);
+    }
+    if minimo < 2 {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    if x.len() < minimo {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let n = x.len() - minimo + 1;
+    Ok(RegressaoJanelas {
+        fim: Vec::with_capacity(n),
+        tamanho: Vec::with_capacity(n),
+        inclinacao: Vec::with_capacity(n),
+        intercepto: Vec::with_capacity(n),
+        r_quadrado: Vec::with_capacity(n),
+        erro_padrao: Vec::with_capacity(n),
+    })
+}
+
+/// Regressão em janela móvel de tamanho fixo para uma série temporal
+/// (x implícito como índices)
+///
+/// # Argumentos
+/// * `y` - Valores da série
+/// * `janela` - Número de observações em cada janela (mínimo 2)
+pub fn regressao_movel(y: &[f64], janela: usize) -> Resultado<RegressaoJanelas> {
+    let x: Vec<f64> = (0..y.len()).map(|i| i as f64).collect();
+    regressao_movel_xy(&x, y, janela)
+}
+
+/// Regressão em janela móvel de tamanho fixo para pontos (x, y) arbitrários
+///
+/// Produz `n - janela + 1` janelas; a primeira cobre as observações
+/// `0..janela`.
+pub fn regressao_movel_xy(x: &[f64], y: &[f64], janela: usize) -> Resultado<RegressaoJanelas> {
+    let mut saida = validar(x, y, janela)?;
+
+    let mut acumulador = Acumulador::default();
+    for i in 0..x.len() {
+        acumulador.incluir(x[i], y[i]);
+        if i >= janela {
+            acumulador.remover(x[i - janela], y[i - janela]);
+        }
+        if i + 1 >= janela {
+            let inicio = i + 1 - janela;
+            acumulador.registrar(&x[inicio..=i], &y[inicio..=i], i, &mut saida);
+        }
+    }
+    Ok(saida)
+}
+
+/// Regressão em janela expansiva para uma série temporal (x implícito como
+/// índices)
+///
+/// # Argumentos
+/// * `y` - Valores da série
+/// * `minimo` - Tamanho da primeira janela (mínimo 2)
+pub fn regressao_expansiva(y: &[f64], minimo: usize) -> Resultado<RegressaoJanelas> {
+    let x: Vec<f64> = (0..y.len()).map(|i| i as f64).collect();
+    regressao_expansiva_xy(&x, y, minimo)
+}
+
+/// Regressão em janela expansiva para pontos (x, y) arbitrários
+///
+/// Todas as janelas começam na primeira observação; a janela `i` cobre
+/// `0..=minimo - 1 + i`.
+pub fn regressao_expansiva_xy(x: &[f64], y: &[f64], minimo: usize) -> Resultado<RegressaoJanelas> {
+    let mut saida = validar(x, y, minimo)?;
+
+    let mut acumulador = Acumulador::default();
+    for i in 0..x.len() {
+        acumulador.incluir(x[i], y[i]);
+        if i + 1 >= minimo {
+            acumulador.registrar(&x[..=i], &y[..=i], i, &mut saida);
+        }
+    }
+    Ok(saida)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mmq::ajustar_mmq;
+    use crate::teste_util::Gerador;
+    use crate::{analise_completa_xy, regressao_linear};
+
+    #[test]
+    fn test_janela_movel_igual_ao_laco() {
+        let mut gerador = Gerador::new(3);
+        let y: Vec<f64> = (0..200)
+            .map(|t| 1000.0 + 0.5 * t as f64 + 20.0 * (t as f64 / 15.0).sin() + gerador.normal())
+            .collect();
+        let movel = regressao_movel(&y, 30).unwrap();
+
+        assert_eq!(movel.len(), 171);
+        for i in 0..movel.len() {
+            let inicio = movel.fim[i] + 1 - 30;
+            let x: Vec<f64> = (inicio..=movel.fim[i]).map(|t| t as f64).collect();
+            let referencia = analise_completa_xy(&x, &y[inicio..=movel.fim[i]]).unwrap();
+            assert!((movel.inclinacao[i] - referencia.inclinacao).abs() < 1e-8);
+            assert!((movel.intercepto[i] - referencia.intercepto).abs() < 1e-6);
+            assert!((movel.r_quadrado[i] - referencia.r_quadrado).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_erro_padrao_da_inclinacao() {
+        let mut gerador = Gerador::new(5);
+        let x: Vec<f64> = (0..60).map(|_| 10.0 * gerador.uniforme()).collect();
+        let y: Vec<f64> = x.iter().map(|v| 2.0 - 0.3 * v + gerador.normal()).collect();
+        let movel = regressao_movel_xy(&x, &y, 25).unwrap();
+
+        for i in [0, 17, movel.len() - 1] {
+            let inicio = movel.fim[i] + 1 - 25;
+            let janela = inicio..=movel.fim[i];
+            let ajuste = ajustar_mmq(&[vec![1.0; 25], x[janela.clone()].to_vec()], &y[janela]).unwrap();
+            assert!((movel.erro_padrao[i] - ajuste.erros_padrao[1]).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_janela_expansiva() {
+        let y = vec![1.0, 3.0, 2.0, 5.0, 4.0, 6.0, 8.0];
+        let expansiva = regressao_expansiva(&y, 3).unwrap();
+
+        assert_eq!(expansiva.fim, vec![2, 3, 4, 5, 6]);
+        assert_eq!(expansiva.tamanho, vec![3, 4, 5, 6, 7]);
+        for (i, &fim) in expansiva.fim.iter().enumerate() {
+            let (a, b) = regressao_linear(&y[..=fim]).unwrap();
+            assert!((expansiva.inclinacao[i] - a).abs() < 1e-12);
+            assert!((expansiva.intercepto[i] - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_janelas_degeneradas_e_erros() {
+        let x = vec![1.0, 1.0, 1.0, 2.0];
+        let y = vec![1.0, 2.0, 3.0, 3.0];
+        let movel = regressao_movel_xy(&x, &y, 2).unwrap();
+        assert!(movel.inclinacao[0].is_nan());
+        assert!(movel.erro_padrao[2].is_nan());
+        assert!((movel.inclinacao[2] - 0.0).abs() < 1e-12);
+        assert!(movel.r_quadrado[2].is_nan());
+
+        // Escala minúscula não é x constante; constante depois de remoções é
+        let minusculos: Vec<f64> = (0..10).map(|i| 1e-9 * i as f64).collect();
+        let y_minusculos: Vec<f64> = minusculos.iter().map(|v| 1.0 + 2.0 * v).collect();
+        let movel = regressao_movel_xy(&minusculos, &y_minusculos, 4).unwrap();
+        assert!(movel.inclinacao.iter().all(|b| (b - 2.0).abs() < 1e-6));
+        let x_plato = vec![0.3, 7.1, 2.9, 1e3 + 0.1, 1e3 + 0.1, 1e3 + 0.1];
+        let movel = regressao_movel_xy(&x_plato, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 3).unwrap();
+        assert!(movel.inclinacao[3].is_nan());
+
+        assert!(matches!(regressao_movel(&y, 1), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(regressao_movel(&y, 5), Err(RegressaoError::DadosInsuficientes)));
+        assert!(matches!(regressao_expansiva(&[], 2), Err(RegressaoError::DadosVazios)));
+        assert!(matches!(regressao_movel_xy(&x, &y[..3], 2), Err(RegressaoError::TamanhosDiferentes)));
+    }
+}
@@ -2,9 +2,109 @@
 //! 
 //! Esta biblioteca fornece funcionalidades para análise de regressão linear,
 //! incluindo cálculo de coeficientes, métricas de avaliação e previsões.
+//!
+//! Módulos adicionais:
+//! * [`acumulador`] - Estatísticas descritivas em fluxo, mescláveis, com quantis aproximados
+//! * [`arima`] - Modelos ARIMA/SARIMA com previsão e seleção de ordem
+//! * [`anomalias`] - Detecção de anomalias na série e nos resíduos
+//! * [`autocorrelacao`] - ACF, PACF e correlação cruzada
+//! * [`correlacao`] - Pearson, Spearman e tau-b de Kendall com testes; matrizes de correlação e covariância
+//! * [`descritivas`] - Quantis de Hyndman–Fan, médias robustas, assimetria e curtose
+//! * [`estacionariedade`] - Testes ADF e KPSS
+//! * [`janelas`] - Regressão em janelas móveis e expansivas em O(n)
+//! * [`matriz`] - Matrizes densas: QR, Cholesky, autodecomposição, SVD, pseudo-inversa
+//! * [`metricas`] - MAPE, sMAPE, WAPE, MASE, RMSLE, U de Theil e outras métricas de previsão
+//! * [`numerico`] - Trait [`numerico::Flutuante`]: as funções de ajuste, métricas e estatísticas aceitam `f32` e `f64`
+//! * [`ponderadas`] - Média, variância e quantis com pesos de frequência ou de confiabilidade
+//! * [`quebras`] - Teste de Chow, CUSUM, segmentação binária e PELT
+//! * [`referencia`] - Previsores de referência (ingênuo, sazonal, deriva, média)
+//! * [`serie_temporal`] - Séries com datas reais e previsões datadas
+//! * [`soma`] - Soma compensada de Neumaier para médias e somas de quadrados estáveis
+//! * [`reamostragem`] - Agregação e preenchimento de séries em frequências fixas
+//! * [`sazonalidade`] - Dummies sazonais, termos de Fourier e ajuste de tendência com sazonalidade
+//! * [`segmentada`] - Regressão linear por partes contínua, com nós fixos ou estimados
+//! * [`tendencia`] - Testes de Mann–Kendall (original, Hamed–Rao, sazonal) e inclinação de Sen
+//! * [`transformacoes`] - Diferenciação, defasagens e Box–Cox com inversas
+//! * [`validacao`] - Validação cruzada com origem móvel (walk-forward)
+//! * [`distribuicoes`] - Funções de distribuição usadas em intervalos e testes
+//!
+//! # Uso sem `std`
+//!
+//! Com `default-features = false` a biblioteca compila com `#![no_std]` e
+//! `alloc`, restrita ao núcleo: ajuste da reta, [`calcular_r2`],
+//! [`calcular_mse`], [`calcular_mae`], [`calcular_estatisticas`] e os
+//! módulos [`descritivas`], [`metricas`], [`numerico`] e [`soma`]. As
+//! variantes [`analise_completa_em`] e [`analise_completa_xy_em`] escrevem os
+//! valores ajustados num buffer do chamador e não alocam;
+//! [`analise_completa_resumida`] nem chega a gerar os valores ajustados.
+//! As previsões têm formas preguiçosas (`*_iter`) e em buffer (`*_em`). A implementação de
+//! `std::error::Error` e os relatórios `Display` dos resultados exigem a
+//! feature `std` (padrão).
 
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use std::fmt;
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod acumulador;
+#[cfg(feature = "std")]
+pub mod anomalias;
+#[cfg(feature = "std")]
+pub mod arima;
+#[cfg(feature = "std")]
+pub mod autocorrelacao;
+#[cfg(feature = "std")]
+pub mod correlacao;
+pub mod descritivas;
+#[cfg(feature = "std")]
+pub mod distribuicoes;
+#[cfg(feature = "std")]
+pub mod estacionariedade;
+#[cfg(feature = "std")]
+pub mod janelas;
+#[cfg(feature = "std")]
+pub mod matriz;
+#[cfg(not(feature = "std"))]
+mod matematica;
+pub mod metricas;
+#[cfg(feature = "std")]
+mod mmq;
+pub mod numerico;
+#[cfg(feature = "std")]
+mod otimizacao;
+#[cfg(feature = "std")]
+pub mod ponderadas;
+#[cfg(feature = "std")]
+pub mod quebras;
+#[cfg(feature = "std")]
+pub mod reamostragem;
+#[cfg(feature = "std")]
+pub mod referencia;
+#[cfg(feature = "std")]
+pub mod sazonalidade;
+#[cfg(feature = "std")]
+pub mod segmentada;
+#[cfg(feature = "std")]
+pub mod serie_temporal;
+pub mod soma;
+#[cfg(feature = "std")]
+pub mod tendencia;
+#[cfg(feature = "std")]
+pub mod transformacoes;
+#[cfg(feature = "std")]
+pub mod validacao;
+#[cfg(all(test, feature = "std"))]
+mod teste_nist;
+#[cfg(all(test, feature = "std"))]
+mod teste_util;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(all(not(feature = "std"), not(test)))]
+use matematica::Real as _;
+use numerico::{valores, Flutuante};
 
 /// Erro personalizado para operações de regressão linear
 #[derive(Debug, Clone, PartialEq)]
@@ -13,6 +113,11 @@ pub enum RegressaoError {
     DadosVazios,
     VarianciaZero,
     TamanhosDiferentes,
+    ParametroInvalido,
+    NaoConvergiu,
+    ValoresNaoPositivos,
+    DataInvalida,
+    ValoresReaisNulos,
 }
 
 impl fmt::Display for RegressaoError {
@@ -22,10 +127,16 @@ impl fmt::Display for RegressaoError {
             RegressaoError::DadosVazios => write!(f, "Conjunto de dados vazio"),
             RegressaoError::VarianciaZero => write!(f, "Variância zero nos dados"),
             RegressaoError::TamanhosDiferentes => write!(f, "Vetores com tamanhos diferentes"),
+            RegressaoError::ParametroInvalido => write!(f, "Parâmetro fora do intervalo válido"),
+            RegressaoError::NaoConvergiu => write!(f, "Otimização numérica não convergiu"),
+            RegressaoError::ValoresNaoPositivos => write!(f, "Transformação exige valores positivos"),
+            RegressaoError::DataInvalida => write!(f, "Data em formato ISO-8601 inválido"),
+            RegressaoError::ValoresReaisNulos => write!(f, "Métrica indefinida para valores reais nulos"),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for RegressaoError {}
 
 /// Tipo Result personalizado para esta biblioteca
@@ -43,22 +154,127 @@ pub struct ResultadoRegressao {
     pub valores_previstos: Vec<f64>,
 }
 
+/// Coeficientes e métricas de um ajuste linear, sem os valores previstos
+///
+/// Devolvido pelas variantes sem alocação ([`analise_completa_em`],
+/// [`analise_completa_xy_em`]), que escrevem os valores ajustados num buffer
+/// do chamador.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AjusteLinear {
+    pub inclinacao: f64,
+    pub intercepto: f64,
+    pub r_quadrado: f64,
+    pub mse: f64,
+    pub rmse: f64,
+    pub mae: f64,
+}
+
+impl AjusteLinear {
+    /// Anexa os valores previstos, formando um [`ResultadoRegressao`]
+    fn com_previstos(self, valores_previstos: Vec<f64>) -> ResultadoRegressao {
+        ResultadoRegressao {
+            inclinacao: self.inclinacao,
+            intercepto: self.intercepto,
+            r_quadrado: self.r_quadrado,
+            mse: self.mse,
+            rmse: self.rmse,
+            mae: self.mae,
+            valores_previstos,
+        }
+    }
+    
+    /// Previsões para novos valores de x, calculadas sob demanda
+    pub fn prever_iter<'a, T: Flutuante>(
+        &self,
+        x_valores: &'a [T],
+    ) -> impl ExactSizeIterator<Item = f64> + Clone + 'a {
+        let (inclinacao, intercepto) = (self.inclinacao, self.intercepto);
+        valores(x_valores).map(move |x| inclinacao * x + intercepto)
+    }
+    
+    /// Escreve em `saida` as previsões para `x_valores`, sem alocar
+    ///
+    /// `saida` deve ter o tamanho de `x_valores`.
+    pub fn prever_em<T: Flutuante>(&self, x_valores: &[T], saida: &mut [f64]) -> Resultado<()> {
+        if saida.len() != x_valores.len() {
+            return Err(RegressaoError::TamanhosDiferentes);
+        }
+        for (destino, previsto) in saida.iter_mut().zip(self.prever_iter(x_valores)) {
+            *destino = previsto;
+        }
+        Ok(())
+    }
+    
+    /// Previsões para os períodos `inicio..inicio + n_periodos`, sob demanda
+    pub fn prever_proximos_periodos_iter(
+        &self,
+        inicio: usize,
+        n_periodos: usize,
+    ) -> impl ExactSizeIterator<Item = f64> + Clone {
+        prever_valores_iter(inicio, n_periodos, self.inclinacao, self.intercepto)
+    }
+    
+    /// Escreve em `saida` as previsões a partir do período `inicio`, um por
+    /// posição do buffer
+    pub fn prever_proximos_periodos_em(&self, inicio: usize, saida: &mut [f64]) {
+        prever_valores_em(inicio, self.inclinacao, self.intercepto, saida);
+    }
+}
+
 impl ResultadoRegressao {
+    /// Coeficientes e métricas sem os valores previstos
+    pub fn ajuste(&self) -> AjusteLinear {
+        AjusteLinear {
+            inclinacao: self.inclinacao,
+            intercepto: self.intercepto,
+            r_quadrado: self.r_quadrado,
+            mse: self.mse,
+            rmse: self.rmse,
+            mae: self.mae,
+        }
+    }
+    
     /// Faz previsões para novos valores de x
-    pub fn prever(&self, x_valores: &[f64]) -> Vec<f64> {
-        x_valores.iter()
-            .map(|&x| self.inclinacao * x + self.intercepto)
-            .collect()
+    pub fn prever<T: Flutuante>(&self, x_valores: &[T]) -> Vec<f64> {
+        self.prever_iter(x_valores).collect()
     }
     
     /// Faz previsões para os próximos n períodos (série temporal)
     pub fn prever_proximos_periodos(&self, inicio: usize, n_periodos: usize) -> Vec<f64> {
-        (inicio..inicio + n_periodos)
-            .map(|x| self.inclinacao * x as f64 + self.intercepto)
-            .collect()
+        self.prever_proximos_periodos_iter(inicio, n_periodos).collect()
+    }
+    
+    /// Versão preguiçosa de [`ResultadoRegressao::prever`]
+    pub fn prever_iter<'a, T: Flutuante>(
+        &self,
+        x_valores: &'a [T],
+    ) -> impl ExactSizeIterator<Item = f64> + Clone + 'a {
+        self.ajuste().prever_iter(x_valores)
+    }
+    
+    /// Versão sem alocação de [`ResultadoRegressao::prever`]; `saida` deve
+    /// ter o tamanho de `x_valores`
+    pub fn prever_em<T: Flutuante>(&self, x_valores: &[T], saida: &mut [f64]) -> Resultado<()> {
+        self.ajuste().prever_em(x_valores, saida)
+    }
+    
+    /// Versão preguiçosa de [`ResultadoRegressao::prever_proximos_periodos`]
+    pub fn prever_proximos_periodos_iter(
+        &self,
+        inicio: usize,
+        n_periodos: usize,
+    ) -> impl ExactSizeIterator<Item = f64> + Clone {
+        self.ajuste().prever_proximos_periodos_iter(inicio, n_periodos)
+    }
+    
+    /// Versão sem alocação de [`ResultadoRegressao::prever_proximos_periodos`]:
+    /// preenche `saida` a partir do período `inicio`
+    pub fn prever_proximos_periodos_em(&self, inicio: usize, saida: &mut [f64]) {
+        self.ajuste().prever_proximos_periodos_em(inicio, saida);
     }
 }
 
+#[cfg(feature = "std")]
 impl fmt::Display for ResultadoRegressao {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "=== Resultado da Regressão Linear ===")?;
@@ -74,13 +290,17 @@ impl fmt::Display for ResultadoRegressao {
 
 /// Calcula a regressão linear para uma série temporal (x implícito como índices)
 /// 
+/// Aceita `f32` ou `f64` ([`numerico::Flutuante`]); os coeficientes são
+/// sempre calculados e devolvidos em `f64`. Não aloca: os índices são
+/// gerados sob demanda.
+/// 
 /// # Argumentos
 /// * `y` - Vetor com os valores y da série temporal
 /// 
 /// # Retorna
 /// * `Ok((inclinacao, intercepto))` - Os coeficientes da regressão
 /// * `Err(RegressaoError)` - Em caso de erro
-pub fn regressao_linear(y: &[f64]) -> Resultado<(f64, f64)> {
+pub fn regressao_linear<T: Flutuante>(y: &[T]) -> Resultado<(f64, f64)> {
     if y.is_empty() {
         return Err(RegressaoError::DadosVazios);
     }
@@ -89,9 +309,10 @@ pub fn regressao_linear(y: &[f64]) -> Resultado<(f64, f64)> {
         return Err(RegressaoError::DadosInsuficientes);
     }
     
-    let x: Vec<f64> = (0..y.len()).map(|i| i as f64).collect();
+    let x = (0..y.len()).map(|i| i as f64);
+    let somas = soma::somas_centradas_iter(x, valores(y));
     
-    regressao_linear_xy(&x, y)
+    ajustar_reta(somas, y.len(), (y.len() - 1) as f64)
 }
 
 /// Calcula a regressão linear para pontos (x, y) arbitrários
@@ -103,7 +324,7 @@ pub fn regressao_linear(y: &[f64]) -> Resultado<(f64, f64)> {
 /// # Retorna
 /// * `Ok((inclinacao, intercepto))` - Os coeficientes da regressão
 /// * `Err(RegressaoError)` - Em caso de erro
-pub fn regressao_linear_xy(x: &[f64], y: &[f64]) -> Resultado<(f64, f64)> {
+pub fn regressao_linear_xy<T: Flutuante>(x: &[T], y: &[T]) -> Resultado<(f64, f64)> {
     if x.is_empty() || y.is_empty() {
         return Err(RegressaoError::DadosVazios);
     }
@@ -116,25 +337,41 @@ pub fn regressao_linear_xy(x: &[f64], y: &[f64]) -> Resultado<(f64, f64)> {
         return Err(RegressaoError::DadosInsuficientes);
     }
     
-    let n = x.len() as f64;
+    // Médias e somas centradas com soma compensada: deslocamentos grandes
+    // (x em segundos desde a época, por exemplo) não perdem dígitos
+    let somas = soma::somas_centradas(x, y);
     
-    // Calcular médias
-    let media_x = x.iter().sum::<f64>() / n;
-    let media_y = y.iter().sum::<f64>() / n;
+    ajustar_reta(somas, x.len(), soma::maior_absoluto(x))
+}
+
+/// [`analise_completa`] sem materializar `valores_previstos`
+///
+/// As métricas são calculadas numa passada, gerando cada valor ajustado sob
+/// demanda; nada é alocado. Para obter os valores ajustados depois, use
+/// [`AjusteLinear::prever_proximos_periodos_iter`] a partir do período 0.
+pub fn analise_completa_resumida<T: Flutuante>(y: &[T]) -> Resultado<AjusteLinear> {
+    let (inclinacao, intercepto) = regressao_linear(y)?;
+    let previstos = prever_valores_iter(0, y.len(), inclinacao, intercepto);
     
-    // Calcular somatórias para os coeficientes
-    let mut soma_xy = 0.0;
-    let mut soma_xx = 0.0;
+    montar_ajuste(y, previstos, inclinacao, intercepto)
+}
+
+/// [`analise_completa_xy`] sem materializar `valores_previstos`
+pub fn analise_completa_xy_resumida<T: Flutuante>(x: &[T], y: &[T]) -> Resultado<AjusteLinear> {
+    let (inclinacao, intercepto) = regressao_linear_xy(x, y)?;
+    let previstos = valores(x).map(|xi| inclinacao * xi + intercepto);
     
-    for i in 0..x.len() {
-        let diff_x = x[i] - media_x;
-        let diff_y = y[i] - media_y;
-        soma_xy += diff_x * diff_y;
-        soma_xx += diff_x * diff_x;
-    }
+    montar_ajuste(y, previstos, inclinacao, intercepto)
+}
+
+/// Coeficientes da reta a partir das somas centradas de dados já validados
+///
+/// `escala_x` é o maior `|x|`, referência do teste de variância nula.
+fn ajustar_reta(somas: (f64, f64, f64, f64, f64), n: usize, escala_x: f64) -> Resultado<(f64, f64)> {
+    let (media_x, media_y, soma_xx, soma_xy, _) = somas;
     
-    // Verificar se há variância em x
-    if soma_xx.abs() < f64::EPSILON {
+    // Verificar se há variância em x, relativa à escala dos dados
+    if soma::variancia_desprezivel(soma_xx, n, escala_x) {
         return Err(RegressaoError::VarianciaZero);
     }
     
@@ -146,33 +383,99 @@ pub fn regressao_linear_xy(x: &[f64], y: &[f64]) -> Resultado<(f64, f64)> {
 }
 
 /// Realiza análise completa de regressão linear
-pub fn analise_completa(y: &[f64]) -> Resultado<ResultadoRegressao> {
+pub fn analise_completa<T: Flutuante>(y: &[T]) -> Resultado<ResultadoRegressao> {
+    let mut valores_previstos = vec![0.0; y.len()];
+    let ajuste = analise_completa_em(y, &mut valores_previstos)?;
+    
+    Ok(ajuste.com_previstos(valores_previstos))
+}
+
+/// Realiza análise completa de regressão linear para pontos (x, y) arbitrários
+pub fn analise_completa_xy<T: Flutuante>(x: &[T], y: &[T]) -> Resultado<ResultadoRegressao> {
+    let mut valores_previstos = vec![0.0; y.len()];
+    let ajuste = analise_completa_xy_em(x, y, &mut valores_previstos)?;
+    
+    Ok(ajuste.com_previstos(valores_previstos))
+}
+
+/// [`analise_completa`] sem alocação: os valores ajustados são escritos em
+/// `previstos`, que deve ter o tamanho de `y`
+///
+/// # Argumentos
+/// * `y` - Série temporal (x implícito como índices)
+/// * `previstos` - Buffer do chamador que recebe `inclinacao * i + intercepto`
+pub fn analise_completa_em<T: Flutuante>(y: &[T], previstos: &mut [f64]) -> Resultado<AjusteLinear> {
+    if previstos.len() != y.len() {
+        return Err(RegressaoError::TamanhosDiferentes);
+    }
     let (inclinacao, intercepto) = regressao_linear(y)?;
     
-    // Calcular valores previstos
-    let valores_previstos: Vec<f64> = (0..y.len())
-        .map(|x| inclinacao * x as f64 + intercepto)
-        .collect();
+    for (i, previsto) in previstos.iter_mut().enumerate() {
+        *previsto = inclinacao * i as f64 + intercepto;
+    }
+    
+    montar_ajuste(y, previstos.iter().copied(), inclinacao, intercepto)
+}
+
+/// [`analise_completa_xy`] sem alocação: os valores ajustados em cada `x`
+/// são escritos em `previstos`, que deve ter o tamanho de `y`
+pub fn analise_completa_xy_em<T: Flutuante>(x: &[T], y: &[T], previstos: &mut [f64]) -> Resultado<AjusteLinear> {
+    if previstos.len() != y.len() {
+        return Err(RegressaoError::TamanhosDiferentes);
+    }
+    let (inclinacao, intercepto) = regressao_linear_xy(x, y)?;
+    
+    for (previsto, xi) in previstos.iter_mut().zip(valores(x)) {
+        *previsto = inclinacao * xi + intercepto;
+    }
     
-    // Calcular métricas
-    let r_quadrado = calcular_r2(y, &valores_previstos)?;
-    let mse = calcular_mse(y, &valores_previstos)?;
-    let rmse = mse.sqrt();
-    let mae = calcular_mae(y, &valores_previstos)?;
+    montar_ajuste(y, previstos.iter().copied(), inclinacao, intercepto)
+}
+
+/// Calcula R², MSE, RMSE e MAE numa única passada sobre os valores previstos
+///
+/// As somas são as mesmas de [`calcular_r2`], [`calcular_mse`] e
+/// [`calcular_mae`], na mesma ordem, de modo que os resultados coincidem com
+/// os dessas funções aplicadas a um vetor materializado.
+fn montar_ajuste<T: Flutuante, P: Iterator<Item = f64>>(
+    y: &[T],
+    previstos: P,
+    inclinacao: f64,
+    intercepto: f64,
+) -> Resultado<AjusteLinear> {
+    let media_y = soma::media(y);
+    let mut ss_tot = soma::SomaCompensada::new();
+    let mut ss_res = soma::SomaCompensada::new();
+    let mut soma_abs = soma::SomaCompensada::new();
+    for (real, previsto) in valores(y).zip(previstos) {
+        ss_tot.adicionar((real - media_y).powi(2));
+        ss_res.adicionar((real - previsto).powi(2));
+        soma_abs.adicionar((real - previsto).abs());
+    }
+    let (ss_tot, ss_res) = (ss_tot.total(), ss_res.total());
     
-    Ok(ResultadoRegressao {
+    if soma::variancia_desprezivel(ss_tot, y.len(), soma::maior_absoluto(y)) {
+        return Err(RegressaoError::VarianciaZero);
+    }
+    
+    let n = y.len() as f64;
+    let mse = ss_res / n;
+    
+    Ok(AjusteLinear {
         inclinacao,
         intercepto,
-        r_quadrado,
+        r_quadrado: 1.0 - (ss_res / ss_tot),
         mse,
-        rmse,
-        mae,
-        valores_previstos,
+        rmse: mse.sqrt(),
+        mae: soma_abs.total() / n,
     })
 }
 
 /// Calcula o R² (coeficiente de determinação)
-pub fn calcular_r2(y_real: &[f64], y_previsto: &[f64]) -> Resultado<f64> {
+///
+/// Valores reais e previstos podem ter tipos diferentes (dados `f32`
+/// contra previsões `f64`, por exemplo), como nas demais métricas.
+pub fn calcular_r2<R: Flutuante, P: Flutuante>(y_real: &[R], y_previsto: &[P]) -> Resultado<f64> {
     if y_real.is_empty() || y_previsto.is_empty() {
         return Err(RegressaoError::DadosVazios);
     }
@@ -181,17 +484,18 @@ pub fn calcular_r2(y_real: &[f64], y_previsto: &[f64]) -> Resultado<f64> {
         return Err(RegressaoError::TamanhosDiferentes);
     }
     
-    let media_y = y_real.iter().sum::<f64>() / y_real.len() as f64;
+    let media_y = soma::media(y_real);
     
-    let mut ss_tot = 0.0; // Soma total dos quadrados
-    let mut ss_res = 0.0; // Soma residual dos quadrados
+    let mut ss_tot = soma::SomaCompensada::new(); // Soma total dos quadrados
+    let mut ss_res = soma::SomaCompensada::new(); // Soma residual dos quadrados
     
-    for i in 0..y_real.len() {
-        ss_tot += (y_real[i] - media_y).powi(2);
-        ss_res += (y_real[i] - y_previsto[i]).powi(2);
+    for (real, previsto) in valores(y_real).zip(valores(y_previsto)) {
+        ss_tot.adicionar((real - media_y).powi(2));
+        ss_res.adicionar((real - previsto).powi(2));
     }
+    let (ss_tot, ss_res) = (ss_tot.total(), ss_res.total());
     
-    if ss_tot.abs() < f64::EPSILON {
+    if soma::variancia_desprezivel(ss_tot, y_real.len(), soma::maior_absoluto(y_real)) {
         return Err(RegressaoError::VarianciaZero);
     }
     
@@ -199,7 +503,7 @@ pub fn calcular_r2(y_real: &[f64], y_previsto: &[f64]) -> Resultado<f64> {
 }
 
 /// Calcula o MSE (Mean Squared Error)
-pub fn calcular_mse(y_real: &[f64], y_previsto: &[f64]) -> Resultado<f64> {
+pub fn calcular_mse<R: Flutuante, P: Flutuante>(y_real: &[R], y_previsto: &[P]) -> Resultado<f64> {
     if y_real.is_empty() || y_previsto.is_empty() {
         return Err(RegressaoError::DadosVazios);
     }
@@ -208,16 +512,15 @@ pub fn calcular_mse(y_real: &[f64], y_previsto: &[f64]) -> Resultado<f64> {
         return Err(RegressaoError::TamanhosDiferentes);
     }
     
-    let soma_erros_quadrados: f64 = y_real.iter()
-        .zip(y_previsto.iter())
-        .map(|(real, prev)| (real - prev).powi(2))
-        .sum();
+    let soma_erros_quadrados = soma::soma_compensada(
+        valores(y_real).zip(valores(y_previsto)).map(|(real, prev)| (real - prev).powi(2)),
+    );
     
     Ok(soma_erros_quadrados / y_real.len() as f64)
 }
 
 /// Calcula o MAE (Mean Absolute Error)
-pub fn calcular_mae(y_real: &[f64], y_previsto: &[f64]) -> Resultado<f64> {
+pub fn calcular_mae<R: Flutuante, P: Flutuante>(y_real: &[R], y_previsto: &[P]) -> Resultado<f64> {
     if y_real.is_empty() || y_previsto.is_empty() {
         return Err(RegressaoError::DadosVazios);
     }
@@ -226,83 +529,195 @@ pub fn calcular_mae(y_real: &[f64], y_previsto: &[f64]) -> Resultado<f64> {
         return Err(RegressaoError::TamanhosDiferentes);
     }
     
-    let soma_erros_absolutos: f64 = y_real.iter()
-        .zip(y_previsto.iter())
-        .map(|(real, prev)| (real - prev).abs())
-        .sum();
+    let soma_erros_absolutos = soma::soma_compensada(
+        valores(y_real).zip(valores(y_previsto)).map(|(real, prev)| (real - prev).abs()),
+    );
     
     Ok(soma_erros_absolutos / y_real.len() as f64)
 }
 
 /// Faz previsões para valores futuros em uma série temporal
 pub fn prever_valores(inicio: usize, n_valores: usize, inclinacao: f64, intercepto: f64) -> Vec<f64> {
-    (inicio..inicio + n_valores)
-        .map(|x| inclinacao * x as f64 + intercepto)
-        .collect()
+    prever_valores_iter(inicio, n_valores, inclinacao, intercepto).collect()
+}
+
+/// Versão preguiçosa de [`prever_valores`]: cada previsão é calculada ao
+/// ser consumida
+pub fn prever_valores_iter(
+    inicio: usize,
+    n_valores: usize,
+    inclinacao: f64,
+    intercepto: f64,
+) -> impl ExactSizeIterator<Item = f64> + Clone {
+    (inicio..inicio + n_valores).map(move |x| inclinacao * x as f64 + intercepto)
+}
+
+/// Versão sem alocação de [`prever_valores`]: preenche `saida` com as
+/// previsões dos períodos `inicio..inicio + saida.len()`
+pub fn prever_valores_em(inicio: usize, inclinacao: f64, intercepto: f64, saida: &mut [f64]) {
+    let previstos = prever_valores_iter(inicio, saida.len(), inclinacao, intercepto);
+    for (destino, previsto) in saida.iter_mut().zip(previstos) {
+        *destino = previsto;
+    }
 }
 
 /// Calcula estatísticas descritivas básicas
+///
+/// A variância e o desvio padrão usam os graus de liberdade em
+/// `graus_liberdade` (por padrão amostral, divisor `n - 1`). Quartis e
+/// intervalo interquartil usam o método 7 de Hyndman e Fan (o padrão do R e
+/// das planilhas); ver [`descritivas::quantil`] para os demais.
+/// Campos indefinidos para a amostra (momentos de ordem superior de dados
+/// constantes ou curtos, médias geométrica e harmônica com valores não
+/// positivos) valem `NaN`.
 #[derive(Debug, Clone)]
 pub struct EstatisticasDescritivas {
     pub media: f64,
     pub mediana: f64,
     pub desvio_padrao: f64,
     pub variancia: f64,
+    /// Divisor usado em `variancia`, `desvio_padrao` e `coeficiente_variacao`
+    pub graus_liberdade: descritivas::GrausLiberdade,
     pub minimo: f64,
     pub maximo: f64,
     pub amplitude: f64,
+    pub primeiro_quartil: f64,
+    pub terceiro_quartil: f64,
+    pub intervalo_interquartil: f64,
+    /// Assimetria pelo estimador de momentos `g1`
+    pub assimetria: f64,
+    /// Assimetria ajustada `G1`, não viesada sob normalidade (`n >= 3`)
+    pub assimetria_ajustada: f64,
+    /// Curtose em excesso (0 na normal) pelo estimador de momentos `g2`
+    pub curtose: f64,
+    /// Curtose em excesso ajustada `G2` (`n >= 4`)
+    pub curtose_ajustada: f64,
+    /// Valores mais frequentes; vazio se nenhum valor se repete
+    pub moda: Vec<f64>,
+    /// `desvio_padrao / |media|`
+    pub coeficiente_variacao: f64,
+    /// Média sem [`descritivas::PROPORCAO_APARADA`] de cada cauda
+    pub media_aparada: f64,
+    /// Média com [`descritivas::PROPORCAO_APARADA`] de cada cauda winsorizada
+    pub media_winsorizada: f64,
+    pub media_geometrica: f64,
+    pub media_harmonica: f64,
+    /// Mediana dos desvios absolutos em relação à mediana
+    pub desvio_absoluto_mediano: f64,
 }
 
+#[cfg(feature = "std")]
 impl fmt::Display for EstatisticasDescritivas {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "=== Estatísticas Descritivas ===")?;
         writeln!(f, "Média: {:.6}", self.media)?;
         writeln!(f, "Mediana: {:.6}", self.mediana)?;
-        writeln!(f, "Desvio Padrão: {:.6}", self.desvio_padrao)?;
-        writeln!(f, "Variância: {:.6}", self.variancia)?;
+        let tipo = match self.graus_liberdade {
+            descritivas::GrausLiberdade::Populacional => "populacional".to_string(),
+            descritivas::GrausLiberdade::Amostral => "amostral".to_string(),
+            descritivas::GrausLiberdade::Personalizado(ddof) => format!("ddof = {}", ddof),
+        };
+        writeln!(f, "Desvio Padrão: {:.6} ({})", self.desvio_padrao, tipo)?;
+        writeln!(f, "Variância: {:.6} ({})", self.variancia, tipo)?;
         writeln!(f, "Mínimo: {:.6}", self.minimo)?;
         writeln!(f, "Máximo: {:.6}", self.maximo)?;
         writeln!(f, "Amplitude: {:.6}", self.amplitude)?;
+        writeln!(f, "Quartis: {:.6} / {:.6}", self.primeiro_quartil, self.terceiro_quartil)?;
+        writeln!(f, "Intervalo Interquartil: {:.6}", self.intervalo_interquartil)?;
+        writeln!(f, "Assimetria: {:.6} (ajustada {:.6})", self.assimetria, self.assimetria_ajustada)?;
+        writeln!(f, "Curtose em Excesso: {:.6} (ajustada {:.6})", self.curtose, self.curtose_ajustada)?;
+        if self.moda.is_empty() {
+            writeln!(f, "Moda: nenhum valor repetido")?;
+        } else {
+            let modas: Vec<String> = self.moda.iter().map(|m| format!("{:.6}", m)).collect();
+            writeln!(f, "Moda: {}", modas.join(", "))?;
+        }
+        writeln!(f, "Coeficiente de Variação: {:.6}", self.coeficiente_variacao)?;
+        writeln!(f, "Média Aparada ({:.0}%): {:.6}", descritivas::PROPORCAO_APARADA * 100.0, self.media_aparada)?;
+        writeln!(f, "Média Winsorizada ({:.0}%): {:.6}", descritivas::PROPORCAO_APARADA * 100.0, self.media_winsorizada)?;
+        writeln!(f, "Média Geométrica: {:.6}", self.media_geometrica)?;
+        writeln!(f, "Média Harmônica: {:.6}", self.media_harmonica)?;
+        writeln!(f, "Desvio Absoluto Mediano: {:.6}", self.desvio_absoluto_mediano)?;
         Ok(())
     }
 }
 
 /// Calcula estatísticas descritivas de um conjunto de dados
-pub fn calcular_estatisticas(dados: &[f64]) -> Resultado<EstatisticasDescritivas> {
+///
+/// Usa a variância amostral (divisor `n - 1`); com uma única observação a
+/// variância e o desvio padrão são `NaN`. Para a variância populacional use
+/// [`calcular_estatisticas_com_graus`] com [`descritivas::GrausLiberdade::Populacional`].
+pub fn calcular_estatisticas<T: Flutuante>(dados: &[T]) -> Resultado<EstatisticasDescritivas> {
+    calcular_estatisticas_com_graus(dados, descritivas::GrausLiberdade::Amostral)
+}
+
+/// Calcula estatísticas descritivas com o divisor da variância escolhido
+///
+/// Os dados são copiados (já em `f64`) e ordenados uma única vez; todas as
+/// estatísticas de ordem (quartis, moda, médias aparada e winsorizada,
+/// desvio absoluto mediano) e os momentos saem dessa cópia.
+///
+/// # Argumentos
+/// * `dados` - Amostra
+/// * `graus_liberdade` - Graus de liberdade descontados no divisor da variância
+pub fn calcular_estatisticas_com_graus<T: Flutuante>(
+    dados: &[T],
+    graus_liberdade: descritivas::GrausLiberdade,
+) -> Resultado<EstatisticasDescritivas> {
+    use descritivas::*;
+
     if dados.is_empty() {
         return Err(RegressaoError::DadosVazios);
     }
     
-    let n = dados.len() as f64;
-    let media = dados.iter().sum::<f64>() / n;
+    let media = soma::media(dados);
     
-    let mut dados_ordenados = dados.to_vec();
-    dados_ordenados.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut dados_ordenados = numerico::para_vec_f64(dados);
+    dados_ordenados.sort_by(|a, b| a.total_cmp(b));
     
-    let mediana = if dados_ordenados.len() % 2 == 0 {
+    let mediana = if dados_ordenados.len().is_multiple_of(2) {
         let meio = dados_ordenados.len() / 2;
         (dados_ordenados[meio - 1] + dados_ordenados[meio]) / 2.0
     } else {
         dados_ordenados[dados_ordenados.len() / 2]
     };
     
-    let variancia = dados.iter()
-        .map(|x| (x - media).powi(2))
-        .sum::<f64>() / n;
+    let variancia = soma::soma_compensada(dados_ordenados.iter().map(|x| (x - media).powi(2)))
+        / graus_liberdade.divisor(dados.len());
     
     let desvio_padrao = variancia.sqrt();
     let minimo = dados_ordenados[0];
     let maximo = dados_ordenados[dados_ordenados.len() - 1];
     let amplitude = maximo - minimo;
+
+    let primeiro_quartil = quantil_ordenado(&dados_ordenados, 0.25, MetodoQuantil::Tipo7);
+    let terceiro_quartil = quantil_ordenado(&dados_ordenados, 0.75, MetodoQuantil::Tipo7);
+    let (assimetria, assimetria_ajustada, curtose, curtose_ajustada) = assimetria_curtose(&dados_ordenados, media);
+    let descartadas = descartadas_padrao(dados_ordenados.len());
     
     Ok(EstatisticasDescritivas {
         media,
         mediana,
         desvio_padrao,
         variancia,
+        graus_liberdade,
         minimo,
         maximo,
         amplitude,
+        primeiro_quartil,
+        terceiro_quartil,
+        intervalo_interquartil: terceiro_quartil - primeiro_quartil,
+        assimetria,
+        assimetria_ajustada,
+        curtose,
+        curtose_ajustada,
+        moda: moda_ordenada(&dados_ordenados),
+        coeficiente_variacao: desvio_padrao / media.abs(),
+        media_aparada: media_aparada_ordenada(&dados_ordenados, descartadas),
+        media_winsorizada: media_winsorizada_ordenada(&dados_ordenados, descartadas),
+        media_geometrica: media_geometrica(&dados_ordenados),
+        media_harmonica: media_harmonica(&dados_ordenados),
+        desvio_absoluto_mediano: desvio_absoluto_mediano_ordenado(&dados_ordenados, mediana),
     })
 }
 
@@ -391,7 +806,7 @@ mod tests {
     #[test]
     fn test_prever_valores() {
         let previsoes = prever_valores(5, 3, 2.0, 1.0);
-        let esperado = vec![11.0, 13.0, 15.0];
+        let esperado = [11.0, 13.0, 15.0];
         
         assert_eq!(previsoes.len(), esperado.len());
         for (prev, esp) in previsoes.iter().zip(esperado.iter()) {
@@ -410,6 +825,18 @@ mod tests {
         assert_approx_eq(resultado.mse, 0.0, 0.001);
     }
     
+    #[test]
+    fn test_analise_completa_xy() {
+        let x = vec![0.0, 1.0, 4.0, 5.0];
+        let y = vec![1.0, 3.0, 9.0, 11.0];
+        let resultado = analise_completa_xy(&x, &y).unwrap();
+        
+        assert_approx_eq(resultado.inclinacao, 2.0, 0.001);
+        assert_approx_eq(resultado.intercepto, 1.0, 0.001);
+        assert_approx_eq(resultado.valores_previstos[2], 9.0, 0.001);
+        assert_approx_eq(resultado.r_quadrado, 1.0, 0.001);
+    }
+    
     #[test]
     fn test_resultado_regressao_prever() {
         let resultado = ResultadoRegressao {
@@ -424,7 +851,7 @@ mod tests {
         
         let x_valores = vec![0.0, 1.0, 2.0];
         let previsoes = resultado.prever(&x_valores);
-        let esperado = vec![1.0, 3.0, 5.0];
+        let esperado = [1.0, 3.0, 5.0];
         
         for (prev, esp) in previsoes.iter().zip(esperado.iter()) {
             assert_approx_eq(*prev, *esp, 0.001);
@@ -443,6 +870,59 @@ mod tests {
         assert_approx_eq(stats.amplitude, 4.0, 0.001);
     }
     
+    #[test]
+    fn test_calcular_estatisticas_forma_e_ordem() {
+        let dados = vec![4.0, 1.0, 2.0, 2.0, 9.0, 3.0, 2.0, 5.0, 8.0, 4.0];
+        let stats = calcular_estatisticas(&dados).unwrap();
+        
+        assert_approx_eq(stats.primeiro_quartil, 2.0, 1e-12);
+        assert_approx_eq(stats.terceiro_quartil, 4.75, 1e-12);
+        assert_approx_eq(stats.intervalo_interquartil, 2.75, 1e-12);
+        assert_eq!(stats.moda, vec![2.0]);
+        assert_approx_eq(stats.media_aparada, 30.0 / 8.0, 1e-12);
+        assert_approx_eq(stats.media_winsorizada, 4.0, 1e-12);
+        assert_approx_eq(stats.desvio_absoluto_mediano, 1.5, 1e-12);
+        assert_approx_eq(stats.coeficiente_variacao, stats.desvio_padrao / 4.0, 1e-12);
+        assert!(stats.assimetria > 0.0 && stats.assimetria_ajustada > stats.assimetria);
+        assert!(stats.media_harmonica < stats.media_geometrica && stats.media_geometrica < stats.media);
+    }
+    
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_exibicao_estatisticas() {
+        let dados = vec![4.0, 1.0, 2.0, 2.0, 9.0, 3.0, 2.0, 5.0, 8.0, 4.0];
+        let texto = calcular_estatisticas(&dados).unwrap().to_string();
+        assert!(texto.contains("Intervalo Interquartil: 2.750000"));
+        assert!(texto.contains("Moda: 2.000000"));
+        
+        let dados = vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let populacional = calcular_estatisticas_com_graus(&dados, descritivas::GrausLiberdade::Populacional).unwrap();
+        assert!(populacional.to_string().contains("Variância: 4.000000 (populacional)"));
+    }
+    
+    #[test]
+    fn test_variancia_amostral_e_populacional() {
+        // Referências: VAR.A / VAR.P das planilhas e var() do R
+        let dados = vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        
+        let amostral = calcular_estatisticas(&dados).unwrap();
+        assert_eq!(amostral.graus_liberdade, descritivas::GrausLiberdade::Amostral);
+        assert_approx_eq(amostral.variancia, 32.0 / 7.0, 1e-12);
+        assert_approx_eq(amostral.desvio_padrao, (32.0f64 / 7.0).sqrt(), 1e-12);
+        assert_approx_eq(amostral.coeficiente_variacao, (32.0f64 / 7.0).sqrt() / 5.0, 1e-12);
+        
+        let populacional = calcular_estatisticas_com_graus(&dados, descritivas::GrausLiberdade::Populacional).unwrap();
+        assert_approx_eq(populacional.variancia, 4.0, 1e-12);
+        assert_approx_eq(populacional.desvio_padrao, 2.0, 1e-12);
+        
+        let ddof = calcular_estatisticas_com_graus(&dados, descritivas::GrausLiberdade::Personalizado(2)).unwrap();
+        assert_approx_eq(ddof.variancia, 32.0 / 6.0, 1e-12);
+        
+        let unica = calcular_estatisticas(&[3.0]).unwrap();
+        assert!(unica.variancia.is_nan());
+        assert_eq!(unica.media, 3.0);
+    }
+    
     #[test]
     fn test_calcular_estatisticas_dados_vazios() {
         let dados: Vec<f64> = vec![];
@@ -488,7 +968,104 @@ mod tests {
         let resultado = regressao_linear(&y_grandes);
         assert!(resultado.is_ok());
     }
+
+    #[test]
+    fn test_regressao_com_deslocamento_de_epoca() {
+        // x em segundos desde a época (~1.7e9) com frações de segundo
+        let x: Vec<f64> = (0..5_000).map(|i| 1.7e9 + 0.37 * i as f64).collect();
+        let y: Vec<f64> = x.iter().map(|&xi| 2.5 + 1e-3 * (xi - 1.7e9)).collect();
+        let (a, b) = regressao_linear_xy(&x, &y).unwrap();
+
+        assert!((a - 1e-3).abs() < 1e-12, "inclinação {}", a);
+        // O intercepto em x = 0 amplifica o erro da inclinação por 1.7e9
+        assert!((b - (2.5 - 1.7e6)).abs() < 1e-3, "intercepto {}", b);
+        let previstos: Vec<f64> = x.iter().map(|&xi| a * xi + b).collect();
+        assert!(calcular_r2(&y, &previstos).unwrap() > 1.0 - 1e-9);
+
+        // Constante grande continua sendo variância zero
+        let constante = vec![1.7e9 + 0.37; 100];
+        assert!(matches!(regressao_linear_xy(&constante, &y[..100]), Err(RegressaoError::VarianciaZero)));
+    }
+
+    #[test]
+    fn test_analise_completa_em_buffer() {
+        let y = [3.0, 5.5, 6.5, 9.0, 10.5, 13.0];
+        let completo = analise_completa(&y).unwrap();
+        let mut previstos = [0.0; 6];
+        let ajuste = analise_completa_em(&y, &mut previstos).unwrap();
+        
+        assert_eq!(previstos.as_slice(), completo.valores_previstos.as_slice());
+        assert_eq!(ajuste.inclinacao, completo.inclinacao);
+        assert_eq!(ajuste.r_quadrado, completo.r_quadrado);
+        assert_eq!(ajuste.mae, completo.mae);
+        
+        let x = [0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+        let mut previstos_xy = [0.0; 6];
+        let ajuste_xy = analise_completa_xy_em(&x, &y, &mut previstos_xy).unwrap();
+        let completo_xy = analise_completa_xy(&x, &y).unwrap();
+        assert_eq!(previstos_xy.as_slice(), completo_xy.valores_previstos.as_slice());
+        assert_eq!(ajuste_xy.rmse, completo_xy.rmse);
+        
+        let mut curto = [0.0; 5];
+        assert!(matches!(analise_completa_em(&y, &mut curto), Err(RegressaoError::TamanhosDiferentes)));
+    }
     
+    #[test]
+    fn test_previsoes_em_buffer_e_iteradores() {
+        let y = [3.0, 5.5, 6.5, 9.0, 10.5, 13.0];
+        let resultado = analise_completa(&y).unwrap();
+        let x_novos = [6.0f32, 7.5, 10.0];
+        
+        let mut saida = [0.0; 3];
+        resultado.prever_em(&x_novos, &mut saida).unwrap();
+        assert_eq!(saida.to_vec(), resultado.prever(&x_novos));
+        assert_eq!(resultado.prever_iter(&x_novos).len(), 3);
+        assert!(matches!(resultado.prever_em(&x_novos, &mut [0.0; 2]), Err(RegressaoError::TamanhosDiferentes)));
+        
+        let mut futuros = [0.0; 4];
+        resultado.prever_proximos_periodos_em(6, &mut futuros);
+        assert_eq!(futuros.to_vec(), resultado.prever_proximos_periodos(6, 4));
+        assert_eq!(
+            resultado.prever_proximos_periodos_iter(6, 4).collect::<Vec<_>>(),
+            prever_valores(6, 4, resultado.inclinacao, resultado.intercepto)
+        );
+        
+        let mut livres = [0.0; 4];
+        prever_valores_em(6, resultado.inclinacao, resultado.intercepto, &mut livres);
+        assert_eq!(livres, futuros);
+        assert_eq!(prever_valores_iter(6, 4, resultado.inclinacao, resultado.intercepto).last(), Some(futuros[3]));
+    }
+    
+    #[test]
+    fn test_analise_completa_resumida_igual_a_completa() {
+        let y = [3.0, 5.5, 6.5, 9.0, 10.5, 13.0, 13.5];
+        let completo = analise_completa(&y).unwrap();
+        let resumo = analise_completa_resumida(&y).unwrap();
+        assert_eq!(resumo, completo.ajuste());
+        
+        // Métricas idênticas às funções públicas sobre o vetor materializado
+        assert_eq!(resumo.r_quadrado, calcular_r2(&y, &completo.valores_previstos).unwrap());
+        assert_eq!(resumo.mse, calcular_mse(&y, &completo.valores_previstos).unwrap());
+        assert_eq!(resumo.mae, calcular_mae(&y, &completo.valores_previstos).unwrap());
+        
+        let x = [0.5, 1.0, 2.0, 4.0, 8.0, 16.0, 32.0];
+        assert_eq!(analise_completa_xy_resumida(&x, &y).unwrap(), analise_completa_xy(&x, &y).unwrap().ajuste());
+        assert!(matches!(analise_completa_resumida(&[2.0, 2.0, 2.0]), Err(RegressaoError::VarianciaZero)));
+    }
+    
+    #[test]
+    fn test_variancia_zero_relativa_a_escala() {
+        // Antes, somas de quadrados abaixo de f64::EPSILON eram tratadas como zero
+        let x: Vec<f64> = (0..10).map(|i| 1e-9 * i as f64).collect();
+        let y: Vec<f64> = x.iter().map(|&xi| 4.0 * xi + 1e-9).collect();
+        let (a, b) = regressao_linear_xy(&x, &y).unwrap();
+        assert_approx_eq(a, 4.0, 1e-9);
+        assert_approx_eq(b, 1e-9, 1e-18);
+        assert_approx_eq(calcular_r2(&y, &y).unwrap(), 1.0, 1e-12);
+
+        assert!(matches!(calcular_r2(&[1e-9; 4], &[1e-9; 4]), Err(RegressaoError::VarianciaZero)));
+    }
+
     #[test]
     fn test_r2_casos_limite() {
         // R² com ajuste ruim (dados aleatórios)
@@ -502,7 +1079,7 @@ mod tests {
     fn test_previsoes_negativas() {
         // Teste com coeficientes que geram valores negativos
         let previsoes = prever_valores(0, 3, -2.0, 5.0);
-        let esperado = vec![5.0, 3.0, 1.0];
+        let esperado = [5.0, 3.0, 1.0];
         
         for (prev, esp) in previsoes.iter().zip(esperado.iter()) {
             assert_approx_eq(*prev, *esp, 0.001);
@@ -0,0 +1,73 @@
+//! Funções de `f64` que dependem de `std`, reimplementadas sobre `libm`
+//!
+//! Raiz, logaritmos, exponencial e potências são métodos inerentes de `f64`
+//! apenas com `std`. Sem ela, os módulos do núcleo importam [`Real`] e as
+//! mesmas chamadas (`x.sqrt()`, `x.ln()`) passam a usar `libm`; com `std` os
+//! métodos inerentes têm precedência e a importação nem é feita.
+//!
+//! O harness de testes sempre liga `std`, o que traz de volta os métodos
+//! inerentes mesmo sem a feature; por isso a importação também é omitida em
+//! `cfg(test)` e os testes abaixo chamam [`Real`] explicitamente.
+
+/// Métodos de `f64` ausentes em `core`
+pub(crate) trait Real {
+    fn sqrt(self) -> Self;
+    fn ln(self) -> Self;
+    fn ln_1p(self) -> Self;
+    fn exp(self) -> Self;
+    fn powf(self, expoente: Self) -> Self;
+    fn powi(self, expoente: i32) -> Self;
+    fn floor(self) -> Self;
+}
+
+impl Real for f64 {
+    fn sqrt(self) -> Self {
+        libm::sqrt(self)
+    }
+
+    fn ln(self) -> Self {
+        libm::log(self)
+    }
+
+    fn ln_1p(self) -> Self {
+        libm::log1p(self)
+    }
+
+    fn exp(self) -> Self {
+        libm::exp(self)
+    }
+
+    fn powf(self, expoente: Self) -> Self {
+        libm::pow(self, expoente)
+    }
+
+    fn powi(self, expoente: i32) -> Self {
+        libm::pow(self, expoente as f64)
+    }
+
+    fn floor(self) -> Self {
+        libm::floor(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Real;
+
+    fn assert_proximo(calculado: f64, esperado: f64) {
+        assert!((calculado - esperado).abs() <= 1e-15 * esperado.abs().max(1.0), "{} != {}", calculado, esperado);
+    }
+
+    #[test]
+    fn test_funcoes_libm() {
+        assert_eq!(Real::sqrt(32.0f64 / 8.0), 2.0);
+        assert_proximo(Real::sqrt(2.0f64), core::f64::consts::SQRT_2);
+        assert_proximo(Real::ln(core::f64::consts::E), 1.0);
+        assert_proximo(Real::ln_1p(1e-20f64), 1e-20);
+        assert_proximo(Real::exp(1.0f64), core::f64::consts::E);
+        assert_proximo(Real::powf(2.0f64, 0.5), core::f64::consts::SQRT_2);
+        assert_eq!(Real::powi(3.0f64, 4), 81.0);
+        assert_eq!(Real::floor(-1.5f64), -2.0);
+        assert!(Real::sqrt(-1.0f64).is_nan());
+    }
+}
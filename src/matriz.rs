@@ -0,0 +1,692 @@
+//! # Álgebra linear densa
+//!
+//! Matrizes densas de `f64` e as decomposições necessárias para modelos com
+//! vários preditores: QR de Householder, Cholesky, autodecomposição
+//! simétrica (Jacobi cíclico) e SVD (Jacobi unilateral de Hestenes), além de
+//! mínimos quadrados, pseudo-inversa e número de condição. Os métodos de
+//! Jacobi são mais lentos que os bidiagonais do LAPACK, mas calculam os
+//! valores pequenos com precisão relativa alta, o que importa para matrizes
+//! mal condicionadas como a de Longley.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::soma::soma_compensada;
+use crate::{RegressaoError, Resultado};
+
+/// Número máximo de varreduras dos métodos de Jacobi
+const MAX_VARREDURAS: usize = 100;
+
+/// Matriz densa armazenada por colunas
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matriz {
+    linhas: usize,
+    colunas: usize,
+    dados: Vec<f64>,
+}
+
+impl Index<(usize, usize)> for Matriz {
+    type Output = f64;
+
+    fn index(&self, (i, j): (usize, usize)) -> &f64 {
+        assert!(i < self.linhas && j < self.colunas, "índice fora da matriz");
+        &self.dados[j * self.linhas + i]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matriz {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut f64 {
+        assert!(i < self.linhas && j < self.colunas, "índice fora da matriz");
+        &mut self.dados[j * self.linhas + i]
+    }
+}
+
+impl fmt::Display for Matriz {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in 0..self.linhas {
+            let linha: Vec<String> = (0..self.colunas).map(|j| format!("{:12.6}", self[(i, j)])).collect();
+            writeln!(f, "[{}]", linha.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+fn produto_interno(a: &[f64], b: &[f64]) -> f64 {
+    soma_compensada(a.iter().zip(b).map(|(x, y)| x * y))
+}
+
+impl Matriz {
+    /// Matriz nula `linhas × colunas`
+    pub fn zeros(linhas: usize, colunas: usize) -> Self {
+        Matriz { linhas, colunas, dados: vec![0.0; linhas * colunas] }
+    }
+
+    /// Matriz identidade `n × n`
+    pub fn identidade(n: usize) -> Self {
+        let mut m = Matriz::zeros(n, n);
+        for i in 0..n {
+            m[(i, i)] = 1.0;
+        }
+        m
+    }
+
+    /// Monta a matriz a partir das colunas, todas do mesmo comprimento
+    pub fn de_colunas(colunas: &[Vec<f64>]) -> Resultado<Self> {
+        if colunas.is_empty() || colunas[0].is_empty() {
+            return Err(RegressaoError::DadosVazios);
+        }
+        let linhas = colunas[0].len();
+        if colunas.iter().any(|c| c.len() != linhas) {
+            return Err(RegressaoError::TamanhosDiferentes);
+        }
+        Ok(Matriz { linhas, colunas: colunas.len(), dados: colunas.concat() })
+    }
+
+    /// Monta a matriz a partir das linhas, todas do mesmo comprimento
+    pub fn de_linhas(linhas: &[Vec<f64>]) -> Resultado<Self> {
+        Ok(Matriz::de_colunas(linhas)?.transposta())
+    }
+
+    pub fn linhas(&self) -> usize {
+        self.linhas
+    }
+
+    pub fn colunas(&self) -> usize {
+        self.colunas
+    }
+
+    /// Coluna `j` como fatia contígua
+    pub fn coluna(&self, j: usize) -> &[f64] {
+        &self.dados[j * self.linhas..(j + 1) * self.linhas]
+    }
+
+    fn coluna_mut(&mut self, j: usize) -> &mut [f64] {
+        &mut self.dados[j * self.linhas..(j + 1) * self.linhas]
+    }
+
+    pub fn transposta(&self) -> Matriz {
+        let mut t = Matriz::zeros(self.colunas, self.linhas);
+        for j in 0..self.colunas {
+            for i in 0..self.linhas {
+                t[(j, i)] = self[(i, j)];
+            }
+        }
+        t
+    }
+
+    /// Produto `self · outra`
+    pub fn produto(&self, outra: &Matriz) -> Resultado<Matriz> {
+        if self.colunas != outra.linhas {
+            return Err(RegressaoError::TamanhosDiferentes);
+        }
+        let mut resultado = Matriz::zeros(self.linhas, outra.colunas);
+        for j in 0..outra.colunas {
+            for k in 0..self.colunas {
+                let fator = outra[(k, j)];
+                if fator != 0.0 {
+                    for (r, a) in resultado.coluna_mut(j).iter_mut().zip(self.coluna(k)) {
+                        *r += fator * a;
+                    }
+                }
+            }
+        }
+        Ok(resultado)
+    }
+
+    /// Produto matriz-vetor `self · v`
+    pub fn aplicar(&self, v: &[f64]) -> Resultado<Vec<f64>> {
+        if v.len() != self.colunas {
+            return Err(RegressaoError::TamanhosDiferentes);
+        }
+        let mut resultado = vec![0.0; self.linhas];
+        for (j, &fator) in v.iter().enumerate() {
+            for (r, a) in resultado.iter_mut().zip(self.coluna(j)) {
+                *r += fator * a;
+            }
+        }
+        Ok(resultado)
+    }
+
+    fn simetrica(&self) -> bool {
+        let escala = self.dados.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+        self.linhas == self.colunas
+            && (0..self.linhas).all(|i| (0..i).all(|j| (self[(i, j)] - self[(j, i)]).abs() <= 1e-12 * escala))
+    }
+
+    /// Decomposição QR por reflexões de Householder (`linhas >= colunas`)
+    pub fn qr(&self) -> Resultado<Qr> {
+        let (m, n) = (self.linhas, self.colunas);
+        if m < n {
+            return Err(RegressaoError::DadosInsuficientes);
+        }
+
+        let mut a = self.clone();
+        let normas = (0..n).map(|j| produto_interno(a.coluna(j), a.coluna(j)).sqrt()).collect();
+        let mut reflexoes = Vec::with_capacity(n);
+        for j in 0..n {
+            let norma = produto_interno(&a.coluna(j)[j..], &a.coluna(j)[j..]).sqrt();
+            let alfa = if a[(j, j)] > 0.0 { -norma } else { norma };
+            let mut v = a.coluna(j)[j..].to_vec();
+            v[0] -= alfa;
+            let vtv = produto_interno(&v, &v);
+            if vtv > 0.0 {
+                for k in j..n {
+                    refletir(&v, vtv, &mut a.coluna_mut(k)[j..]);
+                }
+            }
+            reflexoes.push((v, vtv));
+        }
+
+        let mut r = Matriz::zeros(n, n);
+        for j in 0..n {
+            for i in 0..=j {
+                r[(i, j)] = a[(i, j)];
+            }
+        }
+        Ok(Qr { linhas: m, reflexoes, r, normas })
+    }
+
+    /// Fator de Cholesky `L` (triangular inferior) com `self = L·Lᵀ`
+    ///
+    /// Exige matriz simétrica definida positiva; caso contrário devolve
+    /// [`RegressaoError::ParametroInvalido`].
+    pub fn cholesky(&self) -> Resultado<Matriz> {
+        if !self.simetrica() {
+            return Err(RegressaoError::ParametroInvalido);
+        }
+        let n = self.linhas;
+        let mut l = Matriz::zeros(n, n);
+        for j in 0..n {
+            let diagonal = self[(j, j)] - soma_compensada((0..j).map(|k| l[(j, k)] * l[(j, k)]));
+            if diagonal <= 0.0 || diagonal.is_nan() {
+                return Err(RegressaoError::ParametroInvalido);
+            }
+            l[(j, j)] = diagonal.sqrt();
+            for i in j + 1..n {
+                let soma = soma_compensada((0..j).map(|k| l[(i, k)] * l[(j, k)]));
+                l[(i, j)] = (self[(i, j)] - soma) / l[(j, j)];
+            }
+        }
+        Ok(l)
+    }
+
+    /// Autovalores (decrescentes) e autovetores de uma matriz simétrica
+    pub fn autodecomposicao_simetrica(&self) -> Resultado<AutoDecomposicao> {
+        if !self.simetrica() {
+            return Err(RegressaoError::ParametroInvalido);
+        }
+        let n = self.linhas;
+        let mut a = self.clone();
+        let mut vetores = Matriz::identidade(n);
+
+        let mut convergiu = false;
+        for _ in 0..MAX_VARREDURAS {
+            let fora: f64 = (0..n).flat_map(|i| (0..i).map(move |j| (i, j))).map(|(i, j)| a[(i, j)] * a[(i, j)]).sum();
+            let total: f64 = a.dados.iter().map(|v| v * v).sum();
+            if fora <= f64::EPSILON * f64::EPSILON * total {
+                convergiu = true;
+                break;
+            }
+            for p in 0..n {
+                for q in p + 1..n {
+                    if a[(p, q)] == 0.0 {
+                        continue;
+                    }
+                    // Rotação que anula a[p][q]
+                    let theta = (a[(q, q)] - a[(p, p)]) / (2.0 * a[(p, q)]);
+                    let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                    let t = if theta == 0.0 { 1.0 } else { t };
+                    let c = 1.0 / (t * t + 1.0).sqrt();
+                    let s = t * c;
+                    for k in 0..n {
+                        let (akp, akq) = (a[(k, p)], a[(k, q)]);
+                        a[(k, p)] = c * akp - s * akq;
+                        a[(k, q)] = s * akp + c * akq;
+                    }
+                    for k in 0..n {
+                        let (apk, aqk) = (a[(p, k)], a[(q, k)]);
+                        a[(p, k)] = c * apk - s * aqk;
+                        a[(q, k)] = s * apk + c * aqk;
+                    }
+                    for k in 0..n {
+                        let (vkp, vkq) = (vetores[(k, p)], vetores[(k, q)]);
+                        vetores[(k, p)] = c * vkp - s * vkq;
+                        vetores[(k, q)] = s * vkp + c * vkq;
+                    }
+                }
+            }
+        }
+        if !convergiu {
+            return Err(RegressaoError::NaoConvergiu);
+        }
+
+        let mut ordem: Vec<usize> = (0..n).collect();
+        ordem.sort_by(|&i, &j| a[(j, j)].total_cmp(&a[(i, i)]));
+        let valores = ordem.iter().map(|&i| a[(i, i)]).collect();
+        let colunas: Vec<Vec<f64>> = ordem.iter().map(|&i| vetores.coluna(i).to_vec()).collect();
+        Ok(AutoDecomposicao { valores, vetores: Matriz::de_colunas(&colunas)? })
+    }
+
+    /// Decomposição em valores singulares fina `self = U·diag(σ)·Vᵀ`
+    ///
+    /// Para `m × n`, `U` é `m × k`, `V` é `n × k` e há `k = min(m, n)`
+    /// valores singulares em ordem decrescente. Matrizes sem linhas ou sem
+    /// colunas devolvem [`RegressaoError::DadosVazios`].
+    pub fn svd(&self) -> Resultado<Svd> {
+        if self.linhas == 0 || self.colunas == 0 {
+            return Err(RegressaoError::DadosVazios);
+        }
+        if self.linhas < self.colunas {
+            let Svd { u, valores_singulares, v } = self.transposta().svd()?;
+            return Ok(Svd { u: v, valores_singulares, v: u });
+        }
+
+        // Jacobi unilateral: ortogonaliza as colunas de A·V
+        let n = self.colunas;
+        let mut a = self.clone();
+        let mut v = Matriz::identidade(n);
+        let mut convergiu = false;
+        for _ in 0..MAX_VARREDURAS {
+            let mut rotacoes = false;
+            for p in 0..n {
+                for q in p + 1..n {
+                    let alfa = produto_interno(a.coluna(p), a.coluna(p));
+                    let beta = produto_interno(a.coluna(q), a.coluna(q));
+                    let gama = produto_interno(a.coluna(p), a.coluna(q));
+                    if gama == 0.0 || gama.abs() <= f64::EPSILON * (alfa * beta).sqrt() {
+                        continue;
+                    }
+                    rotacoes = true;
+                    let zeta = (beta - alfa) / (2.0 * gama);
+                    let t = if zeta == 0.0 { 1.0 } else { zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt()) };
+                    let c = 1.0 / (1.0 + t * t).sqrt();
+                    let s = c * t;
+                    for matriz in [&mut a, &mut v] {
+                        for k in 0..matriz.linhas {
+                            let (xp, xq) = (matriz[(k, p)], matriz[(k, q)]);
+                            matriz[(k, p)] = c * xp - s * xq;
+                            matriz[(k, q)] = s * xp + c * xq;
+                        }
+                    }
+                }
+            }
+            if !rotacoes {
+                convergiu = true;
+                break;
+            }
+        }
+        if !convergiu {
+            return Err(RegressaoError::NaoConvergiu);
+        }
+
+        let normas: Vec<f64> = (0..n).map(|j| produto_interno(a.coluna(j), a.coluna(j)).sqrt()).collect();
+        let mut ordem: Vec<usize> = (0..n).collect();
+        ordem.sort_by(|&i, &j| normas[j].total_cmp(&normas[i]));
+
+        let mut u = Matriz::zeros(self.linhas, n);
+        let mut v_ordenada = Matriz::zeros(n, n);
+        for (destino, &origem) in ordem.iter().enumerate() {
+            if normas[origem] > 0.0 {
+                for (x, y) in u.coluna_mut(destino).iter_mut().zip(a.coluna(origem)) {
+                    *x = y / normas[origem];
+                }
+            }
+            v_ordenada.coluna_mut(destino).copy_from_slice(v.coluna(origem));
+        }
+        let valores_singulares = ordem.iter().map(|&i| normas[i]).collect();
+        Ok(Svd { u, valores_singulares, v: v_ordenada })
+    }
+
+    /// Solução de mínimos quadrados de `self · x ≈ b` por QR
+    pub fn minimos_quadrados(&self, b: &[f64]) -> Resultado<Vec<f64>> {
+        self.qr()?.resolver(b)
+    }
+
+    /// Pseudo-inversa de Moore–Penrose pela SVD
+    ///
+    /// Valores singulares abaixo de `max(m, n)·ε·σ_max` são tratados como nulos.
+    pub fn pseudo_inversa(&self) -> Resultado<Matriz> {
+        let svd = self.svd()?;
+        let limite = svd.tolerancia(self.linhas.max(self.colunas));
+        let mut resultado = Matriz::zeros(self.colunas, self.linhas);
+        for (k, &sigma) in svd.valores_singulares.iter().enumerate() {
+            if sigma <= limite {
+                continue;
+            }
+            for j in 0..self.linhas {
+                let fator = svd.u[(j, k)] / sigma;
+                for (r, vik) in resultado.coluna_mut(j).iter_mut().zip(svd.v.coluna(k)) {
+                    *r += vik * fator;
+                }
+            }
+        }
+        Ok(resultado)
+    }
+
+    /// Número de condição na norma 2, `σ_max / σ_min`
+    ///
+    /// Infinito quando a matriz é numericamente singular, isto é, quando o
+    /// posto de [`Svd::posto`] é menor que o número de valores singulares: o
+    /// `σ_min` abaixo da tolerância é ruído de arredondamento e a razão não
+    /// teria significado.
+    pub fn numero_condicao(&self) -> Resultado<f64> {
+        let svd = self.svd()?;
+        let valores = &svd.valores_singulares;
+        if svd.posto() < valores.len() {
+            return Ok(f64::INFINITY);
+        }
+        Ok(valores[0] / valores[valores.len() - 1])
+    }
+}
+
+/// Aplica a reflexão `I - 2vvᵀ/vᵀv` a `x`
+fn refletir(v: &[f64], vtv: f64, x: &mut [f64]) {
+    let fator = 2.0 * produto_interno(v, x) / vtv;
+    for (xi, vi) in x.iter_mut().zip(v) {
+        *xi -= fator * vi;
+    }
+}
+
+/// Decomposição QR fina guardada como reflexões de Householder
+#[derive(Debug, Clone)]
+pub struct Qr {
+    linhas: usize,
+    reflexoes: Vec<(Vec<f64>, f64)>,
+    r: Matriz,
+    /// Normas das colunas originais, escala do teste de posto
+    normas: Vec<f64>,
+}
+
+impl Qr {
+    /// Fator triangular superior `R` (`n × n`)
+    pub fn r(&self) -> &Matriz {
+        &self.r
+    }
+
+    /// Fator ortogonal fino `Q` (`m × n`)
+    pub fn q(&self) -> Matriz {
+        let n = self.r.colunas;
+        let mut q = Matriz::zeros(self.linhas, n);
+        for j in 0..n {
+            q[(j, j)] = 1.0;
+            let coluna = q.coluna_mut(j);
+            for (k, (v, vtv)) in self.reflexoes.iter().enumerate().rev() {
+                if *vtv > 0.0 {
+                    refletir(v, *vtv, &mut coluna[k..]);
+                }
+            }
+        }
+        q
+    }
+
+    /// `Qᵀ·b` completo (`m` componentes; as `m - n` últimas são o resíduo rotacionado)
+    pub fn aplicar_qt(&self, b: &[f64]) -> Resultado<Vec<f64>> {
+        if b.len() != self.linhas {
+            return Err(RegressaoError::TamanhosDiferentes);
+        }
+        let mut qtb = b.to_vec();
+        for (k, (v, vtv)) in self.reflexoes.iter().enumerate() {
+            if *vtv > 0.0 {
+                refletir(v, *vtv, &mut qtb[k..]);
+            }
+        }
+        Ok(qtb)
+    }
+
+    /// Verifica o posto numérico: `|r_jj| > ||a_j||·1e-12·m` para todo `j`
+    ///
+    /// Comparar cada `r_jj` com a norma da própria coluna torna o teste
+    /// invariante à escala das colunas: `x` e `x²` com `x ~ 1e6` não são
+    /// confundidos com colunas dependentes.
+    fn posto_completo(&self) -> bool {
+        let tolerancia = 1e-12 * self.linhas as f64;
+        self.normas
+            .iter()
+            .enumerate()
+            .all(|(j, &norma)| norma > 0.0 && self.r[(j, j)].abs() > norma * tolerancia)
+    }
+
+    /// Resolve `R·x = y` por substituição regressiva
+    fn substituir(&self, y: &[f64]) -> Vec<f64> {
+        let n = self.r.colunas;
+        let mut x = vec![0.0; n];
+        for i in (0..n).rev() {
+            let soma = soma_compensada((i + 1..n).map(|j| self.r[(i, j)] * x[j]));
+            x[i] = (y[i] - soma) / self.r[(i, i)];
+        }
+        x
+    }
+
+    /// Solução de mínimos quadrados de `A·x ≈ b`
+    ///
+    /// Colunas linearmente dependentes produzem [`RegressaoError::VarianciaZero`].
+    pub fn resolver(&self, b: &[f64]) -> Resultado<Vec<f64>> {
+        let qtb = self.aplicar_qt(b)?;
+        if !self.posto_completo() {
+            return Err(RegressaoError::VarianciaZero);
+        }
+        Ok(self.substituir(&qtb))
+    }
+
+    /// Diagonal de `(AᵀA)⁻¹ = R⁻¹R⁻ᵀ`, base dos erros padrão dos coeficientes
+    pub fn diagonal_inversa_ata(&self) -> Resultado<Vec<f64>> {
+        if !self.posto_completo() {
+            return Err(RegressaoError::VarianciaZero);
+        }
+        // diag(R⁻¹R⁻ᵀ) = somas dos quadrados das linhas de R⁻¹
+        let n = self.r.colunas;
+        let mut diagonal = vec![0.0; n];
+        for coluna in 0..n {
+            let mut e = vec![0.0; n];
+            e[coluna] = 1.0;
+            let inversa = self.substituir(&e);
+            for (d, v) in diagonal.iter_mut().zip(&inversa) {
+                *d += v * v;
+            }
+        }
+        Ok(diagonal)
+    }
+}
+
+/// Autovalores e autovetores de uma matriz simétrica
+#[derive(Debug, Clone)]
+pub struct AutoDecomposicao {
+    /// Autovalores em ordem decrescente
+    pub valores: Vec<f64>,
+    /// Autovetores ortonormais nas colunas, na ordem de `valores`
+    pub vetores: Matriz,
+}
+
+/// Decomposição em valores singulares fina
+#[derive(Debug, Clone)]
+pub struct Svd {
+    pub u: Matriz,
+    /// Valores singulares em ordem decrescente
+    pub valores_singulares: Vec<f64>,
+    pub v: Matriz,
+}
+
+impl Svd {
+    fn tolerancia(&self, dimensao: usize) -> f64 {
+        dimensao as f64 * f64::EPSILON * self.valores_singulares[0]
+    }
+
+    /// Posto numérico: valores singulares acima de `max(m, n)·ε·σ_max`
+    pub fn posto(&self) -> usize {
+        let dimensao = self.u.linhas.max(self.v.linhas);
+        let limite = self.tolerancia(dimensao);
+        self.valores_singulares.iter().filter(|&&s| s > limite).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// NIST StRD, conjunto Longley: emprego contra seis indicadores (1947–1962)
+    const LONGLEY: [[f64; 7]; 16] = [
+        [60323.0, 83.0, 234289.0, 2356.0, 1590.0, 107608.0, 1947.0],
+        [61122.0, 88.5, 259426.0, 2325.0, 1456.0, 108632.0, 1948.0],
+        [60171.0, 88.2, 258054.0, 3682.0, 1616.0, 109773.0, 1949.0],
+        [61187.0, 89.5, 284599.0, 3351.0, 1650.0, 110929.0, 1950.0],
+        [63221.0, 96.2, 328975.0, 2099.0, 3099.0, 112075.0, 1951.0],
+        [63639.0, 98.1, 346999.0, 1932.0, 3594.0, 113270.0, 1952.0],
+        [64989.0, 99.0, 365385.0, 1870.0, 3547.0, 115094.0, 1953.0],
+        [63761.0, 100.0, 363112.0, 3578.0, 3350.0, 116219.0, 1954.0],
+        [66019.0, 101.2, 397469.0, 2904.0, 3048.0, 117388.0, 1955.0],
+        [67857.0, 104.6, 419180.0, 2822.0, 2857.0, 118734.0, 1956.0],
+        [68169.0, 108.4, 442769.0, 2936.0, 2798.0, 120445.0, 1957.0],
+        [66513.0, 110.8, 444546.0, 4681.0, 2637.0, 121950.0, 1958.0],
+        [68655.0, 112.6, 482704.0, 3813.0, 2552.0, 123366.0, 1959.0],
+        [69564.0, 114.2, 502601.0, 3931.0, 2514.0, 125368.0, 1960.0],
+        [69331.0, 115.7, 518173.0, 4806.0, 2572.0, 127852.0, 1961.0],
+        [70551.0, 116.9, 554894.0, 4007.0, 2827.0, 130081.0, 1962.0],
+    ];
+
+    /// Coeficientes certificados (intercepto e seis inclinações)
+    const LONGLEY_CERTIFICADOS: [f64; 7] = [
+        -3_482_258.634_595_82,
+        15.061_872_271_373_3,
+        -0.358_191_792_925_910e-1,
+        -2.020_229_803_816_83,
+        -1.033_226_867_173_59,
+        -0.511_041_056_535_807e-1,
+        1_829.151_464_613_55,
+    ];
+
+    fn longley() -> (Matriz, Vec<f64>) {
+        let mut colunas = vec![vec![1.0; 16]];
+        colunas.extend((1..7).map(|j| LONGLEY.iter().map(|linha| linha[j]).collect()));
+        let y = LONGLEY.iter().map(|linha| linha[0]).collect();
+        (Matriz::de_colunas(&colunas).unwrap(), y)
+    }
+
+    /// Dígitos significativos corretos (logaritmo do erro relativo, NIST)
+    fn digitos(calculado: f64, certificado: f64) -> f64 {
+        -((calculado - certificado).abs() / certificado.abs()).log10()
+    }
+
+    #[test]
+    fn test_longley_qr() {
+        let (x, y) = longley();
+        let coeficientes = x.minimos_quadrados(&y).unwrap();
+        for (b, certificado) in coeficientes.iter().zip(LONGLEY_CERTIFICADOS) {
+            assert!(digitos(*b, certificado) > 8.0, "{} vs {}", b, certificado);
+        }
+
+        // Erros padrão certificados do intercepto e de x6
+        let qr = x.qr().unwrap();
+        let residuos: Vec<f64> = x.aplicar(&coeficientes).unwrap().iter().zip(&y).map(|(p, v)| v - p).collect();
+        let sigma2 = produto_interno(&residuos, &residuos) / 9.0;
+        let diagonal = qr.diagonal_inversa_ata().unwrap();
+        assert!(digitos((sigma2 * diagonal[0]).sqrt(), 890_420.383_607_373) > 6.0);
+        assert!(digitos((sigma2 * diagonal[6]).sqrt(), 455.478_499_142_212) > 6.0);
+        assert!(digitos(sigma2.sqrt(), 304.854_073_561_965) > 8.0);
+    }
+
+    #[test]
+    fn test_longley_svd_e_pseudo_inversa() {
+        let (x, y) = longley();
+        let coeficientes = x.pseudo_inversa().unwrap().aplicar(&y).unwrap();
+        for (b, certificado) in coeficientes.iter().zip(LONGLEY_CERTIFICADOS) {
+            assert!(digitos(*b, certificado) > 7.0, "{} vs {}", b, certificado);
+        }
+
+        let svd = x.svd().unwrap();
+        assert_eq!(svd.posto(), 7);
+        let reconstruida = svd.u.produto(&Matriz::de_colunas(
+            &svd.valores_singulares.iter().enumerate().map(|(j, s)| {
+                let mut coluna = vec![0.0; 7];
+                coluna[j] = *s;
+                coluna
+            }).collect::<Vec<_>>(),
+        ).unwrap()).unwrap().produto(&svd.v.transposta()).unwrap();
+        for (a, b) in reconstruida.dados.iter().zip(&x.dados) {
+            assert!((a - b).abs() <= 1e-9 * b.abs().max(1.0));
+        }
+        // Matriz sabidamente mal condicionada
+        assert!(x.numero_condicao().unwrap() > 1e9);
+    }
+
+    #[test]
+    fn test_qr_ortogonal() {
+        let (x, _) = longley();
+        let qr = x.qr().unwrap();
+        let q = qr.q();
+        let qtq = q.transposta().produto(&q).unwrap();
+        let qr_produto = q.produto(qr.r()).unwrap();
+        for i in 0..7 {
+            for j in 0..7 {
+                let esperado = if i == j { 1.0 } else { 0.0 };
+                assert!((qtq[(i, j)] - esperado).abs() < 1e-12);
+            }
+        }
+        for (a, b) in qr_produto.dados.iter().zip(&x.dados) {
+            assert!((a - b).abs() <= 1e-10 * b.abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn test_cholesky_e_autodecomposicao() {
+        let a = Matriz::de_linhas(&[vec![4.0, 12.0, -16.0], vec![12.0, 37.0, -43.0], vec![-16.0, -43.0, 98.0]]).unwrap();
+        let l = a.cholesky().unwrap();
+        let esperado = Matriz::de_linhas(&[vec![2.0, 0.0, 0.0], vec![6.0, 1.0, 0.0], vec![-8.0, 5.0, 3.0]]).unwrap();
+        assert_eq!(l, esperado);
+
+        let auto = a.autodecomposicao_simetrica().unwrap();
+        assert!((auto.valores.iter().sum::<f64>() - 139.0).abs() < 1e-10);
+        // Determinante = (2·1·3)² = 36
+        assert!((auto.valores.iter().product::<f64>() - 36.0).abs() < 1e-8);
+        for k in 0..3 {
+            let vetor = auto.vetores.coluna(k);
+            let av = a.aplicar(vetor).unwrap();
+            for (x, v) in av.iter().zip(vetor) {
+                assert!((x - auto.valores[k] * v).abs() < 1e-9);
+            }
+        }
+
+        let indefinida = Matriz::de_linhas(&[vec![1.0, 2.0], vec![2.0, 1.0]]).unwrap();
+        assert!(matches!(indefinida.cholesky(), Err(RegressaoError::ParametroInvalido)));
+        let valores = indefinida.autodecomposicao_simetrica().unwrap().valores;
+        assert!((valores[0] - 3.0).abs() < 1e-14 && (valores[1] + 1.0).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_matriz_deficiente_e_erros() {
+        let a = Matriz::de_colunas(&[vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 6.0]]).unwrap();
+        assert!(matches!(a.minimos_quadrados(&[1.0, 2.0, 3.0]), Err(RegressaoError::VarianciaZero)));
+        assert_eq!(a.svd().unwrap().posto(), 1);
+        assert_eq!(a.numero_condicao().unwrap(), f64::INFINITY);
+
+        // Coluna quase dependente: σ_min sai do arredondamento, não da matriz
+        let x: Vec<f64> = (1..=20).map(|i| (i as f64).sqrt()).collect();
+        let quase: Vec<f64> = x.iter().map(|v| v * 3.0 + v * 1e-17).collect();
+        let quase_singular = Matriz::de_colunas(&[x, quase]).unwrap();
+        assert_eq!(quase_singular.svd().unwrap().posto(), 1);
+        assert_eq!(quase_singular.numero_condicao().unwrap(), f64::INFINITY);
+
+        // A⁺ de uma matriz de posto 1: A⁺ = Aᵀ / ||A||²_F
+        let pseudo = a.pseudo_inversa().unwrap();
+        for i in 0..2 {
+            for j in 0..3 {
+                assert!((pseudo[(i, j)] - a[(j, i)] / 70.0).abs() < 1e-14);
+            }
+        }
+
+        // Matriz larga: SVD pela transposta
+        let larga = a.transposta();
+        assert_eq!(larga.svd().unwrap().u.linhas(), 2);
+        assert!(matches!(larga.qr(), Err(RegressaoError::DadosInsuficientes)));
+        assert!(matches!(Matriz::de_colunas(&[vec![1.0], vec![1.0, 2.0]]), Err(RegressaoError::TamanhosDiferentes)));
+        assert!(matches!(a.produto(&a), Err(RegressaoError::TamanhosDiferentes)));
+    }
+
+    #[test]
+    fn test_matrizes_vazias() {
+        for vazia in [Matriz::zeros(3, 0), Matriz::zeros(0, 3), Matriz::zeros(0, 0)] {
+            assert!(matches!(vazia.svd(), Err(RegressaoError::DadosVazios)));
+            assert!(matches!(vazia.pseudo_inversa(), Err(RegressaoError::DadosVazios)));
+            assert!(matches!(vazia.numero_condicao(), Err(RegressaoError::DadosVazios)));
+        }
+    }
+}
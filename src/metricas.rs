@@ -0,0 +1,263 @@
+//! # Métricas de acurácia de previsão
+//!
+//! Complementam [`crate::calcular_mse`], [`crate::calcular_mae`] e
+//! [`crate::calcular_r2`] com as métricas usuais em avaliação de previsões.
+//! Todas recebem `(y_real, y_previsto)` e podem ser passadas diretamente a
+//! [`crate::validacao::ResultadoValidacao::por_horizonte`] (a MASE, que
+//! depende do treino, via closure). Métricas percentuais são devolvidas em
+//! porcentagem e, quando um valor real nulo as torna indefinidas, retornam
+//! [`RegressaoError::ValoresReaisNulos`] em vez de um número arbitrário.
+
+use alloc::vec::Vec;
+
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::matematica::Real as _;
+use crate::numerico::{valores, Flutuante};
+use crate::soma::{self, soma_compensada, SomaCompensada};
+use crate::{RegressaoError, Resultado};
+
+fn validar<R: Flutuante, P: Flutuante>(y_real: &[R], y_previsto: &[P]) -> Resultado<()> {
+    if y_real.is_empty() || y_previsto.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if y_real.len() != y_previsto.len() {
+        return Err(RegressaoError::TamanhosDiferentes);
+    }
+    Ok(())
+}
+
+/// Calcula o MAPE (Mean Absolute Percentage Error), em %
+///
+/// `100/n Σ |y - ŷ| / |y|`; indefinido se algum valor real for zero.
+pub fn calcular_mape<R: Flutuante, P: Flutuante>(y_real: &[R], y_previsto: &[P]) -> Resultado<f64> {
+    validar(y_real, y_previsto)?;
+    if valores(y_real).any(|v| v == 0.0) {
+        return Err(RegressaoError::ValoresReaisNulos);
+    }
+
+    let soma = soma_compensada(
+        valores(y_real).zip(valores(y_previsto)).map(|(real, prev)| ((real - prev) / real).abs()),
+    );
+    Ok(100.0 * soma / y_real.len() as f64)
+}
+
+/// Calcula o sMAPE (MAPE simétrico), em %, no intervalo [0, 200]
+///
+/// `100/n Σ 2|y - ŷ| / (|y| + |ŷ|)`. Um valor real nulo é admitido; quando
+/// real e previsto são ambos nulos a previsão é exata e o termo vale zero.
+pub fn calcular_smape<R: Flutuante, P: Flutuante>(y_real: &[R], y_previsto: &[P]) -> Resultado<f64> {
+    validar(y_real, y_previsto)?;
+
+    let soma = soma_compensada(valores(y_real).zip(valores(y_previsto)).map(|(real, prev)| {
+        let denominador = real.abs() + prev.abs();
+        if denominador == 0.0 { 0.0 } else { 2.0 * (real - prev).abs() / denominador }
+    }));
+    Ok(100.0 * soma / y_real.len() as f64)
+}
+
+/// Calcula o WAPE (erro percentual absoluto ponderado), em %
+///
+/// `100 Σ |y - ŷ| / Σ |y|`: admite valores reais nulos isolados e só é
+/// indefinido se todos forem zero.
+pub fn calcular_wape<R: Flutuante, P: Flutuante>(y_real: &[R], y_previsto: &[P]) -> Resultado<f64> {
+    validar(y_real, y_previsto)?;
+    let total = soma_compensada(valores(y_real).map(|v| v.abs()));
+    if total == 0.0 {
+        return Err(RegressaoError::ValoresReaisNulos);
+    }
+
+    let erros = soma_compensada(valores(y_real).zip(valores(y_previsto)).map(|(real, prev)| (real - prev).abs()));
+    Ok(100.0 * erros / total)
+}
+
+/// Calcula a MASE (Mean Absolute Scaled Error) de Hyndman e Koehler
+///
+/// Divide o MAE da previsão pelo MAE dentro da amostra da previsão ingênua
+/// sazonal no treino, `1/(n-m) Σ |y_t - y_{t-m}|`. Valores abaixo de 1
+/// indicam previsões melhores que a ingênua.
+///
+/// # Argumentos
+/// * `y_real` - Valores reais do período de teste
+/// * `y_previsto` - Previsões para o período de teste
+/// * `treino` - Série usada no ajuste (define a escala)
+/// * `periodo` - Período sazonal `m` (1 para a previsão ingênua simples)
+pub fn calcular_mase<R: Flutuante, P: Flutuante, T: Flutuante>(
+    y_real: &[R],
+    y_previsto: &[P],
+    treino: &[T],
+    periodo: usize,
+) -> Resultado<f64> {
+    validar(y_real, y_previsto)?;
+    if periodo == 0 {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    if treino.len() <= periodo {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let diferencas = valores(&treino[periodo..]).zip(valores(treino)).map(|(atual, anterior)| (atual - anterior).abs());
+    let escala = soma_compensada(diferencas)
+        / (treino.len() - periodo) as f64;
+    if escala == 0.0 {
+        return Err(RegressaoError::VarianciaZero);
+    }
+
+    let mae = soma_compensada(valores(y_real).zip(valores(y_previsto)).map(|(real, prev)| (real - prev).abs()))
+        / y_real.len() as f64;
+    Ok(mae / escala)
+}
+
+/// Calcula o RMSLE (raiz do erro quadrático médio logarítmico)
+///
+/// `sqrt(1/n Σ (ln(1 + ŷ) - ln(1 + y))²)`; exige valores não negativos.
+pub fn calcular_rmsle<R: Flutuante, P: Flutuante>(y_real: &[R], y_previsto: &[P]) -> Resultado<f64> {
+    validar(y_real, y_previsto)?;
+    if valores(y_real).chain(valores(y_previsto)).any(|v| v < 0.0) {
+        return Err(RegressaoError::ValoresNaoPositivos);
+    }
+
+    let soma = soma_compensada(
+        valores(y_real).zip(valores(y_previsto)).map(|(real, prev)| (prev.ln_1p() - real.ln_1p()).powi(2)),
+    );
+    Ok((soma / y_real.len() as f64).sqrt())
+}
+
+/// Calcula a mediana dos erros absolutos (robusta a erros extremos)
+pub fn calcular_mediana_erro_absoluto<R: Flutuante, P: Flutuante>(
+    y_real: &[R],
+    y_previsto: &[P],
+) -> Resultado<f64> {
+    validar(y_real, y_previsto)?;
+
+    let mut erros: Vec<f64> = valores(y_real)
+        .zip(valores(y_previsto))
+        .map(|(real, prev)| (real - prev).abs())
+        .collect();
+    erros.sort_by(|a, b| a.total_cmp(b));
+    let n = erros.len();
+    if n.is_multiple_of(2) {
+        Ok((erros[n / 2 - 1] + erros[n / 2]) / 2.0)
+    } else {
+        Ok(erros[n / 2])
+    }
+}
+
+/// Calcula o maior erro absoluto
+pub fn calcular_erro_maximo<R: Flutuante, P: Flutuante>(y_real: &[R], y_previsto: &[P]) -> Resultado<f64> {
+    validar(y_real, y_previsto)?;
+    Ok(valores(y_real).zip(valores(y_previsto)).map(|(real, prev)| (real - prev).abs()).fold(0.0, f64::max))
+}
+
+/// Calcula a variância explicada: `1 - Var(y - ŷ) / Var(y)`
+///
+/// Difere do R² por ignorar um viés constante nas previsões.
+pub fn calcular_variancia_explicada<R: Flutuante, P: Flutuante>(y_real: &[R], y_previsto: &[P]) -> Resultado<f64> {
+    validar(y_real, y_previsto)?;
+
+    let n = y_real.len() as f64;
+    let media_real = soma::media(y_real);
+    let media_erro = soma_compensada(valores(y_real).zip(valores(y_previsto)).map(|(real, prev)| real - prev)) / n;
+    let variancia_real = soma_compensada(valores(y_real).map(|v| (v - media_real).powi(2)));
+    let variancia_erro = soma_compensada(
+        valores(y_real).zip(valores(y_previsto)).map(|(real, prev)| (real - prev - media_erro).powi(2)),
+    );
+    if soma::variancia_desprezivel(variancia_real, y_real.len(), soma::maior_absoluto(y_real)) {
+        return Err(RegressaoError::VarianciaZero);
+    }
+
+    Ok(1.0 - variancia_erro / variancia_real)
+}
+
+/// Calcula o U de Theil (U2), que compara a previsão com a ingênua
+///
+/// `U = sqrt(Σ ((ŷ_{t+1} - y_{t+1}) / y_t)²) / sqrt(Σ ((y_{t+1} - y_t) / y_t)²)`
+///
+/// `U < 1` indica previsões melhores que repetir o valor anterior e `U = 1`
+/// equivale a ele. Os valores reais, usados como base das variações
+/// relativas, não podem ser nulos (exceto o último).
+pub fn calcular_u_theil<R: Flutuante, P: Flutuante>(y_real: &[R], y_previsto: &[P]) -> Resultado<f64> {
+    validar(y_real, y_previsto)?;
+    let n = y_real.len();
+    if n < 2 {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+    if valores(&y_real[..n - 1]).any(|v| v == 0.0) {
+        return Err(RegressaoError::ValoresReaisNulos);
+    }
+
+    let mut numerador = SomaCompensada::new();
+    let mut denominador = SomaCompensada::new();
+    for t in 0..n - 1 {
+        let base = y_real[t].para_f64();
+        let (real, previsto) = (y_real[t + 1].para_f64(), y_previsto[t + 1].para_f64());
+        numerador.adicionar(((previsto - real) / base).powi(2));
+        denominador.adicionar(((real - base) / base).powi(2));
+    }
+    let (numerador, denominador) = (numerador.total(), denominador.total());
+    if denominador == 0.0 {
+        return Err(RegressaoError::VarianciaZero);
+    }
+
+    Ok((numerador / denominador).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REAL: [f64; 5] = [100.0, 110.0, 120.0, 130.0, 140.0];
+    const PREVISTO: [f64; 5] = [90.0, 110.0, 126.0, 130.0, 147.0];
+
+    #[test]
+    fn test_metricas_percentuais() {
+        let mape = (10.0 / 100.0 + 0.0 + 6.0 / 120.0 + 0.0 + 7.0 / 140.0) / 5.0 * 100.0;
+        assert!((calcular_mape(&REAL, &PREVISTO).unwrap() - mape).abs() < 1e-12);
+
+        let smape = (20.0 / 190.0 + 12.0 / 246.0 + 14.0 / 287.0) / 5.0 * 100.0;
+        assert!((calcular_smape(&REAL, &PREVISTO).unwrap() - smape).abs() < 1e-12);
+        assert_eq!(calcular_smape(&[0.0, 2.0], &[0.0, 2.0]).unwrap(), 0.0);
+        assert_eq!(calcular_smape(&[0.0], &[5.0]).unwrap(), 200.0);
+
+        assert!((calcular_wape(&REAL, &PREVISTO).unwrap() - 2300.0 / 600.0).abs() < 1e-12);
+        assert!((calcular_wape(&[0.0, 4.0], &[1.0, 3.0]).unwrap() - 50.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_valores_reais_nulos() {
+        assert!(matches!(calcular_mape(&[0.0, 1.0], &[1.0, 1.0]), Err(RegressaoError::ValoresReaisNulos)));
+        assert!(matches!(calcular_wape(&[0.0, 0.0], &[1.0, 1.0]), Err(RegressaoError::ValoresReaisNulos)));
+        assert!(matches!(calcular_u_theil(&[0.0, 1.0], &[1.0, 1.0]), Err(RegressaoError::ValoresReaisNulos)));
+        assert!(matches!(calcular_rmsle(&[-1.0], &[1.0]), Err(RegressaoError::ValoresNaoPositivos)));
+        assert!(matches!(calcular_mape::<f64, f64>(&[], &[]), Err(RegressaoError::DadosVazios)));
+        assert!(matches!(calcular_smape(&[1.0], &[1.0, 2.0]), Err(RegressaoError::TamanhosDiferentes)));
+    }
+
+    #[test]
+    fn test_mase_sazonal() {
+        let treino = [1.0, 5.0, 2.0, 6.0, 3.0, 7.0];
+        // Escala sazonal (m = 2): todas as diferenças valem 1
+        assert!((calcular_mase(&[4.0, 8.0], &[5.0, 8.0], &treino, 2).unwrap() - 0.5).abs() < 1e-12);
+        // Escala simples (m = 1): média de |Δ| = (4 + 3 + 4 + 3 + 4) / 5
+        assert!((calcular_mase(&[4.0, 8.0], &[5.0, 8.0], &treino, 1).unwrap() - 0.5 / 3.6).abs() < 1e-12);
+        assert!(matches!(calcular_mase(&[1.0], &[1.0], &[2.0, 2.0], 1), Err(RegressaoError::VarianciaZero)));
+        assert!(matches!(calcular_mase(&[1.0], &[1.0], &treino, 6), Err(RegressaoError::DadosInsuficientes)));
+    }
+
+    #[test]
+    fn test_demais_metricas() {
+        let rmsle = ((11f64.ln() - 3f64.ln()).powi(2) / 2.0).sqrt();
+        assert!((calcular_rmsle(&[2.0, 5.0], &[10.0, 5.0]).unwrap() - rmsle).abs() < 1e-12);
+
+        assert_eq!(calcular_mediana_erro_absoluto(&REAL, &PREVISTO).unwrap(), 6.0);
+        assert_eq!(calcular_erro_maximo(&REAL, &PREVISTO).unwrap(), 10.0);
+
+        // Viés constante: variância explicada 1, R² menor
+        let enviesado: Vec<f64> = REAL.iter().map(|v| v + 5.0).collect();
+        assert!((calcular_variancia_explicada(&REAL, &enviesado).unwrap() - 1.0).abs() < 1e-12);
+
+        // Previsão ingênua tem U = 1; previsão perfeita, U = 0
+        let ingenua = [100.0, 100.0, 110.0, 120.0, 130.0];
+        assert!((calcular_u_theil(&REAL, &ingenua).unwrap() - 1.0).abs() < 1e-12);
+        assert_eq!(calcular_u_theil(&REAL, &REAL).unwrap(), 0.0);
+    }
+}
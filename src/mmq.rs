@@ -0,0 +1,84 @@
+//! Mínimos quadrados ordinários multivariados (uso interno)
+//!
+//! Resolve `min ||y - Xb||²` pela decomposição QR de Householder de
+//! [`crate::matriz`], evitando as equações normais.
+
+use crate::matriz::Matriz;
+use crate::soma::soma_compensada;
+use crate::{RegressaoError, Resultado};
+
+/// Resultado de um ajuste por mínimos quadrados
+#[derive(Debug, Clone)]
+pub(crate) struct AjusteMmq {
+    pub coeficientes: Vec<f64>,
+    pub erros_padrao: Vec<f64>,
+    pub residuos: Vec<f64>,
+    pub soma_quadrados_residuos: f64,
+}
+
+/// Ajusta `y` sobre as colunas de `colunas` (cada uma com `y.len()` linhas)
+pub(crate) fn ajustar_mmq(colunas: &[Vec<f64>], y: &[f64]) -> Resultado<AjusteMmq> {
+    let n = y.len();
+    let k = colunas.len();
+    if n == 0 || k == 0 {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if colunas.iter().any(|c| c.len() != n) {
+        return Err(RegressaoError::TamanhosDiferentes);
+    }
+    if n <= k {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let x = Matriz::de_colunas(colunas)?;
+    let qr = x.qr()?;
+    let coeficientes = qr.resolver(y)?;
+
+    let residuos: Vec<f64> = x.aplicar(&coeficientes)?.iter().zip(y).map(|(p, v)| v - p).collect();
+    let soma_quadrados_residuos = soma_compensada(residuos.iter().map(|e| e * e));
+    let sigma2 = soma_quadrados_residuos / (n - k) as f64;
+    let erros_padrao = qr.diagonal_inversa_ata()?.iter().map(|d| (sigma2 * d).sqrt()).collect();
+
+    Ok(AjusteMmq {
+        coeficientes,
+        erros_padrao,
+        residuos,
+        soma_quadrados_residuos,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mmq_reta_exata() {
+        let x = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let y: Vec<f64> = x.iter().map(|v| 3.0 * v - 1.0).collect();
+        let ajuste = ajustar_mmq(&[vec![1.0; 5], x], &y).unwrap();
+
+        assert!((ajuste.coeficientes[0] + 1.0).abs() < 1e-12);
+        assert!((ajuste.coeficientes[1] - 3.0).abs() < 1e-12);
+        assert!(ajuste.soma_quadrados_residuos < 1e-20);
+    }
+
+    #[test]
+    fn test_mmq_erros_padrao() {
+        // Referência: lm(y ~ x) no R
+        let x = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let y = vec![2.1, 3.9, 6.1, 7.8, 10.2];
+        let ajuste = ajustar_mmq(&[vec![1.0; 5], x], &y).unwrap();
+
+        assert!((ajuste.coeficientes[1] - 2.01).abs() < 1e-10);
+        assert!((ajuste.erros_padrao[1] - 0.059_721_6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_mmq_colinear() {
+        let x = vec![1.0, 2.0, 3.0, 4.0];
+        let dobro: Vec<f64> = x.iter().map(|v| 2.0 * v).collect();
+        let resultado = ajustar_mmq(&[x, dobro], &[1.0, 2.0, 3.0, 4.0]);
+
+        assert!(matches!(resultado, Err(RegressaoError::VarianciaZero)));
+    }
+}
@@ -0,0 +1,124 @@
+//! # Tipos de ponto flutuante aceitos
+//!
+//! Os ajustes, métricas e estatísticas da raiz da biblioteca, de
+//! [`crate::metricas`] e de [`crate::descritivas`] recebem fatias de qualquer
+//! [`Flutuante`] — `f32` ou `f64` — sem exigir cópia ou alargamento prévio
+//! dos dados. Com `std`, o mesmo vale para as estatísticas ponderadas, para
+//! [`crate::correlacao::correlacao`] e [`crate::correlacao::coeficiente_correlacao`]
+//! e para os testes de tendência de [`crate::tendencia`]. Internamente cada
+//! valor é convertido para `f64` ao ser lido e as somas são compensadas
+//! ([`crate::soma`]), de modo que buffers `f32` são acumulados com precisão
+//! maior que a de entrada. Os resultados são sempre `f64`.
+//!
+//! Ficam só em `f64`, de propósito, os módulos que guardam ou transformam
+//! a série (ARIMA, janelas, quebras, sazonalidade, transformações, séries
+//! datadas e afins), as matrizes de correlação e de covariância, que
+//! recebem colunas `Vec<f64>`, e a álgebra linear de [`crate::matriz`] e
+//! [`crate::mmq`].
+
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Tipo de ponto flutuante lido pelas funções genéricas da biblioteca
+pub trait Flutuante: Copy + PartialOrd + fmt::Debug + 'static {
+    /// Converte para `f64` (exato para `f32` e `f64`)
+    fn para_f64(self) -> f64;
+}
+
+impl Flutuante for f64 {
+    fn para_f64(self) -> f64 {
+        self
+    }
+}
+
+impl Flutuante for f32 {
+    fn para_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+/// Iterador dos valores de uma fatia convertidos para `f64`
+pub(crate) fn valores<T: Flutuante>(dados: &[T]) -> impl ExactSizeIterator<Item = f64> + Clone + '_ {
+    dados.iter().map(|v| v.para_f64())
+}
+
+/// Cópia em `f64` de uma fatia (para algoritmos que precisam ordenar)
+pub(crate) fn para_vec_f64<T: Flutuante>(dados: &[T]) -> Vec<f64> {
+    valores(dados).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use crate::{calcular_estatisticas, calcular_mse, calcular_r2, metricas, regressao_linear, regressao_linear_xy};
+
+    #[test]
+    fn test_f32_igual_a_f64_alargado() {
+        let y32: Vec<f32> = vec![2.1, 3.9, 6.2, 7.8, 10.1, 12.2, 13.8];
+        let y64 = para_vec_f64(&y32);
+
+        assert_eq!(regressao_linear(&y32).unwrap(), regressao_linear(&y64).unwrap());
+        let x32: Vec<f32> = (0..7).map(|i| i as f32 * 0.5).collect();
+        assert_eq!(
+            regressao_linear_xy(&x32, &y32).unwrap(),
+            regressao_linear_xy(&para_vec_f64(&x32), &y64).unwrap()
+        );
+
+        let e32 = calcular_estatisticas(&y32).unwrap();
+        let e64 = calcular_estatisticas(&y64).unwrap();
+        assert_eq!(e32.media, e64.media);
+        assert_eq!(e32.variancia, e64.variancia);
+        assert_eq!(e32.terceiro_quartil, e64.terceiro_quartil);
+    }
+
+    #[test]
+    fn test_metricas_com_tipos_mistos() {
+        let real: [f32; 4] = [1.0, 2.0, 3.0, 4.0];
+        let previsto: [f64; 4] = [1.5, 2.0, 2.5, 4.0];
+        assert_eq!(calcular_mse(&real, &previsto).unwrap(), 0.125);
+        assert_eq!(calcular_r2(&real, &previsto).unwrap(), 1.0 - 0.5 / 5.0);
+        assert!((metricas::calcular_wape(&real, &previsto).unwrap() - 10.0).abs() < 1e-12);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_f32_em_ponderadas_correlacao_e_tendencia() {
+        use crate::correlacao::{correlacao, MetodoCorrelacao};
+        use crate::ponderadas::{calcular_estatisticas_ponderadas, quantis_ponderados, TipoPeso};
+        use crate::tendencia::{inclinacao_sen, teste_mann_kendall, VarianteMannKendall};
+
+        let y32: Vec<f32> = vec![2.1, 3.9, 3.9, 7.8, 6.1, 12.2, 13.8, 11.0];
+        let pesos32: [f32; 8] = [1.0, 2.0, 0.5, 1.0, 3.0, 1.0, 0.0, 2.0];
+        let (y64, pesos64) = (para_vec_f64(&y32), para_vec_f64(&pesos32));
+
+        let p32 = calcular_estatisticas_ponderadas(&y32, &pesos32, TipoPeso::Confiabilidade).unwrap();
+        let p64 = calcular_estatisticas_ponderadas(&y64, &pesos64, TipoPeso::Confiabilidade).unwrap();
+        assert_eq!((p32.media, p32.variancia, p32.mediana), (p64.media, p64.variancia, p64.mediana));
+        assert_eq!(
+            quantis_ponderados(&y32, &pesos32, &[0.1, 0.9], TipoPeso::Frequencia).unwrap(),
+            quantis_ponderados(&y64, &pesos64, &[0.1, 0.9], TipoPeso::Frequencia).unwrap()
+        );
+
+        let x: Vec<f64> = (0..8).map(|i| i as f64).collect();
+        for metodo in [MetodoCorrelacao::Pearson, MetodoCorrelacao::Spearman, MetodoCorrelacao::Kendall] {
+            let c32 = correlacao(&x, &y32, metodo, 0.95).unwrap();
+            let c64 = correlacao(&x, &y64, metodo, 0.95).unwrap();
+            assert_eq!((c32.coeficiente, c32.valor_p), (c64.coeficiente, c64.valor_p));
+        }
+
+        let original = VarianteMannKendall::Original;
+        assert_eq!(teste_mann_kendall(&y32, original).unwrap().valor_p, teste_mann_kendall(&y64, original).unwrap().valor_p);
+        assert_eq!(inclinacao_sen(&y32, 0.9).unwrap().inclinacao, inclinacao_sen(&y64, 0.9).unwrap().inclinacao);
+    }
+
+    #[test]
+    fn test_acumulacao_de_f32_em_precisao_maior() {
+        // Somar 1e6 vezes 0.1f32 em f32 erra na quarta casa; alargado não
+        let dados = vec![0.1f32; 1_000_000];
+        let ingenua = dados.iter().sum::<f32>() / dados.len() as f32;
+        let media = calcular_estatisticas(&dados).unwrap().media;
+        assert!((ingenua as f64 - 0.1f32 as f64).abs() > 1e-4);
+        assert!((media - 0.1f32 as f64).abs() < 1e-15);
+    }
+}
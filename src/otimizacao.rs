@@ -0,0 +1,140 @@
+//! Rotinas internas de otimização numérica sem derivadas
+
+/// Minimiza `f` pelo método simplex de Nelder–Mead
+///
+/// Retorna o melhor ponto encontrado e o valor da função nele. Valores não
+/// finitos de `f` são tratados como piores que qualquer valor finito, o que
+/// permite usar `f64::INFINITY` como penalidade para regiões inválidas.
+pub(crate) fn nelder_mead<F>(f: F, inicio: &[f64], passo: f64, max_iter: usize) -> (Vec<f64>, f64)
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let n = inicio.len();
+    let avaliar = |x: &[f64]| {
+        let v = f(x);
+        if v.is_finite() { v } else { f64::INFINITY }
+    };
+
+    if n == 0 {
+        return (Vec::new(), avaliar(inicio));
+    }
+
+    let mut simplex: Vec<Vec<f64>> = Vec::with_capacity(n + 1);
+    simplex.push(inicio.to_vec());
+    for i in 0..n {
+        let mut vertice = inicio.to_vec();
+        vertice[i] += if vertice[i].abs() > 1e-8 { passo * vertice[i].abs().max(1.0) } else { passo };
+        simplex.push(vertice);
+    }
+    let mut valores: Vec<f64> = simplex.iter().map(|x| avaliar(x)).collect();
+
+    for _ in 0..max_iter {
+        let mut ordem: Vec<usize> = (0..=n).collect();
+        ordem.sort_by(|&a, &b| valores[a].total_cmp(&valores[b]));
+        simplex = ordem.iter().map(|&i| simplex[i].clone()).collect();
+        valores = ordem.iter().map(|&i| valores[i]).collect();
+
+        let melhor = valores[0];
+        let pior = valores[n];
+        if pior.is_finite() && (pior - melhor).abs() <= 1e-10 * (melhor.abs() + 1e-10) {
+            break;
+        }
+
+        let centroide: Vec<f64> = (0..n)
+            .map(|j| simplex[..n].iter().map(|v| v[j]).sum::<f64>() / n as f64)
+            .collect();
+        let ponto = |coef: f64| -> Vec<f64> {
+            (0..n).map(|j| centroide[j] + coef * (simplex[n][j] - centroide[j])).collect()
+        };
+
+        let refletido = ponto(-1.0);
+        let f_refletido = avaliar(&refletido);
+
+        if f_refletido < valores[0] {
+            let expandido = ponto(-2.0);
+            let f_expandido = avaliar(&expandido);
+            if f_expandido < f_refletido {
+                simplex[n] = expandido;
+                valores[n] = f_expandido;
+            } else {
+                simplex[n] = refletido;
+                valores[n] = f_refletido;
+            }
+        } else if f_refletido < valores[n - 1] {
+            simplex[n] = refletido;
+            valores[n] = f_refletido;
+        } else {
+            let contraido = if f_refletido < valores[n] { ponto(-0.5) } else { ponto(0.5) };
+            let f_contraido = avaliar(&contraido);
+            if f_contraido < valores[n].min(f_refletido) {
+                simplex[n] = contraido;
+                valores[n] = f_contraido;
+            } else {
+                let (melhor_vertice, demais) = simplex.split_at_mut(1);
+                for (vertice, valor) in demais.iter_mut().zip(valores[1..].iter_mut()) {
+                    for (x, x0) in vertice.iter_mut().zip(&melhor_vertice[0]) {
+                        *x = x0 + 0.5 * (*x - x0);
+                    }
+                    *valor = avaliar(vertice);
+                }
+            }
+        }
+    }
+
+    let indice_melhor = (0..=n)
+        .min_by(|&a, &b| valores[a].total_cmp(&valores[b]))
+        .unwrap_or(0);
+    (simplex[indice_melhor].clone(), valores[indice_melhor])
+}
+
+/// Minimiza uma função unimodal em `[a, b]` por seção áurea
+///
+/// Retorna o ponto de mínimo com precisão absoluta `tolerancia`.
+pub(crate) fn secao_aurea<F>(f: F, mut a: f64, mut b: f64, tolerancia: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let razao = (5f64.sqrt() - 1.0) / 2.0;
+    let mut c = b - razao * (b - a);
+    let mut d = a + razao * (b - a);
+    let mut fc = f(c);
+    let mut fd = f(d);
+
+    while (b - a).abs() > tolerancia {
+        if fc < fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - razao * (b - a);
+            fc = f(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + razao * (b - a);
+            fd = f(d);
+        }
+    }
+    (a + b) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nelder_mead_rosenbrock() {
+        let rosenbrock = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+        let (x, fx) = nelder_mead(rosenbrock, &[-1.2, 1.0], 0.5, 5000);
+
+        assert!((x[0] - 1.0).abs() < 1e-3);
+        assert!((x[1] - 1.0).abs() < 1e-3);
+        assert!(fx < 1e-6);
+    }
+
+    #[test]
+    fn test_secao_aurea_parabola() {
+        let minimo = secao_aurea(|x| (x - 0.3).powi(2), -2.0, 2.0, 1e-9);
+        assert!((minimo - 0.3).abs() < 1e-8);
+    }
+}
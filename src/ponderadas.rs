@@ -0,0 +1,257 @@
+//! # Estatísticas descritivas ponderadas
+//!
+//! Média, variância, mediana e quantis com pesos por observação. O tipo de
+//! peso define a correção de viés da variância e a regra dos quantis:
+//! pesos de frequência contam repetições (o resultado é o mesmo de repetir
+//! cada valor `w` vezes), enquanto pesos de confiabilidade só medem a
+//! importância relativa e não dependem da escala.
+
+use std::fmt;
+
+use crate::numerico::{valores, Flutuante};
+use crate::soma::soma_compensada;
+use crate::{RegressaoError, Resultado};
+
+/// Interpretação dos pesos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TipoPeso {
+    /// Número de vezes que cada valor foi observado; variância com divisor
+    /// `Σw - 1` e quantis do método 7 sobre a amostra expandida
+    Frequencia,
+    /// Importância relativa (ex.: inverso da variância); variância com
+    /// divisor `Σw - Σw²/Σw` e quantis do método 5 generalizado, ambos
+    /// invariantes à escala dos pesos
+    Confiabilidade,
+}
+
+/// Resultado das estatísticas ponderadas
+#[derive(Debug, Clone)]
+pub struct EstatisticasPonderadas {
+    pub tipo_peso: TipoPeso,
+    pub media: f64,
+    pub mediana: f64,
+    /// Variância corrigida para o tipo de peso; `NaN` se o divisor não for positivo
+    pub variancia: f64,
+    pub desvio_padrao: f64,
+    pub soma_pesos: f64,
+    /// Tamanho efetivo de Kish, `(Σw)² / Σw²`
+    pub tamanho_efetivo: f64,
+}
+
+impl fmt::Display for EstatisticasPonderadas {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let tipo = match self.tipo_peso {
+            TipoPeso::Frequencia => "frequência",
+            TipoPeso::Confiabilidade => "confiabilidade",
+        };
+        writeln!(f, "=== Estatísticas Ponderadas ({}) ===", tipo)?;
+        writeln!(f, "Média: {:.6}", self.media)?;
+        writeln!(f, "Mediana: {:.6}", self.mediana)?;
+        writeln!(f, "Desvio Padrão: {:.6}", self.desvio_padrao)?;
+        writeln!(f, "Variância: {:.6}", self.variancia)?;
+        writeln!(f, "Soma dos Pesos: {:.6}", self.soma_pesos)?;
+        writeln!(f, "Tamanho Efetivo: {:.6}", self.tamanho_efetivo)?;
+        Ok(())
+    }
+}
+
+fn validar<D: Flutuante, W: Flutuante>(dados: &[D], pesos: &[W]) -> Resultado<()> {
+    if dados.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if dados.len() != pesos.len() {
+        return Err(RegressaoError::TamanhosDiferentes);
+    }
+    if valores(pesos).any(|w| !w.is_finite() || w < 0.0) || valores(pesos).all(|w| w == 0.0) {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    Ok(())
+}
+
+/// Pares (valor, peso) ordenados pelo valor, sem os pesos nulos
+fn ordenar_pares<D: Flutuante, W: Flutuante>(dados: &[D], pesos: &[W]) -> Vec<(f64, f64)> {
+    let mut pares: Vec<(f64, f64)> = valores(dados).zip(valores(pesos)).filter(|&(_, w)| w > 0.0).collect();
+    pares.sort_by(|a, b| a.0.total_cmp(&b.0));
+    pares
+}
+
+/// Quantil `p` de pares já ordenados
+fn quantil_pares(pares: &[(f64, f64)], p: f64, tipo: TipoPeso) -> f64 {
+    let total = soma_compensada(pares.iter().map(|&(_, w)| w));
+    match tipo {
+        TipoPeso::Frequencia => {
+            // Posição h = (W - 1)p + 1 na amostra expandida; x(k) é o valor
+            // cujo intervalo acumulado (cum_{i-1}, cum_i] contém k
+            let valor_em = |k: f64| {
+                let mut acumulado = 0.0;
+                for &(x, w) in pares {
+                    acumulado += w;
+                    if k <= acumulado {
+                        return x;
+                    }
+                }
+                pares[pares.len() - 1].0
+            };
+            let h = ((total - 1.0) * p + 1.0).max(1.0);
+            let j = h.floor();
+            valor_em(j) + (h - j) * (valor_em(j + 1.0) - valor_em(j))
+        }
+        TipoPeso::Confiabilidade => {
+            // Cada valor fica no centro da sua massa: (cum_{i-1} + w_i/2) / W
+            let alvo = p * total;
+            let mut acumulado = 0.0;
+            let mut anterior: Option<(f64, f64)> = None;
+            for &(x, w) in pares {
+                let centro = acumulado + w / 2.0;
+                if alvo <= centro {
+                    return match anterior {
+                        Some((x0, c0)) => x0 + (alvo - c0) / (centro - c0) * (x - x0),
+                        None => x,
+                    };
+                }
+                anterior = Some((x, centro));
+                acumulado += w;
+            }
+            pares[pares.len() - 1].0
+        }
+    }
+}
+
+/// Calcula quantis ponderados com uma única ordenação
+///
+/// # Argumentos
+/// * `dados` - Valores observados
+/// * `pesos` - Pesos não negativos, não todos nulos
+/// * `probabilidades` - Probabilidades em [0, 1]
+/// * `tipo` - Interpretação dos pesos
+pub fn quantis_ponderados<D: Flutuante, W: Flutuante>(
+    dados: &[D],
+    pesos: &[W],
+    probabilidades: &[f64],
+    tipo: TipoPeso,
+) -> Resultado<Vec<f64>> {
+    validar(dados, pesos)?;
+    if probabilidades.iter().any(|p| !(0.0..=1.0).contains(p)) {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    let pares = ordenar_pares(dados, pesos);
+    Ok(probabilidades.iter().map(|&p| quantil_pares(&pares, p, tipo)).collect())
+}
+
+/// Calcula o quantil ponderado `p`
+pub fn quantil_ponderado<D: Flutuante, W: Flutuante>(dados: &[D], pesos: &[W], p: f64, tipo: TipoPeso) -> Resultado<f64> {
+    Ok(quantis_ponderados(dados, pesos, &[p], tipo)?[0])
+}
+
+/// Calcula média, variância, mediana e tamanho efetivo ponderados
+///
+/// # Argumentos
+/// * `dados` - Valores observados
+/// * `pesos` - Pesos não negativos, não todos nulos
+/// * `tipo` - Interpretação dos pesos
+pub fn calcular_estatisticas_ponderadas<D: Flutuante, W: Flutuante>(
+    dados: &[D],
+    pesos: &[W],
+    tipo: TipoPeso,
+) -> Resultado<EstatisticasPonderadas> {
+    validar(dados, pesos)?;
+
+    let soma_pesos = soma_compensada(valores(pesos));
+    let soma_quadrados_pesos = soma_compensada(valores(pesos).map(|w| w * w));
+    let media = soma_compensada(valores(dados).zip(valores(pesos)).map(|(x, w)| w * x)) / soma_pesos;
+    let soma_quadrados = soma_compensada(valores(dados).zip(valores(pesos)).map(|(x, w)| w * (x - media).powi(2)));
+
+    let divisor = match tipo {
+        TipoPeso::Frequencia => soma_pesos - 1.0,
+        TipoPeso::Confiabilidade => soma_pesos - soma_quadrados_pesos / soma_pesos,
+    };
+    // Tolerância relativa: com um único peso positivo o divisor de
+    // confiabilidade é zero a menos de arredondamento
+    let variancia = if divisor > 1e-12 * soma_pesos { soma_quadrados / divisor } else { f64::NAN };
+
+    Ok(EstatisticasPonderadas {
+        tipo_peso: tipo,
+        media,
+        mediana: quantil_pares(&ordenar_pares(dados, pesos), 0.5, tipo),
+        variancia,
+        desvio_padrao: variancia.sqrt(),
+        soma_pesos,
+        tamanho_efetivo: soma_pesos * soma_pesos / soma_quadrados_pesos,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::descritivas::{quantis, MetodoQuantil};
+    use crate::{calcular_estatisticas, teste_util::Gerador};
+
+    #[test]
+    fn test_frequencia_equivale_a_repeticao() {
+        let dados = [3.0, 1.0, 4.0, 1.5, 9.0, 2.6];
+        let pesos = [2.0, 1.0, 3.0, 1.0, 1.0, 4.0];
+        let expandidos: Vec<f64> = dados.iter().zip(&pesos).flat_map(|(&x, &w)| vec![x; w as usize]).collect();
+
+        let ponderadas = calcular_estatisticas_ponderadas(&dados, &pesos, TipoPeso::Frequencia).unwrap();
+        let repetidas = calcular_estatisticas(&expandidos).unwrap();
+        assert!((ponderadas.media - repetidas.media).abs() < 1e-12);
+        assert!((ponderadas.variancia - repetidas.variancia).abs() < 1e-12);
+        assert_eq!(ponderadas.mediana, repetidas.mediana);
+        assert_eq!(ponderadas.soma_pesos, 12.0);
+
+        let probabilidades = [0.0, 0.1, 0.25, 0.33, 0.5, 0.9, 1.0];
+        assert_eq!(
+            quantis_ponderados(&dados, &pesos, &probabilidades, TipoPeso::Frequencia).unwrap(),
+            quantis(&expandidos, &probabilidades, MetodoQuantil::Tipo7).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_confiabilidade() {
+        // cov.wt(cbind(1:4), wt = 1:4 / 10)$cov no R: 10/7
+        let dados = [1.0, 2.0, 3.0, 4.0];
+        let pesos = [1.0, 2.0, 3.0, 4.0];
+        let estatisticas = calcular_estatisticas_ponderadas(&dados, &pesos, TipoPeso::Confiabilidade).unwrap();
+        assert!((estatisticas.media - 3.0).abs() < 1e-12);
+        assert!((estatisticas.variancia - 10.0 / 7.0).abs() < 1e-12);
+        assert!((estatisticas.tamanho_efetivo - 100.0 / 30.0).abs() < 1e-12);
+
+        // Invariância à escala dos pesos
+        let escalados: Vec<f64> = pesos.iter().map(|w| w * 0.037).collect();
+        let reescaladas = calcular_estatisticas_ponderadas(&dados, &escalados, TipoPeso::Confiabilidade).unwrap();
+        assert!((reescaladas.variancia - estatisticas.variancia).abs() < 1e-12);
+        assert!((reescaladas.mediana - estatisticas.mediana).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_quantis_de_confiabilidade() {
+        // Pesos iguais reproduzem o método 5 de Hyndman e Fan
+        let mut gerador = Gerador::new(4);
+        let dados: Vec<f64> = (0..37).map(|_| gerador.normal()).collect();
+        let probabilidades = [0.0, 0.05, 0.3, 0.5, 0.77, 1.0];
+        let ponderados = quantis_ponderados(&dados, &[0.5; 37], &probabilidades, TipoPeso::Confiabilidade).unwrap();
+        let esperados = quantis(&dados, &probabilidades, MetodoQuantil::Tipo5).unwrap();
+        for (a, b) in ponderados.iter().zip(&esperados) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        // Um peso dominante puxa a mediana; pesos nulos são ignorados.
+        // Centros acumulados: 0.5, 1.5, 2.5 e 8; alvo 6.5 de 13
+        let mediana = quantil_ponderado(&[1.0, 2.0, 3.0, 10.0, 50.0], &[1.0, 1.0, 1.0, 10.0, 0.0], 0.5, TipoPeso::Confiabilidade).unwrap();
+        assert!((mediana - (3.0 + 4.0 / 5.5 * 7.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ponderadas_erros() {
+        let tipo = TipoPeso::Confiabilidade;
+        assert!(matches!(calcular_estatisticas_ponderadas::<f64, f64>(&[], &[], tipo), Err(RegressaoError::DadosVazios)));
+        assert!(matches!(calcular_estatisticas_ponderadas(&[1.0], &[1.0, 2.0], tipo), Err(RegressaoError::TamanhosDiferentes)));
+        assert!(matches!(calcular_estatisticas_ponderadas(&[1.0, 2.0], &[1.0, -1.0], tipo), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(calcular_estatisticas_ponderadas(&[1.0, 2.0], &[0.0, 0.0], tipo), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(quantil_ponderado(&[1.0], &[1.0], -0.1, tipo), Err(RegressaoError::ParametroInvalido)));
+
+        // Um único peso positivo: variância indefinida nos dois tipos
+        assert!(calcular_estatisticas_ponderadas(&[1.0, 2.0], &[3.0, 0.0], tipo).unwrap().variancia.is_nan());
+        assert!(calcular_estatisticas_ponderadas(&[1.0], &[1.0], TipoPeso::Frequencia).unwrap().variancia.is_nan());
+    }
+}
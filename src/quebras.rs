@@ -0,0 +1,660 @@
+//! # Quebras estruturais e pontos de mudança
+//!
+//! Quando a série muda de regime (nova política de preços, nova versão de
+//! produto), uma única reta de [`crate::analise_completa`] atravessa os
+//! regimes. Este módulo oferece o teste de Chow para uma quebra conhecida,
+//! o teste CUSUM para uma quebra desconhecida e a detecção de múltiplas
+//! quebras por segmentação binária e PELT, tanto na média quanto na
+//! tendência linear. As quebras detectadas são ajustadas por segmento em
+//! [`ResultadoRegressao`] com o índice global como `x`, de modo que
+//! `prever_proximos_periodos(n, h)` do último segmento continua a série.
+
+use std::fmt;
+
+use crate::distribuicoes::f_cdf;
+use crate::soma::{self, soma_compensada, SomaCompensada};
+use crate::{RegressaoError, Resultado, ResultadoRegressao};
+
+/// Modelo ajustado dentro de cada segmento
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeloSegmento {
+    /// Nível constante (mudanças na média)
+    Media,
+    /// Reta `a·t + b` (mudanças de nível e de inclinação)
+    Tendencia,
+}
+
+impl ModeloSegmento {
+    fn parametros(self) -> usize {
+        match self {
+            ModeloSegmento::Media => 1,
+            ModeloSegmento::Tendencia => 2,
+        }
+    }
+}
+
+/// Penalidade por quebra adicional na detecção de múltiplas quebras
+///
+/// O custo de um segmento é a soma dos quadrados dos resíduos dividida por
+/// uma estimativa robusta da variância do ruído (MAD das primeiras
+/// diferenças), isto é, menos duas vezes a log-verossimilhança gaussiana.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Penalidade {
+    /// `(k + 1)·ln n`, com `k` parâmetros por segmento
+    Bic,
+    /// `2(k + 1)`
+    Aic,
+    /// Valor fixo, nas mesmas unidades do custo
+    Fixa(f64),
+}
+
+/// Resultado do teste de Chow
+#[derive(Debug, Clone)]
+pub struct TesteChow {
+    /// Estatística F
+    pub estatistica: f64,
+    pub valor_p: f64,
+    /// Índice da primeira observação do segundo regime
+    pub quebra: usize,
+    /// Graus de liberdade do numerador e do denominador
+    pub graus_liberdade: (usize, usize),
+}
+
+impl fmt::Display for TesteChow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "=== Teste de Chow ===")?;
+        writeln!(f, "Quebra no índice: {}", self.quebra)?;
+        writeln!(f, "Estatística F({}, {}): {:.6}", self.graus_liberdade.0, self.graus_liberdade.1, self.estatistica)?;
+        writeln!(f, "Valor-p: {:.6}", self.valor_p)?;
+        Ok(())
+    }
+}
+
+/// Resultado do teste CUSUM dos resíduos
+#[derive(Debug, Clone)]
+pub struct TesteCusum {
+    /// Máximo de `|S_j|`, onde `S_j` é a soma acumulada padronizada dos resíduos
+    pub estatistica: f64,
+    /// Valor-p assintótico (supremo da ponte browniana)
+    pub valor_p: f64,
+    /// Índice que maximiza `|S_j|`: primeira observação do suposto novo regime
+    pub quebra: usize,
+    /// `S_j` para `j = 1..n`
+    pub trajetoria: Vec<f64>,
+}
+
+impl fmt::Display for TesteCusum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "=== Teste CUSUM ===")?;
+        writeln!(f, "Estatística: {:.6}", self.estatistica)?;
+        writeln!(f, "Valor-p: {:.6}", self.valor_p)?;
+        writeln!(f, "Quebra mais provável no índice: {}", self.quebra)?;
+        Ok(())
+    }
+}
+
+/// Série dividida em regimes, com um ajuste por segmento
+#[derive(Debug, Clone)]
+pub struct Segmentacao {
+    pub modelo: ModeloSegmento,
+    /// Índices da primeira observação de cada regime após o primeiro
+    pub quebras: Vec<usize>,
+    /// Ajuste de cada segmento, com o índice global da série como `x`
+    pub segmentos: Vec<ResultadoRegressao>,
+}
+
+impl Segmentacao {
+    /// Intervalos de índices `[inicio, fim)` de cada segmento
+    pub fn limites(&self) -> Vec<(usize, usize)> {
+        let mut inicio = 0;
+        let mut limites = Vec::with_capacity(self.segmentos.len());
+        for segmento in &self.segmentos {
+            let fim = inicio + segmento.valores_previstos.len();
+            limites.push((inicio, fim));
+            inicio = fim;
+        }
+        limites
+    }
+
+    /// Ajuste do último regime, usado para prever a continuação da série
+    pub fn ultimo_segmento(&self) -> &ResultadoRegressao {
+        &self.segmentos[self.segmentos.len() - 1]
+    }
+}
+
+impl fmt::Display for Segmentacao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "=== Segmentação ({} regimes) ===", self.segmentos.len())?;
+        for ((inicio, fim), segmento) in self.limites().into_iter().zip(&self.segmentos) {
+            writeln!(
+                f,
+                "[{}, {}): inclinação {:.6}, intercepto {:.6}, R² {:.6}",
+                inicio, fim, segmento.inclinacao, segmento.intercepto, segmento.r_quadrado
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Ajusta `modelo` separadamente em cada regime delimitado por `quebras`
+///
+/// # Argumentos
+/// * `y` - Valores da série
+/// * `quebras` - Índices (crescentes) da primeira observação de cada novo regime
+/// * `modelo` - Modelo de cada segmento
+pub fn segmentar(y: &[f64], quebras: &[usize], modelo: ModeloSegmento) -> Resultado<Segmentacao> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+
+    let mut segmentos = Vec::with_capacity(quebras.len() + 1);
+    let mut inicio = 0;
+    for &fim in quebras.iter().chain(std::iter::once(&y.len())) {
+        if fim <= inicio || fim > y.len() {
+            return Err(RegressaoError::ParametroInvalido);
+        }
+        if fim - inicio < modelo.parametros() {
+            return Err(RegressaoError::DadosInsuficientes);
+        }
+        segmentos.push(ajustar_segmento(y, inicio, fim, modelo));
+        inicio = fim;
+    }
+
+    Ok(Segmentacao { modelo, quebras: quebras.to_vec(), segmentos })
+}
+
+fn ajustar_segmento(y: &[f64], inicio: usize, fim: usize, modelo: ModeloSegmento) -> ResultadoRegressao {
+    let trecho = &y[inicio..fim];
+    let m = trecho.len() as f64;
+    let media_y = trecho.iter().sum::<f64>() / m;
+
+    let (inclinacao, intercepto) = match modelo {
+        ModeloSegmento::Media => (0.0, media_y),
+        ModeloSegmento::Tendencia => {
+            let media_t = (inicio + fim - 1) as f64 / 2.0;
+            let mut stt = 0.0;
+            let mut sty = 0.0;
+            for (i, v) in trecho.iter().enumerate() {
+                let dt = (inicio + i) as f64 - media_t;
+                stt += dt * dt;
+                sty += dt * (v - media_y);
+            }
+            let inclinacao = sty / stt;
+            (inclinacao, media_y - inclinacao * media_t)
+        }
+    };
+
+    let valores_previstos: Vec<f64> = (inicio..fim).map(|t| inclinacao * t as f64 + intercepto).collect();
+    let mut ss_tot = 0.0;
+    let mut ss_res = 0.0;
+    let mut soma_abs = 0.0;
+    for (v, p) in trecho.iter().zip(&valores_previstos) {
+        ss_tot += (v - media_y).powi(2);
+        ss_res += (v - p).powi(2);
+        soma_abs += (v - p).abs();
+    }
+    // Segmento constante: o ajuste é perfeito
+    let r_quadrado = if ss_tot > 0.0 { 1.0 - ss_res / ss_tot } else { 1.0 };
+    let mse = ss_res / m;
+
+    ResultadoRegressao {
+        inclinacao,
+        intercepto,
+        r_quadrado,
+        mse,
+        rmse: mse.sqrt(),
+        mae: soma_abs / m,
+        valores_previstos,
+    }
+}
+
+/// Teste de Chow para uma quebra em índice conhecido
+///
+/// Compara a soma dos quadrados dos resíduos do modelo único com a dos
+/// modelos ajustados separadamente antes e depois de `quebra`:
+/// `F = ((S_c - S_1 - S_2) / k) / ((S_1 + S_2) / (n - 2k))`.
+///
+/// # Argumentos
+/// * `y` - Valores da série
+/// * `quebra` - Índice da primeira observação do segundo regime
+/// * `modelo` - Modelo comparado nos dois regimes
+pub fn teste_chow(y: &[f64], quebra: usize, modelo: ModeloSegmento) -> Resultado<TesteChow> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    let k = modelo.parametros();
+    let n = y.len();
+    if quebra == 0 || quebra >= n {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    if quebra < k || n - quebra < k || n <= 2 * k {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let somas = SomasPrefixadas::new(y);
+    let completo = somas.custo(0, n, modelo);
+    let separados = somas.custo(0, quebra, modelo) + somas.custo(quebra, n, modelo);
+    let gl_denominador = n - 2 * k;
+
+    let (estatistica, valor_p) = if separados > 0.0 {
+        let f = ((completo - separados).max(0.0) / k as f64) / (separados / gl_denominador as f64);
+        (f, 1.0 - f_cdf(f, k as f64, gl_denominador as f64))
+    } else if completo > 0.0 {
+        (f64::INFINITY, 0.0)
+    } else {
+        return Err(RegressaoError::VarianciaZero);
+    };
+
+    Ok(TesteChow { estatistica, valor_p, quebra, graus_liberdade: (k, gl_denominador) })
+}
+
+/// Teste CUSUM dos resíduos de mínimos quadrados (Ploberger–Krämer)
+///
+/// Ajusta `modelo` à série inteira e acumula os resíduos padronizados
+/// `S_j = Σ_{i<j} e_i / (σ̂ √n)`. Sob estabilidade, `max |S_j|` converge para o
+/// supremo de uma ponte browniana, cuja distribuição (de Kolmogorov) dá o valor-p.
+pub fn teste_cusum(y: &[f64], modelo: ModeloSegmento) -> Resultado<TesteCusum> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    let k = modelo.parametros();
+    let n = y.len();
+    if n <= k + 1 {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let ajuste = ajustar_segmento(y, 0, n, modelo);
+    let residuos: Vec<f64> = y.iter().zip(&ajuste.valores_previstos).map(|(v, p)| v - p).collect();
+    let soma_quadrados = soma_compensada(residuos.iter().map(|e| e * e));
+    if soma::variancia_desprezivel(soma_quadrados, n, soma::maior_absoluto(y)) {
+        return Err(RegressaoError::VarianciaZero);
+    }
+    let escala = (soma_quadrados / (n - k) as f64).sqrt() * (n as f64).sqrt();
+
+    let mut acumulado = 0.0;
+    let trajetoria: Vec<f64> = residuos.iter()
+        .map(|e| {
+            acumulado += e / escala;
+            acumulado
+        })
+        .collect();
+
+    let (indice, estatistica) = trajetoria[..n - 1].iter()
+        .map(|s| s.abs())
+        .enumerate()
+        .fold((0, 0.0), |melhor, (j, s)| if s > melhor.1 { (j, s) } else { melhor });
+
+    Ok(TesteCusum {
+        estatistica,
+        valor_p: cauda_kolmogorov(estatistica),
+        quebra: indice + 1,
+        trajetoria,
+    })
+}
+
+/// `P(sup |B(t)| > x)` para a ponte browniana: `2 Σ (-1)^{j-1} exp(-2 j² x²)`
+fn cauda_kolmogorov(x: f64) -> f64 {
+    // Abaixo de 0.3 a probabilidade é 1 em dupla precisão e a série converge devagar
+    if x < 0.3 {
+        return 1.0;
+    }
+    let mut soma = 0.0;
+    for j in 1..=100u32 {
+        let termo = (-2.0 * f64::from(j * j) * x * x).exp();
+        soma += if j % 2 == 1 { termo } else { -termo };
+        if termo < 1e-17 {
+            break;
+        }
+    }
+    (2.0 * soma).clamp(0.0, 1.0)
+}
+
+/// Detecta múltiplas quebras por segmentação binária
+///
+/// Divide recursivamente cada segmento no ponto que mais reduz o custo,
+/// enquanto a redução superar a penalidade. Rápida, mas aproximada: uma
+/// quebra escolhida cedo não é revista.
+///
+/// # Argumentos
+/// * `y` - Valores da série
+/// * `modelo` - Modelo de cada segmento
+/// * `penalidade` - Custo de cada quebra adicional
+/// * `tamanho_minimo` - Menor número de observações por segmento
+pub fn segmentacao_binaria(
+    y: &[f64],
+    modelo: ModeloSegmento,
+    penalidade: Penalidade,
+    tamanho_minimo: usize,
+) -> Resultado<Segmentacao> {
+    let (somas, escala, beta) = preparar(y, modelo, penalidade, tamanho_minimo)?;
+    let custo = |inicio, fim| somas.custo(inicio, fim, modelo) / escala;
+
+    let mut quebras = Vec::new();
+    let mut pendentes = vec![(0, y.len())];
+    while let Some((inicio, fim)) = pendentes.pop() {
+        if fim - inicio < 2 * tamanho_minimo {
+            continue;
+        }
+        let total = custo(inicio, fim);
+        let (melhor, custo_dividido) = (inicio + tamanho_minimo..=fim - tamanho_minimo)
+            .map(|tau| (tau, custo(inicio, tau) + custo(tau, fim)))
+            .fold((0, f64::INFINITY), |melhor, atual| if atual.1 < melhor.1 { atual } else { melhor });
+
+        if total - custo_dividido > beta {
+            quebras.push(melhor);
+            pendentes.push((inicio, melhor));
+            pendentes.push((melhor, fim));
+        }
+    }
+
+    quebras.sort_unstable();
+    segmentar(y, &quebras, modelo)
+}
+
+/// Detecta múltiplas quebras por PELT (Killick, Fearnhead e Eckley, 2012)
+///
+/// Minimiza exatamente `Σ custo(segmento) + β·(número de quebras)` por
+/// programação dinâmica, descartando inícios de segmento que não podem mais
+/// ser ótimos; o custo esperado é linear em `n`.
+///
+/// # Argumentos
+/// * `y` - Valores da série
+/// * `modelo` - Modelo de cada segmento
+/// * `penalidade` - Custo de cada quebra adicional
+/// * `tamanho_minimo` - Menor número de observações por segmento
+pub fn pelt(
+    y: &[f64],
+    modelo: ModeloSegmento,
+    penalidade: Penalidade,
+    tamanho_minimo: usize,
+) -> Resultado<Segmentacao> {
+    let (somas, escala, beta) = preparar(y, modelo, penalidade, tamanho_minimo)?;
+    let custo = |inicio, fim| somas.custo(inicio, fim, modelo) / escala;
+    let n = y.len();
+
+    let mut otimo = vec![f64::INFINITY; n + 1];
+    let mut anterior = vec![0; n + 1];
+    otimo[0] = -beta;
+    let mut candidatos: Vec<usize> = Vec::new();
+
+    for t in tamanho_minimo..=n {
+        let novo = t - tamanho_minimo;
+        if otimo[novo].is_finite() {
+            candidatos.push(novo);
+        }
+
+        let mut melhor = f64::INFINITY;
+        for &s in &candidatos {
+            let valor = otimo[s] + custo(s, t) + beta;
+            if valor < melhor {
+                melhor = valor;
+                anterior[t] = s;
+            }
+        }
+        otimo[t] = melhor;
+
+        candidatos.retain(|&s| otimo[s] + custo(s, t) <= melhor);
+    }
+
+    let mut quebras = Vec::new();
+    let mut t = n;
+    while t > 0 {
+        t = anterior[t];
+        if t > 0 {
+            quebras.push(t);
+        }
+    }
+    quebras.reverse();
+    segmentar(y, &quebras, modelo)
+}
+
+/// Valida os argumentos e calcula as somas, a escala do ruído e a penalidade
+fn preparar(
+    y: &[f64],
+    modelo: ModeloSegmento,
+    penalidade: Penalidade,
+    tamanho_minimo: usize,
+) -> Resultado<(SomasPrefixadas, f64, f64)> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if tamanho_minimo < modelo.parametros() {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    if y.len() < tamanho_minimo {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let k = modelo.parametros() as f64;
+    let beta = match penalidade {
+        Penalidade::Bic => (k + 1.0) * (y.len() as f64).ln(),
+        Penalidade::Aic => 2.0 * (k + 1.0),
+        Penalidade::Fixa(valor) if valor >= 0.0 => valor,
+        Penalidade::Fixa(_) => return Err(RegressaoError::ParametroInvalido),
+    };
+
+    let somas = SomasPrefixadas::new(y);
+    // Sem ruído a escala cai ao arredondamento dos custos, da ordem de
+    // ε vezes a soma de quadrados total
+    let soma_quadrados_total = somas.custo(0, y.len(), ModeloSegmento::Media);
+    let escala = variancia_ruido(y)
+        .max(f64::EPSILON * soma_quadrados_total)
+        .max(f64::MIN_POSITIVE);
+    Ok((somas, escala, beta))
+}
+
+/// Variância do ruído estimada pelo MAD das primeiras diferenças
+///
+/// As diferenças eliminam o nível e a tendência; quebras afetam poucas
+/// diferenças e quase não alteram a mediana.
+fn variancia_ruido(y: &[f64]) -> f64 {
+    if y.len() < 3 {
+        return 0.0;
+    }
+    let mut diferencas: Vec<f64> = y.windows(2).map(|w| w[1] - w[0]).collect();
+    let mediana = mediana_ordenando(&mut diferencas);
+    let mut desvios: Vec<f64> = diferencas.iter().map(|d| (d - mediana).abs()).collect();
+    let mad = mediana_ordenando(&mut desvios);
+    // MAD / Φ⁻¹(3/4) estima o desvio das diferenças, que é √2 vezes o do ruído
+    let sigma = mad / 0.674_489_750_196_081_7 / std::f64::consts::SQRT_2;
+    sigma * sigma
+}
+
+fn mediana_ordenando(valores: &mut [f64]) -> f64 {
+    valores.sort_by(|a, b| a.total_cmp(b));
+    let n = valores.len();
+    if n.is_multiple_of(2) {
+        (valores[n / 2 - 1] + valores[n / 2]) / 2.0
+    } else {
+        valores[n / 2]
+    }
+}
+
+/// Somas acumuladas que dão o custo de qualquer segmento em O(1)
+///
+/// `y` é centrado na média global para reduzir o cancelamento numérico, e
+/// as somas de `t` não são guardadas: num segmento de tamanho `m` o índice
+/// local `t - inicio` tem `Σ = m(m-1)/2` e soma de quadrados centrados
+/// `m(m²-1)/12` exatas, sem diferenças de prefixos da ordem de `n³`.
+struct SomasPrefixadas {
+    y: Vec<SomaCompensada>,
+    yy: Vec<SomaCompensada>,
+    ty: Vec<SomaCompensada>,
+}
+
+impl SomasPrefixadas {
+    fn new(y: &[f64]) -> Self {
+        let n = y.len();
+        let media = soma::media(y);
+        let mut somas = SomasPrefixadas {
+            y: Vec::with_capacity(n + 1),
+            yy: Vec::with_capacity(n + 1),
+            ty: Vec::with_capacity(n + 1),
+        };
+        let (mut soma_y, mut soma_yy, mut soma_ty) = (SomaCompensada::new(), SomaCompensada::new(), SomaCompensada::new());
+        for (i, v) in y.iter().enumerate() {
+            somas.y.push(soma_y);
+            somas.yy.push(soma_yy);
+            somas.ty.push(soma_ty);
+            let v = v - media;
+            soma_y.adicionar(v);
+            soma_yy.adicionar(v * v);
+            soma_ty.adicionar(i as f64 * v);
+        }
+        somas.y.push(soma_y);
+        somas.yy.push(soma_yy);
+        somas.ty.push(soma_ty);
+        somas
+    }
+
+    /// Soma dos quadrados dos resíduos de `modelo` ajustado a `y[inicio..fim]`
+    fn custo(&self, inicio: usize, fim: usize, modelo: ModeloSegmento) -> f64 {
+        let m = (fim - inicio) as f64;
+        let soma_y = self.y[fim].desde(&self.y[inicio]);
+        let syy = self.yy[fim].desde(&self.yy[inicio]) - soma_y * soma_y / m;
+
+        let ssr = match modelo {
+            ModeloSegmento::Media => syy,
+            ModeloSegmento::Tendencia => {
+                // Σ(t - inicio)·v = Σt·v - inicio·Σv, centrado em (m - 1)/2
+                let stt = m * (m * m - 1.0) / 12.0;
+                let sty = self.ty[fim].desde(&self.ty[inicio]) - (inicio as f64 + (m - 1.0) / 2.0) * soma_y;
+                if stt > 0.0 { syy - sty * sty / stt } else { syy }
+            }
+        };
+        ssr.max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teste_util::Gerador;
+
+    fn degraus(gerador: &mut Gerador) -> Vec<f64> {
+        let mut y = Vec::new();
+        for (nivel, tamanho) in [(10.0, 60), (14.0, 50), (8.0, 70)] {
+            y.extend((0..tamanho).map(|_| nivel + 0.5 * gerador.normal()));
+        }
+        y
+    }
+
+    #[test]
+    fn test_chow_detecta_mudanca_de_inclinacao() {
+        let mut gerador = Gerador::new(11);
+        let y: Vec<f64> = (0..80)
+            .map(|t| {
+                let t = t as f64;
+                let base = if t < 40.0 { 2.0 + 0.5 * t } else { 22.0 - 0.3 * (t - 40.0) };
+                base + 0.5 * gerador.normal()
+            })
+            .collect();
+
+        let teste = teste_chow(&y, 40, ModeloSegmento::Tendencia).unwrap();
+        assert_eq!(teste.graus_liberdade, (2, 76));
+        assert!(teste.valor_p < 1e-10);
+
+        let sem_quebra: Vec<f64> = (0..80).map(|t| 1.0 + 0.2 * t as f64 + gerador.normal()).collect();
+        assert!(teste_chow(&sem_quebra, 40, ModeloSegmento::Tendencia).unwrap().valor_p > 0.01);
+    }
+
+    #[test]
+    fn test_chow_igual_a_regressoes_separadas() {
+        let y = vec![1.0, 2.0, 2.5, 4.5, 5.0, 9.0, 10.5, 11.0, 13.5, 14.0];
+        let teste = teste_chow(&y, 5, ModeloSegmento::Tendencia).unwrap();
+
+        let ssr = |inicio: usize, fim: usize| {
+            let ajuste = ajustar_segmento(&y, inicio, fim, ModeloSegmento::Tendencia);
+            ajuste.mse * (fim - inicio) as f64
+        };
+        let esperado = ((ssr(0, 10) - ssr(0, 5) - ssr(5, 10)) / 2.0) / ((ssr(0, 5) + ssr(5, 10)) / 6.0);
+        assert!((teste.estatistica - esperado).abs() < 1e-9 * esperado);
+    }
+
+    #[test]
+    fn test_cusum_localiza_mudanca_de_media() {
+        let mut gerador = Gerador::new(2);
+        let y: Vec<f64> = (0..120).map(|t| if t < 70 { 0.0 } else { 1.5 } + gerador.normal()).collect();
+        let teste = teste_cusum(&y, ModeloSegmento::Media).unwrap();
+
+        assert!(teste.valor_p < 0.01);
+        assert!((teste.quebra as i64 - 70).abs() <= 5);
+        assert_eq!(teste.trajetoria.len(), 120);
+        assert!(teste.trajetoria[119].abs() < 1e-9);
+
+        let estavel = gerador.ruido(120, 1.0);
+        assert!(teste_cusum(&estavel, ModeloSegmento::Media).unwrap().valor_p > 0.05);
+    }
+
+    #[test]
+    fn test_pelt_e_segmentacao_binaria_na_media() {
+        let mut gerador = Gerador::new(7);
+        let y = degraus(&mut gerador);
+
+        for segmentacao in [
+            pelt(&y, ModeloSegmento::Media, Penalidade::Bic, 5).unwrap(),
+            segmentacao_binaria(&y, ModeloSegmento::Media, Penalidade::Bic, 5).unwrap(),
+        ] {
+            assert_eq!(segmentacao.quebras.len(), 2);
+            assert!((segmentacao.quebras[0] as i64 - 60).abs() <= 2);
+            assert!((segmentacao.quebras[1] as i64 - 110).abs() <= 2);
+            assert_eq!(segmentacao.limites().last(), Some(&(segmentacao.quebras[1], 180)));
+            assert!((segmentacao.segmentos[1].intercepto - 14.0).abs() < 0.3);
+            assert_eq!(segmentacao.segmentos[1].inclinacao, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_pelt_na_tendencia_e_previsao() {
+        // Tendência que muda de inclinação sem salto de nível, sem ruído
+        let y: Vec<f64> = (0..60)
+            .map(|t| if t < 25 { 1.0 * t as f64 } else { 25.0 + 3.0 * (t - 25) as f64 })
+            .collect();
+        let segmentacao = pelt(&y, ModeloSegmento::Tendencia, Penalidade::Bic, 3).unwrap();
+
+        // y[25] está sobre as duas retas: 25 e 26 são quebras de custo nulo
+        assert_eq!(segmentacao.quebras.len(), 1);
+        assert!([25, 26].contains(&segmentacao.quebras[0]), "{:?}", segmentacao.quebras);
+        let ultimo = segmentacao.ultimo_segmento();
+        assert!((ultimo.inclinacao - 3.0).abs() < 1e-9);
+        assert!((ultimo.r_quadrado - 1.0).abs() < 1e-12);
+        let previsao = ultimo.prever_proximos_periodos(60, 2);
+        assert!((previsao[0] - (25.0 + 3.0 * 35.0)).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_custo_de_tendencia_no_fim_de_serie_longa() {
+        // Com n = 1e6, Σt² - (Σt)²/m a partir de prefixos brutos cancelava
+        // todos os dígitos e o custo caía para o da média
+        let n = 1_000_000;
+        let y: Vec<f64> = (0..n).map(|t| 0.002 * t as f64 + ((t * 7) % 5) as f64).collect();
+        let somas = SomasPrefixadas::new(&y);
+
+        for m in [5, 50] {
+            let (inicio, fim) = (n - m, n);
+            let segmento = &y[inicio..fim];
+            let (inclinacao, intercepto) = crate::regressao_linear(segmento).unwrap();
+            let direto: f64 = segmento.iter().enumerate()
+                .map(|(i, v)| (v - (intercepto + inclinacao * i as f64)).powi(2))
+                .sum();
+            let custo = somas.custo(inicio, fim, ModeloSegmento::Tendencia);
+            assert!((custo - direto).abs() < 1e-6 * direto.max(1.0), "m = {}: {} contra {}", m, custo, direto);
+            assert!(custo < somas.custo(inicio, fim, ModeloSegmento::Media));
+        }
+    }
+
+    #[test]
+    fn test_sem_quebras_e_erros() {
+        let mut gerador = Gerador::new(13);
+        let ruido = gerador.ruido(200, 1.0);
+        let segmentacao = pelt(&ruido, ModeloSegmento::Media, Penalidade::Bic, 2).unwrap();
+        assert!(segmentacao.quebras.is_empty());
+        assert_eq!(segmentacao.segmentos.len(), 1);
+
+        assert!(matches!(teste_chow(&ruido, 0, ModeloSegmento::Media), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(teste_chow(&ruido[..3], 1, ModeloSegmento::Tendencia), Err(RegressaoError::DadosInsuficientes)));
+        assert!(matches!(pelt(&ruido, ModeloSegmento::Tendencia, Penalidade::Bic, 1), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(segmentar(&ruido, &[50, 40], ModeloSegmento::Media), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(segmentacao_binaria(&[], ModeloSegmento::Media, Penalidade::Aic, 1), Err(RegressaoError::DadosVazios)));
+    }
+}
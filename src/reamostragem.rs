@@ -0,0 +1,300 @@
+//! # Reamostragem de séries temporais
+//!
+//! Agrega eventos irregulares em períodos fixos (hora, dia, mês...) e
+//! preenche períodos sem observações por repetição do último valor,
+//! interpolação linear ou spline cúbica natural. O resultado é uma série
+//! regular cujos valores podem ser passados diretamente a
+//! [`crate::analise_completa`].
+
+use crate::serie_temporal::{Frequencia, SerieTemporal};
+use crate::{RegressaoError, Resultado};
+
+/// Função de agregação dos valores de cada período
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Agregacao {
+    Soma,
+    Media,
+    Mediana,
+    Minimo,
+    Maximo,
+    Contagem,
+    /// Último valor observado no período
+    Ultimo,
+}
+
+/// Tratamento dos períodos sem observações
+///
+/// Não se aplica a `Soma` e `Contagem`, para as quais um período vazio vale zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preenchimento {
+    /// Omite os períodos vazios (a série resultante pode ter lacunas)
+    Nenhum,
+    /// Repete o valor do último período com dados
+    RepetirAnterior,
+    /// Interpolação linear entre os períodos com dados
+    Linear,
+    /// Spline cúbica natural pelos períodos com dados
+    Spline,
+}
+
+/// Reamostra `serie` para `frequencia`, agregando cada período com `agregacao`
+///
+/// Os instantes do resultado são os inícios dos períodos (ver
+/// [`Frequencia::truncar`]), do período da primeira à última observação.
+/// Serve tanto para reduzir a frequência (agregação) quanto para aumentá-la
+/// (preenchimento dos períodos intermediários).
+///
+/// # Argumentos
+/// * `serie` - Série original, possivelmente irregular
+/// * `frequencia` - Frequência de destino
+/// * `agregacao` - Como combinar as observações de um mesmo período
+/// * `preenchimento` - Como tratar períodos sem observações
+pub fn reamostrar(
+    serie: &SerieTemporal,
+    frequencia: Frequencia,
+    agregacao: Agregacao,
+    preenchimento: Preenchimento,
+) -> Resultado<SerieTemporal> {
+    if serie.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if matches!(frequencia, Frequencia::Segundos(s) if s <= 0) {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+
+    // Agrupa as observações (já ordenadas) por período
+    let mut grupos: Vec<(i64, Vec<f64>)> = Vec::new();
+    for (&t, &v) in serie.instantes().iter().zip(serie.valores()) {
+        let periodo = frequencia.truncar(t);
+        match grupos.last_mut() {
+            Some((inicio, valores)) if *inicio == periodo => valores.push(v),
+            _ => grupos.push((periodo, vec![v])),
+        }
+    }
+
+    let primeiro = grupos[0].0;
+    let ultimo = grupos[grupos.len() - 1].0;
+    let mut grade = Vec::new();
+    let mut k = 0;
+    loop {
+        let inicio = frequencia.avancar(primeiro, k);
+        if inicio > ultimo {
+            break;
+        }
+        grade.push(inicio);
+        k += 1;
+    }
+
+    let vazio_vale_zero = matches!(agregacao, Agregacao::Soma | Agregacao::Contagem);
+    let mut conhecidos: Vec<Option<f64>> = Vec::with_capacity(grade.len());
+    let mut grupos = grupos.into_iter().peekable();
+    for &inicio in &grade {
+        match grupos.next_if(|(periodo, _)| *periodo == inicio) {
+            Some((_, mut valores)) => conhecidos.push(Some(agregar(&mut valores, agregacao))),
+            None if vazio_vale_zero => conhecidos.push(Some(0.0)),
+            None => conhecidos.push(None),
+        }
+    }
+
+    let (instantes, valores) = preencher(&grade, &conhecidos, preenchimento);
+    SerieTemporal::new(instantes, valores)
+}
+
+fn agregar(valores: &mut [f64], agregacao: Agregacao) -> f64 {
+    let n = valores.len();
+    match agregacao {
+        Agregacao::Soma => valores.iter().sum(),
+        Agregacao::Media => valores.iter().sum::<f64>() / n as f64,
+        Agregacao::Mediana => {
+            valores.sort_by(|a, b| a.total_cmp(b));
+            if n.is_multiple_of(2) {
+                (valores[n / 2 - 1] + valores[n / 2]) / 2.0
+            } else {
+                valores[n / 2]
+            }
+        }
+        Agregacao::Minimo => valores.iter().copied().fold(f64::INFINITY, f64::min),
+        Agregacao::Maximo => valores.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        Agregacao::Contagem => n as f64,
+        Agregacao::Ultimo => valores[n - 1],
+    }
+}
+
+fn preencher(grade: &[i64], conhecidos: &[Option<f64>], preenchimento: Preenchimento) -> (Vec<i64>, Vec<f64>) {
+    let origem = grade[0];
+    let pontos: Vec<(f64, f64)> = grade.iter().zip(conhecidos)
+        .filter_map(|(&t, v)| v.map(|v| ((t - origem) as f64, v)))
+        .collect();
+
+    match preenchimento {
+        Preenchimento::Nenhum => grade.iter().zip(conhecidos)
+            .filter_map(|(&t, v)| v.map(|v| (t, v)))
+            .unzip(),
+        Preenchimento::RepetirAnterior => {
+            let mut anterior = f64::NAN;
+            let valores = conhecidos.iter()
+                .map(|v| {
+                    if let Some(v) = v {
+                        anterior = *v;
+                    }
+                    anterior
+                })
+                .collect();
+            (grade.to_vec(), valores)
+        }
+        Preenchimento::Linear => {
+            let valores = grade.iter().zip(conhecidos)
+                .map(|(&t, v)| v.unwrap_or_else(|| interpolar_linear(&pontos, (t - origem) as f64)))
+                .collect();
+            (grade.to_vec(), valores)
+        }
+        Preenchimento::Spline => {
+            let segundas = segundas_derivadas_spline(&pontos);
+            let valores = grade.iter().zip(conhecidos)
+                .map(|(&t, v)| v.unwrap_or_else(|| avaliar_spline(&pontos, &segundas, (t - origem) as f64)))
+                .collect();
+            (grade.to_vec(), valores)
+        }
+    }
+}
+
+/// Índice `i` tal que `pontos[i].0 <= x < pontos[i + 1].0`
+fn intervalo(pontos: &[(f64, f64)], x: f64) -> usize {
+    let i = pontos.partition_point(|p| p.0 <= x);
+    i.saturating_sub(1).min(pontos.len().saturating_sub(2))
+}
+
+fn interpolar_linear(pontos: &[(f64, f64)], x: f64) -> f64 {
+    if pontos.len() == 1 {
+        return pontos[0].1;
+    }
+    let i = intervalo(pontos, x);
+    let (x0, y0) = pontos[i];
+    let (x1, y1) = pontos[i + 1];
+    y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+}
+
+/// Segundas derivadas da spline cúbica natural (algoritmo de Thomas)
+fn segundas_derivadas_spline(pontos: &[(f64, f64)]) -> Vec<f64> {
+    let n = pontos.len();
+    let mut m = vec![0.0; n];
+    if n < 3 {
+        return m;
+    }
+
+    let h: Vec<f64> = pontos.windows(2).map(|p| p[1].0 - p[0].0).collect();
+    let mut diagonal = vec![0.0; n];
+    let mut lado = vec![0.0; n];
+    for i in 1..n - 1 {
+        diagonal[i] = 2.0 * (h[i - 1] + h[i]);
+        lado[i] = 6.0 * ((pontos[i + 1].1 - pontos[i].1) / h[i] - (pontos[i].1 - pontos[i - 1].1) / h[i - 1]);
+    }
+    // Eliminação progressiva
+    for i in 2..n - 1 {
+        let fator = h[i - 1] / diagonal[i - 1];
+        diagonal[i] -= fator * h[i - 1];
+        lado[i] -= fator * lado[i - 1];
+    }
+    // Substituição regressiva
+    for i in (1..n - 1).rev() {
+        m[i] = (lado[i] - h[i] * m[i + 1]) / diagonal[i];
+    }
+    m
+}
+
+fn avaliar_spline(pontos: &[(f64, f64)], m: &[f64], x: f64) -> f64 {
+    if pontos.len() < 3 {
+        return interpolar_linear(pontos, x);
+    }
+    let i = intervalo(pontos, x);
+    let (x0, y0) = pontos[i];
+    let (x1, y1) = pontos[i + 1];
+    let h = x1 - x0;
+    let a = (x1 - x) / h;
+    let b = (x - x0) / h;
+    a * y0 + b * y1 + ((a * a * a - a) * m[i] + (b * b * b - b) * m[i + 1]) * h * h / 6.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analise_completa;
+    use crate::serie_temporal::analisar_iso8601;
+
+    fn eventos() -> SerieTemporal {
+        let datas = [
+            "2024-01-01T00:05:00Z", "2024-01-01T00:40:00Z", "2024-01-01T00:59:59Z",
+            "2024-01-01T01:10:00Z",
+            "2024-01-01T03:30:00Z", "2024-01-01T03:31:00Z",
+        ];
+        SerieTemporal::de_iso8601(&datas, &[1.0, 5.0, 3.0, 2.0, 10.0, 4.0]).unwrap()
+    }
+
+    #[test]
+    fn test_agregacoes_por_hora() {
+        let serie = eventos();
+        let por = |a: Agregacao| reamostrar(&serie, Frequencia::Hora, a, Preenchimento::Nenhum).unwrap();
+
+        assert_eq!(por(Agregacao::Soma).valores(), &[9.0, 2.0, 0.0, 14.0]);
+        assert_eq!(por(Agregacao::Contagem).valores(), &[3.0, 1.0, 0.0, 2.0]);
+        assert_eq!(por(Agregacao::Media).valores(), &[3.0, 2.0, 7.0]);
+        assert_eq!(por(Agregacao::Mediana).valores(), &[3.0, 2.0, 7.0]);
+        assert_eq!(por(Agregacao::Minimo).valores(), &[1.0, 2.0, 4.0]);
+        assert_eq!(por(Agregacao::Maximo).valores(), &[5.0, 2.0, 10.0]);
+        assert_eq!(por(Agregacao::Ultimo).valores(), &[3.0, 2.0, 4.0]);
+        assert_eq!(por(Agregacao::Media).datas_iso8601()[2], "2024-01-01T03:00:00Z");
+    }
+
+    #[test]
+    fn test_preenchimento_de_lacunas() {
+        let serie = eventos();
+        let com = |p: Preenchimento| reamostrar(&serie, Frequencia::Hora, Agregacao::Media, p).unwrap();
+
+        assert_eq!(com(Preenchimento::RepetirAnterior).valores(), &[3.0, 2.0, 2.0, 7.0]);
+        assert_eq!(com(Preenchimento::Linear).valores(), &[3.0, 2.0, 4.5, 7.0]);
+        let spline = com(Preenchimento::Spline);
+        assert_eq!(spline.len(), 4);
+        assert!(spline.valores()[2].is_finite());
+    }
+
+    #[test]
+    fn test_aumentar_frequencia_spline_exata_em_reta() {
+        let inicio = analisar_iso8601("2024-01-01").unwrap();
+        let instantes: Vec<i64> = [0, 2, 3, 6].iter().map(|d| inicio + d * 86_400).collect();
+        let valores: Vec<f64> = [0.0, 2.0, 3.0, 6.0].iter().map(|d| 10.0 + 3.0 * d).collect();
+        let serie = SerieTemporal::new(instantes, valores).unwrap();
+
+        for preenchimento in [Preenchimento::Linear, Preenchimento::Spline] {
+            let diaria = reamostrar(&serie, Frequencia::Dia, Agregacao::Ultimo, preenchimento).unwrap();
+            assert_eq!(diaria.len(), 7);
+            for (d, v) in diaria.valores().iter().enumerate() {
+                assert!((v - (10.0 + 3.0 * d as f64)).abs() < 1e-9);
+            }
+            let resultado = analise_completa(diaria.valores()).unwrap();
+            assert!((resultado.inclinacao - 3.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_reamostragem_mensal() {
+        let datas = ["2024-01-15", "2024-01-20", "2024-03-02", "2024-03-31T23:59:59Z"];
+        let serie = SerieTemporal::de_iso8601(&datas, &[1.0, 3.0, 5.0, 7.0]).unwrap();
+        let mensal = reamostrar(&serie, Frequencia::Mes, Agregacao::Media, Preenchimento::RepetirAnterior).unwrap();
+
+        assert_eq!(mensal.datas_iso8601(), vec!["2024-01-01T00:00:00Z", "2024-02-01T00:00:00Z", "2024-03-01T00:00:00Z"]);
+        assert_eq!(mensal.valores(), &[2.0, 2.0, 6.0]);
+    }
+
+    #[test]
+    fn test_reamostragem_erros() {
+        let vazia = SerieTemporal::new(vec![], vec![]).unwrap();
+        assert!(matches!(
+            reamostrar(&vazia, Frequencia::Dia, Agregacao::Soma, Preenchimento::Nenhum),
+            Err(RegressaoError::DadosVazios)
+        ));
+        assert!(matches!(
+            reamostrar(&eventos(), Frequencia::Segundos(0), Agregacao::Soma, Preenchimento::Nenhum),
+            Err(RegressaoError::ParametroInvalido)
+        ));
+    }
+}
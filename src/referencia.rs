@@ -0,0 +1,233 @@
+//! # Previsores de referência
+//!
+//! Métodos triviais contra os quais qualquer modelo deve ser comparado:
+//! ingênuo (repete o último valor), ingênuo sazonal (repete o último ciclo),
+//! deriva (reta entre a primeira e a última observação) e média histórica.
+//! Têm a mesma forma de saída de
+//! [`crate::ResultadoRegressao::prever_proximos_periodos`] e intervalos de
+//! previsão analíticos, supondo resíduos normais não correlacionados.
+
+use crate::distribuicoes::normal_quantil;
+use crate::soma::{self, soma_compensada};
+use crate::{RegressaoError, Resultado};
+
+/// Método de previsão de referência
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetodoReferencia {
+    /// `ŷ_{T+h} = y_T`
+    Ingenuo,
+    /// `ŷ_{T+h} = y_{T+h-m(k+1)}`, com `k = ⌊(h-1)/m⌋` e período `m`
+    IngenuoSazonal(usize),
+    /// `ŷ_{T+h} = y_T + h (y_T - y_1) / (T - 1)`
+    Deriva,
+    /// `ŷ_{T+h} = ȳ`
+    Media,
+}
+
+/// Previsões de um método de referência com intervalos de previsão
+#[derive(Debug, Clone)]
+pub struct PrevisaoReferencia {
+    pub previsoes: Vec<f64>,
+    pub erro_padrao: Vec<f64>,
+    pub limite_inferior: Vec<f64>,
+    pub limite_superior: Vec<f64>,
+    /// Nível de confiança dos intervalos (ex.: 0.95)
+    pub nivel: f64,
+}
+
+/// Método de referência ajustado a uma série
+#[derive(Debug, Clone)]
+pub struct ModeloReferencia {
+    pub metodo: MetodoReferencia,
+    /// Desvio padrão dos resíduos de um passo dentro da amostra
+    pub sigma: f64,
+    /// Previsões de um passo dentro da amostra (`NaN` onde o método não se
+    /// aplica, como na primeira observação do ingênuo)
+    pub valores_ajustados: Vec<f64>,
+    serie: Vec<f64>,
+}
+
+/// Ajusta um previsor de referência a `y`
+///
+/// # Argumentos
+/// * `y` - Valores da série
+/// * `metodo` - Método de referência
+pub fn ajustar_referencia(y: &[f64], metodo: MetodoReferencia) -> Resultado<ModeloReferencia> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    let n = y.len();
+    let minimo = match metodo {
+        MetodoReferencia::Ingenuo | MetodoReferencia::Media => 2,
+        MetodoReferencia::IngenuoSazonal(0) => return Err(RegressaoError::ParametroInvalido),
+        MetodoReferencia::IngenuoSazonal(m) => m + 1,
+        MetodoReferencia::Deriva => 3,
+    };
+    if n < minimo {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let media = soma::media(y);
+    let deriva = (y[n - 1] - y[0]) / (n - 1) as f64;
+    let valores_ajustados: Vec<f64> = (0..n)
+        .map(|t| match metodo {
+            MetodoReferencia::Ingenuo if t >= 1 => y[t - 1],
+            MetodoReferencia::IngenuoSazonal(m) if t >= m => y[t - m],
+            MetodoReferencia::Deriva if t >= 1 => y[t - 1] + deriva,
+            MetodoReferencia::Media => media,
+            _ => f64::NAN,
+        })
+        .collect();
+
+    // Parâmetros estimados descontados dos graus de liberdade
+    let parametros = match metodo {
+        MetodoReferencia::Deriva | MetodoReferencia::Media => 1,
+        _ => 0,
+    };
+    let residuos: Vec<f64> = y.iter()
+        .zip(&valores_ajustados)
+        .filter(|(_, a)| !a.is_nan())
+        .map(|(v, a)| v - a)
+        .collect();
+    let soma_quadrados = soma_compensada(residuos.iter().map(|e| e * e));
+    let sigma = (soma_quadrados / (residuos.len() - parametros) as f64).sqrt();
+
+    Ok(ModeloReferencia { metodo, sigma, valores_ajustados, serie: y.to_vec() })
+}
+
+impl ModeloReferencia {
+    /// Previsão pontual `h >= 1` passos à frente do fim da série
+    fn prever_passo(&self, h: usize) -> f64 {
+        let y = &self.serie;
+        let n = y.len();
+        match self.metodo {
+            MetodoReferencia::Ingenuo => y[n - 1],
+            MetodoReferencia::IngenuoSazonal(m) => y[n - m + (h - 1) % m],
+            MetodoReferencia::Deriva => y[n - 1] + h as f64 * (y[n - 1] - y[0]) / (n - 1) as f64,
+            MetodoReferencia::Media => self.valores_ajustados[0],
+        }
+    }
+
+    /// Erro padrão da previsão `h` passos à frente
+    fn erro_padrao(&self, h: usize) -> f64 {
+        let n = self.serie.len() as f64;
+        let fator = match self.metodo {
+            MetodoReferencia::Ingenuo => h as f64,
+            MetodoReferencia::IngenuoSazonal(m) => ((h - 1) / m + 1) as f64,
+            MetodoReferencia::Deriva => h as f64 * (1.0 + h as f64 / (n - 1.0)),
+            MetodoReferencia::Media => 1.0 + 1.0 / n,
+        };
+        self.sigma * fator.sqrt()
+    }
+
+    /// Faz previsões para os períodos `inicio..inicio + n_periodos` (série temporal)
+    ///
+    /// Índices a partir de `y.len()` recebem previsões `h = t - y.len() + 1`
+    /// passos à frente; índices anteriores recebem o valor ajustado de um passo.
+    pub fn prever_proximos_periodos(&self, inicio: usize, n_periodos: usize) -> Vec<f64> {
+        let n = self.serie.len();
+        (inicio..inicio + n_periodos)
+            .map(|t| if t < n { self.valores_ajustados[t] } else { self.prever_passo(t - n + 1) })
+            .collect()
+    }
+
+    /// Previsões para `horizonte` passos com intervalos de previsão
+    ///
+    /// # Argumentos
+    /// * `horizonte` - Número de passos à frente
+    /// * `nivel` - Nível de confiança dos intervalos, em (0, 1)
+    pub fn prever(&self, horizonte: usize, nivel: f64) -> Resultado<PrevisaoReferencia> {
+        if !(nivel > 0.0 && nivel < 1.0) {
+            return Err(RegressaoError::ParametroInvalido);
+        }
+        let z = normal_quantil(0.5 + nivel / 2.0);
+
+        let previsoes: Vec<f64> = (1..=horizonte).map(|h| self.prever_passo(h)).collect();
+        let erro_padrao: Vec<f64> = (1..=horizonte).map(|h| self.erro_padrao(h)).collect();
+        let limite_inferior = previsoes.iter().zip(&erro_padrao).map(|(p, e)| p - z * e).collect();
+        let limite_superior = previsoes.iter().zip(&erro_padrao).map(|(p, e)| p + z * e).collect();
+
+        Ok(PrevisaoReferencia { previsoes, erro_padrao, limite_inferior, limite_superior, nivel })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validacao::{previsor_tendencia_linear, validacao_cruzada, Origem};
+
+    const Y: [f64; 8] = [10.0, 12.0, 15.0, 11.0, 13.0, 16.0, 12.0, 14.0];
+
+    #[test]
+    fn test_previsoes_pontuais() {
+        let prever = |metodo| ajustar_referencia(&Y, metodo).unwrap().prever_proximos_periodos(8, 4);
+
+        assert_eq!(prever(MetodoReferencia::Ingenuo), vec![14.0; 4]);
+        assert_eq!(prever(MetodoReferencia::IngenuoSazonal(3)), vec![16.0, 12.0, 14.0, 16.0]);
+        for (h, previsao) in prever(MetodoReferencia::Deriva).iter().enumerate() {
+            assert!((previsao - (14.0 + (h + 1) as f64 * 4.0 / 7.0)).abs() < 1e-12);
+        }
+        assert_eq!(prever(MetodoReferencia::Media), vec![12.875; 4]);
+    }
+
+    #[test]
+    fn test_valores_ajustados_dentro_da_amostra() {
+        let modelo = ajustar_referencia(&Y, MetodoReferencia::IngenuoSazonal(3)).unwrap();
+        let ajustados = modelo.prever_proximos_periodos(2, 3);
+        assert!(ajustados[0].is_nan());
+        assert_eq!(&ajustados[1..], &[10.0, 12.0]);
+
+        // Os cinco resíduos do ingênuo sazonal valem 1
+        assert!((modelo.sigma - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_intervalos_analiticos() {
+        let ingenuo = ajustar_referencia(&Y, MetodoReferencia::Ingenuo).unwrap();
+        let previsao = ingenuo.prever(4, 0.95).unwrap();
+        for h in 1..=4 {
+            assert!((previsao.erro_padrao[h - 1] - ingenuo.sigma * (h as f64).sqrt()).abs() < 1e-12);
+        }
+        let meia_largura = previsao.limite_superior[0] - previsao.previsoes[0];
+        assert!((meia_largura - 1.959_963_984_540_054 * ingenuo.sigma).abs() < 1e-8);
+
+        let sazonal = ajustar_referencia(&Y, MetodoReferencia::IngenuoSazonal(3)).unwrap().prever(7, 0.8).unwrap();
+        assert_eq!(sazonal.erro_padrao[2], sazonal.erro_padrao[0]);
+        assert!((sazonal.erro_padrao[3] - 2f64.sqrt() * sazonal.erro_padrao[0]).abs() < 1e-12);
+        assert!((sazonal.erro_padrao[6] - 3f64.sqrt() * sazonal.erro_padrao[0]).abs() < 1e-12);
+
+        let media = ajustar_referencia(&Y, MetodoReferencia::Media).unwrap();
+        let desvio = (Y.iter().map(|v| (v - 12.875f64).powi(2)).sum::<f64>() / 7.0).sqrt();
+        assert!((media.sigma - desvio).abs() < 1e-12);
+        assert!((media.prever(1, 0.95).unwrap().erro_padrao[0] - desvio * (1.0 + 1.0 / 8.0f64).sqrt()).abs() < 1e-12);
+
+        let deriva = ajustar_referencia(&Y, MetodoReferencia::Deriva).unwrap();
+        let erro = deriva.prever(3, 0.95).unwrap().erro_padrao[2];
+        assert!((erro - deriva.sigma * (3.0 * (1.0 + 3.0 / 7.0f64)).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_comparacao_com_tendencia_linear() {
+        // Série sazonal sem tendência: o ingênuo sazonal é exato
+        let y: Vec<f64> = (0..48).map(|t| [5.0, 9.0, 7.0, 3.0][t % 4]).collect();
+        let sazonal = |treino: &[f64], h: usize| {
+            Ok(ajustar_referencia(treino, MetodoReferencia::IngenuoSazonal(4))?.prever_proximos_periodos(treino.len(), h))
+        };
+
+        let referencia = validacao_cruzada(&y, Origem::Expansiva { minimo: 12 }, 4, 1, sazonal).unwrap();
+        let tendencia = validacao_cruzada(&y, Origem::Expansiva { minimo: 12 }, 4, 1, previsor_tendencia_linear).unwrap();
+        assert_eq!(referencia.mse, 0.0);
+        assert!(tendencia.mse > 1.0);
+    }
+
+    #[test]
+    fn test_referencia_erros() {
+        assert!(matches!(ajustar_referencia(&[], MetodoReferencia::Media), Err(RegressaoError::DadosVazios)));
+        assert!(matches!(ajustar_referencia(&Y, MetodoReferencia::IngenuoSazonal(0)), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(ajustar_referencia(&Y[..3], MetodoReferencia::IngenuoSazonal(3)), Err(RegressaoError::DadosInsuficientes)));
+        assert!(matches!(ajustar_referencia(&Y[..2], MetodoReferencia::Deriva), Err(RegressaoError::DadosInsuficientes)));
+
+        let modelo = ajustar_referencia(&Y, MetodoReferencia::Ingenuo).unwrap();
+        assert!(matches!(modelo.prever(3, 1.5), Err(RegressaoError::ParametroInvalido)));
+    }
+}
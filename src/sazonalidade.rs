@@ -0,0 +1,235 @@
+//! # Tendência linear com sazonalidade
+//!
+//! Geradores de regressores sazonais — variáveis indicadoras (dummies) por
+//! estação e termos de Fourier com período arbitrário, inclusive não inteiro
+//! como 365.25 — que se combinam com o índice de tempo em uma matriz de
+//! planejamento. [`ajustar_tendencia_sazonal`] ajusta em uma chamada
+//! `y_t = b + a·t + s(t)`, preservando a interpretação da inclinação de
+//! [`crate::regressao_linear`], e prevê períodos futuros.
+
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::mmq::ajustar_mmq;
+use crate::{calcular_mae, calcular_mse, calcular_r2, RegressaoError, Resultado};
+
+/// Componente sazonal da regressão
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sazonalidade {
+    /// Uma indicadora por estação, exceto a primeira (referência)
+    Dummies(usize),
+    /// Pares seno/cosseno para os harmônicos `1..=harmonicos` do período
+    Fourier { periodo: f64, harmonicos: usize },
+}
+
+impl Sazonalidade {
+    fn colunas(&self, inicio: usize, n: usize) -> Resultado<Vec<Vec<f64>>> {
+        match *self {
+            Sazonalidade::Dummies(periodo) => dummies_sazonais(inicio, n, periodo),
+            Sazonalidade::Fourier { periodo, harmonicos } => termos_fourier(inicio, n, periodo, harmonicos),
+        }
+    }
+}
+
+/// Indicadoras sazonais para os períodos `inicio..inicio + n`
+///
+/// Devolve `periodo - 1` colunas; a coluna `j` vale 1 quando
+/// `t % periodo == j + 1`. A estação 0 é a referência, absorvida pelo intercepto.
+pub fn dummies_sazonais(inicio: usize, n: usize, periodo: usize) -> Resultado<Vec<Vec<f64>>> {
+    if periodo < 2 {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    Ok((1..periodo)
+        .map(|estacao| (inicio..inicio + n).map(|t| if t % periodo == estacao { 1.0 } else { 0.0 }).collect())
+        .collect())
+}
+
+/// Termos de Fourier para os períodos `inicio..inicio + n`
+///
+/// Para cada harmônico `k` devolve as colunas `sen(2πkt/P)` e `cos(2πkt/P)`.
+/// Exige `2K ≤ P`; quando `2K = P` o seno do último harmônico é nulo em
+/// todos os inteiros e é omitido.
+///
+/// # Argumentos
+/// * `inicio` - Índice do primeiro período
+/// * `n` - Número de períodos
+/// * `periodo` - Comprimento do ciclo `P` (pode ser não inteiro)
+/// * `harmonicos` - Número de harmônicos `K`
+pub fn termos_fourier(inicio: usize, n: usize, periodo: f64, harmonicos: usize) -> Resultado<Vec<Vec<f64>>> {
+    if !periodo.is_finite() || periodo < 2.0 || harmonicos == 0 || 2.0 * harmonicos as f64 > periodo {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+
+    let mut colunas = Vec::with_capacity(2 * harmonicos);
+    for k in 1..=harmonicos {
+        let frequencia = 2.0 * PI * k as f64 / periodo;
+        if 2.0 * k as f64 != periodo {
+            colunas.push((inicio..inicio + n).map(|t| (frequencia * t as f64).sin()).collect());
+        }
+        colunas.push((inicio..inicio + n).map(|t| (frequencia * t as f64).cos()).collect());
+    }
+    Ok(colunas)
+}
+
+/// Matriz de planejamento `[1, t, regressores sazonais...]` em colunas
+pub fn matriz_planejamento(inicio: usize, n: usize, sazonalidades: &[Sazonalidade]) -> Resultado<Vec<Vec<f64>>> {
+    let mut colunas = vec![vec![1.0; n], (inicio..inicio + n).map(|t| t as f64).collect()];
+    for sazonalidade in sazonalidades {
+        colunas.extend(sazonalidade.colunas(inicio, n)?);
+    }
+    Ok(colunas)
+}
+
+/// Resultado do ajuste de tendência linear com sazonalidade
+#[derive(Debug, Clone)]
+pub struct ModeloTendenciaSazonal {
+    pub sazonalidades: Vec<Sazonalidade>,
+    pub inclinacao: f64,
+    pub intercepto: f64,
+    /// Coeficientes dos regressores sazonais, na ordem de [`matriz_planejamento`]
+    pub coeficientes_sazonais: Vec<f64>,
+    /// Erros padrão de `[intercepto, inclinacao, coeficientes_sazonais...]`
+    pub erros_padrao: Vec<f64>,
+    pub r_quadrado: f64,
+    pub mse: f64,
+    pub rmse: f64,
+    pub mae: f64,
+    pub valores_previstos: Vec<f64>,
+}
+
+impl ModeloTendenciaSazonal {
+    /// Componente sazonal `s(t)` nos períodos `inicio..inicio + n_periodos`
+    pub fn componente_sazonal(&self, inicio: usize, n_periodos: usize) -> Vec<f64> {
+        let colunas = matriz_planejamento(inicio, n_periodos, &self.sazonalidades)
+            .expect("sazonalidades validadas no ajuste");
+        (0..n_periodos)
+            .map(|t| colunas[2..].iter().zip(&self.coeficientes_sazonais).map(|(c, b)| c[t] * b).sum())
+            .collect()
+    }
+
+    /// Faz previsões para os próximos n períodos (série temporal)
+    pub fn prever_proximos_periodos(&self, inicio: usize, n_periodos: usize) -> Vec<f64> {
+        self.componente_sazonal(inicio, n_periodos)
+            .into_iter()
+            .zip(inicio..)
+            .map(|(s, t)| self.intercepto + self.inclinacao * t as f64 + s)
+            .collect()
+    }
+}
+
+impl fmt::Display for ModeloTendenciaSazonal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "=== Tendência Linear com Sazonalidade ===")?;
+        writeln!(f, "Inclinação (a): {:.6} (erro padrão {:.6})", self.inclinacao, self.erros_padrao[1])?;
+        writeln!(f, "Intercepto (b): {:.6} (erro padrão {:.6})", self.intercepto, self.erros_padrao[0])?;
+        writeln!(f, "Regressores sazonais: {}", self.coeficientes_sazonais.len())?;
+        writeln!(f, "R²: {:.6}", self.r_quadrado)?;
+        writeln!(f, "MSE: {:.6}", self.mse)?;
+        writeln!(f, "RMSE: {:.6}", self.rmse)?;
+        writeln!(f, "MAE: {:.6}", self.mae)?;
+        Ok(())
+    }
+}
+
+/// Ajusta tendência linear mais sazonalidade por mínimos quadrados
+///
+/// # Argumentos
+/// * `y` - Valores da série (índice de tempo implícito `0..n`)
+/// * `sazonalidades` - Componentes sazonais; várias podem ser combinadas
+///   (por exemplo, semanal e anual)
+pub fn ajustar_tendencia_sazonal(y: &[f64], sazonalidades: &[Sazonalidade]) -> Resultado<ModeloTendenciaSazonal> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    let colunas = matriz_planejamento(0, y.len(), sazonalidades)?;
+    let ajuste = ajustar_mmq(&colunas, y)?;
+
+    let valores_previstos: Vec<f64> = y.iter().zip(&ajuste.residuos).map(|(v, e)| v - e).collect();
+    let mse = calcular_mse(y, &valores_previstos)?;
+
+    Ok(ModeloTendenciaSazonal {
+        sazonalidades: sazonalidades.to_vec(),
+        inclinacao: ajuste.coeficientes[1],
+        intercepto: ajuste.coeficientes[0],
+        coeficientes_sazonais: ajuste.coeficientes[2..].to_vec(),
+        erros_padrao: ajuste.erros_padrao,
+        r_quadrado: calcular_r2(y, &valores_previstos)?,
+        mse,
+        rmse: mse.sqrt(),
+        mae: calcular_mae(y, &valores_previstos)?,
+        valores_previstos,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teste_util::Gerador;
+
+    #[test]
+    fn test_geradores_de_regressores() {
+        let dummies = dummies_sazonais(2, 5, 3).unwrap();
+        assert_eq!(dummies, vec![vec![0.0, 0.0, 1.0, 0.0, 0.0], vec![1.0, 0.0, 0.0, 1.0, 0.0]]);
+
+        // Período par com K = P/2: o último seno é omitido
+        assert_eq!(termos_fourier(0, 10, 4.0, 2).unwrap().len(), 3);
+        let anual = termos_fourier(0, 800, 365.25, 3).unwrap();
+        assert_eq!(anual.len(), 6);
+        assert!((anual[1][365] - (2.0 * PI * 365.0 / 365.25).cos()).abs() < 1e-15);
+
+        assert!(matches!(termos_fourier(0, 10, 4.0, 3), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(dummies_sazonais(0, 10, 1), Err(RegressaoError::ParametroInvalido)));
+        assert_eq!(matriz_planejamento(0, 4, &[Sazonalidade::Dummies(2)]).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_dummies_recuperam_padrao_e_tendencia() {
+        let padrao = [0.0, 4.0, -2.0, 1.0];
+        let y: Vec<f64> = (0..40).map(|t| 10.0 + 0.5 * t as f64 + padrao[t % 4]).collect();
+        let modelo = ajustar_tendencia_sazonal(&y, &[Sazonalidade::Dummies(4)]).unwrap();
+
+        assert!((modelo.inclinacao - 0.5).abs() < 1e-10);
+        assert!((modelo.intercepto - 10.0).abs() < 1e-10);
+        for (coef, esperado) in modelo.coeficientes_sazonais.iter().zip(&padrao[1..]) {
+            assert!((coef - esperado).abs() < 1e-10);
+        }
+        let previsao = modelo.prever_proximos_periodos(40, 5);
+        for (h, p) in previsao.iter().enumerate() {
+            let t = 40 + h;
+            assert!((p - (10.0 + 0.5 * t as f64 + padrao[t % 4])).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_fourier_periodo_nao_inteiro_com_ruido() {
+        let mut gerador = Gerador::new(19);
+        let y: Vec<f64> = (0..1000)
+            .map(|t| {
+                let fase = 2.0 * PI * t as f64 / 365.25;
+                100.0 + 0.02 * t as f64 + 8.0 * fase.sin() - 3.0 * (2.0 * fase).cos() + gerador.normal()
+            })
+            .collect();
+        let modelo = ajustar_tendencia_sazonal(&y, &[Sazonalidade::Fourier { periodo: 365.25, harmonicos: 2 }]).unwrap();
+
+        assert!((modelo.inclinacao - 0.02).abs() < 3.0 * modelo.erros_padrao[1]);
+        assert!((modelo.coeficientes_sazonais[0] - 8.0).abs() < 0.2);
+        assert!((modelo.coeficientes_sazonais[3] + 3.0).abs() < 0.2);
+        assert!((modelo.rmse - 1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_sazonalidades_combinadas() {
+        let y: Vec<f64> = (0..120)
+            .map(|t| {
+                let semanal = [0.0, 1.0, 2.0, 3.0, 2.0, 1.0, -4.0][t % 7];
+                5.0 - 0.1 * t as f64 + semanal + 2.0 * (2.0 * PI * t as f64 / 30.5).cos()
+            })
+            .collect();
+        let sazonalidades = [Sazonalidade::Dummies(7), Sazonalidade::Fourier { periodo: 30.5, harmonicos: 1 }];
+        let modelo = ajustar_tendencia_sazonal(&y, &sazonalidades).unwrap();
+
+        assert_eq!(modelo.coeficientes_sazonais.len(), 8);
+        assert!(modelo.r_quadrado > 1.0 - 1e-12);
+        assert!((modelo.componente_sazonal(0, 1)[0] - 2.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,334 @@
+//! # Regressão linear segmentada contínua
+//!
+//! Ajusta uma tendência linear por partes, contínua nos nós:
+//!
+//! `y = b + a₀x + Σ_j δ_j (x - κ_j)₊`
+//!
+//! Os nós `κ_j` podem ser fixados ou estimados por mínimos quadrados. A
+//! inclinação de cada segmento é `a₀ + δ_1 + … + δ_j`, e as previsões
+//! prolongam o último segmento, como [`crate::ResultadoRegressao::prever_proximos_periodos`]
+//! faz com a reta única.
+
+use std::fmt;
+
+use crate::distribuicoes::t_student_quantil;
+use crate::mmq::ajustar_mmq;
+use crate::otimizacao::nelder_mead;
+use crate::{calcular_mae, calcular_mse, calcular_r2, RegressaoError, Resultado};
+
+/// Especificação dos nós (pontos de mudança de inclinação)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Nos {
+    /// Posições conhecidas, estritamente crescentes e internas ao intervalo de `x`
+    Fixos(Vec<f64>),
+    /// Número de nós cujas posições são estimadas
+    Otimizados(usize),
+}
+
+/// Resultado da regressão segmentada
+#[derive(Debug, Clone)]
+pub struct RegressaoSegmentada {
+    /// Posições dos nós, em ordem crescente
+    pub nos: Vec<f64>,
+    /// Erros padrão assintóticos dos nós (zero para nós fixos; `NaN` se a
+    /// mudança de inclinação no nó for nula)
+    pub erros_padrao_nos: Vec<f64>,
+    /// Intercepto da reta do primeiro segmento
+    pub intercepto: f64,
+    /// Inclinação de cada um dos `nos.len() + 1` segmentos
+    pub inclinacoes: Vec<f64>,
+    /// Graus de liberdade dos resíduos (nós estimados contam como parâmetros)
+    pub graus_liberdade: usize,
+    pub r_quadrado: f64,
+    pub mse: f64,
+    pub rmse: f64,
+    pub mae: f64,
+    pub valores_previstos: Vec<f64>,
+}
+
+impl RegressaoSegmentada {
+    fn avaliar(&self, x: f64) -> f64 {
+        let mut valor = self.intercepto + self.inclinacoes[0] * x;
+        for (j, no) in self.nos.iter().enumerate() {
+            if x > *no {
+                valor += (self.inclinacoes[j + 1] - self.inclinacoes[j]) * (x - no);
+            }
+        }
+        valor
+    }
+
+    /// Faz previsões para novos valores de x
+    pub fn prever(&self, x_valores: &[f64]) -> Vec<f64> {
+        x_valores.iter().map(|&x| self.avaliar(x)).collect()
+    }
+
+    /// Faz previsões para os próximos n períodos (série temporal)
+    pub fn prever_proximos_periodos(&self, inicio: usize, n_periodos: usize) -> Vec<f64> {
+        (inicio..inicio + n_periodos).map(|x| self.avaliar(x as f64)).collect()
+    }
+
+    /// Intervalos de confiança dos nós, baseados na distribuição t
+    ///
+    /// # Argumentos
+    /// * `nivel` - Nível de confiança em (0, 1), por exemplo 0.95
+    pub fn intervalos_nos(&self, nivel: f64) -> Resultado<Vec<(f64, f64)>> {
+        if !(nivel > 0.0 && nivel < 1.0) {
+            return Err(RegressaoError::ParametroInvalido);
+        }
+        let t = t_student_quantil(0.5 + nivel / 2.0, self.graus_liberdade as f64);
+        Ok(self.nos.iter()
+            .zip(&self.erros_padrao_nos)
+            .map(|(no, ep)| (no - t * ep, no + t * ep))
+            .collect())
+    }
+}
+
+impl fmt::Display for RegressaoSegmentada {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "=== Regressão Linear Segmentada ===")?;
+        writeln!(f, "Intercepto (b): {:.6}", self.intercepto)?;
+        for (j, inclinacao) in self.inclinacoes.iter().enumerate() {
+            writeln!(f, "Inclinação do segmento {}: {:.6}", j + 1, inclinacao)?;
+        }
+        for (no, ep) in self.nos.iter().zip(&self.erros_padrao_nos) {
+            writeln!(f, "Nó: {:.6} (erro padrão {:.6})", no, ep)?;
+        }
+        writeln!(f, "R²: {:.6}", self.r_quadrado)?;
+        writeln!(f, "MSE: {:.6}", self.mse)?;
+        writeln!(f, "RMSE: {:.6}", self.rmse)?;
+        writeln!(f, "MAE: {:.6}", self.mae)?;
+        Ok(())
+    }
+}
+
+/// Regressão segmentada para uma série temporal (x implícito como índices)
+pub fn regressao_segmentada(y: &[f64], nos: Nos) -> Resultado<RegressaoSegmentada> {
+    let x: Vec<f64> = (0..y.len()).map(|i| i as f64).collect();
+    regressao_segmentada_xy(&x, y, nos)
+}
+
+/// Regressão segmentada para pontos (x, y) arbitrários
+///
+/// Com [`Nos::Otimizados`], as posições minimizam a soma dos quadrados dos
+/// resíduos: uma busca em grade coordenada a coordenada escolhe o ponto de
+/// partida, refinado em seguida por Nelder–Mead. Os erros padrão dos nós vêm
+/// da linearização do modelo em torno da solução (como no método de Muggeo).
+///
+/// # Argumentos
+/// * `x` - Valores x
+/// * `y` - Valores y
+/// * `nos` - Nós fixos ou número de nós a estimar
+pub fn regressao_segmentada_xy(x: &[f64], y: &[f64], nos: Nos) -> Resultado<RegressaoSegmentada> {
+    if x.is_empty() || y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if x.len() != y.len() {
+        return Err(RegressaoError::TamanhosDiferentes);
+    }
+
+    let (minimo, maximo) = x.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), &v| (a.min(v), b.max(v)));
+    let (posicoes, estimados) = match nos {
+        Nos::Fixos(posicoes) => {
+            if !nos_validos(&posicoes, minimo, maximo) {
+                return Err(RegressaoError::ParametroInvalido);
+            }
+            (posicoes, false)
+        }
+        Nos::Otimizados(k) => (otimizar_nos(x, y, k, minimo, maximo)?, true),
+    };
+
+    let k = posicoes.len();
+    let parametros = 2 + if estimados { 2 * k } else { k };
+    if x.len() <= parametros {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let ajuste = ajustar_mmq(&colunas(x, &posicoes), y)?;
+    let coeficientes = &ajuste.coeficientes;
+    let mut inclinacoes = Vec::with_capacity(k + 1);
+    inclinacoes.push(coeficientes[1]);
+    for delta in &coeficientes[2..] {
+        inclinacoes.push(inclinacoes[inclinacoes.len() - 1] + delta);
+    }
+
+    let erros_padrao_nos = if estimados {
+        erros_padrao_nos(x, &posicoes, &coeficientes[2..], &ajuste.residuos)
+    } else {
+        vec![0.0; k]
+    };
+
+    let valores_previstos: Vec<f64> = y.iter().zip(&ajuste.residuos).map(|(v, e)| v - e).collect();
+    let mse = calcular_mse(y, &valores_previstos)?;
+
+    Ok(RegressaoSegmentada {
+        nos: posicoes,
+        erros_padrao_nos,
+        intercepto: coeficientes[0],
+        inclinacoes,
+        graus_liberdade: x.len() - parametros,
+        r_quadrado: calcular_r2(y, &valores_previstos)?,
+        mse,
+        rmse: mse.sqrt(),
+        mae: calcular_mae(y, &valores_previstos)?,
+        valores_previstos,
+    })
+}
+
+fn nos_validos(nos: &[f64], minimo: f64, maximo: f64) -> bool {
+    nos.iter().all(|no| no.is_finite() && *no > minimo && *no < maximo)
+        && nos.windows(2).all(|par| par[0] < par[1])
+}
+
+/// Colunas da matriz de planejamento: `1`, `x` e `(x - κ_j)₊`
+fn colunas(x: &[f64], nos: &[f64]) -> Vec<Vec<f64>> {
+    let mut colunas = vec![vec![1.0; x.len()], x.to_vec()];
+    for no in nos {
+        colunas.push(x.iter().map(|v| (v - no).max(0.0)).collect());
+    }
+    colunas
+}
+
+fn soma_quadrados(x: &[f64], y: &[f64], nos: &[f64], minimo: f64, maximo: f64) -> f64 {
+    if !nos_validos(nos, minimo, maximo) {
+        return f64::INFINITY;
+    }
+    ajustar_mmq(&colunas(x, nos), y)
+        .map(|ajuste| ajuste.soma_quadrados_residuos)
+        .unwrap_or(f64::INFINITY)
+}
+
+fn otimizar_nos(x: &[f64], y: &[f64], k: usize, minimo: f64, maximo: f64) -> Resultado<Vec<f64>> {
+    if k == 0 {
+        return Ok(Vec::new());
+    }
+    if x.len() <= 2 + 2 * k {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+    let objetivo = |nos: &[f64]| soma_quadrados(x, y, nos, minimo, maximo);
+
+    // Ponto de partida: nós igualmente espaçados, melhorados por busca em grade
+    // em uma coordenada por vez
+    const PONTOS_GRADE: usize = 64;
+    let amplitude = maximo - minimo;
+    let grade: Vec<f64> = (1..PONTOS_GRADE)
+        .map(|i| minimo + amplitude * i as f64 / PONTOS_GRADE as f64)
+        .collect();
+    let mut nos: Vec<f64> = (1..=k).map(|j| minimo + amplitude * j as f64 / (k + 1) as f64).collect();
+    let mut melhor = objetivo(&nos);
+    for _ in 0..3 {
+        for j in 0..k {
+            for &candidato in &grade {
+                let original = nos[j];
+                nos[j] = candidato;
+                let valor = objetivo(&nos);
+                if valor < melhor {
+                    melhor = valor;
+                } else {
+                    nos[j] = original;
+                }
+            }
+        }
+    }
+
+    // Refinamento; reiniciar o simplex evita paradas prematuras em uma função
+    // que é apenas contínua por partes
+    let mut passo = amplitude / PONTOS_GRADE as f64;
+    for _ in 0..3 {
+        let (refinados, valor) = nelder_mead(objetivo, &nos, passo, 2000);
+        if valor <= melhor {
+            nos = refinados;
+            melhor = valor;
+        }
+        passo /= 4.0;
+    }
+
+    if !melhor.is_finite() {
+        return Err(RegressaoError::NaoConvergiu);
+    }
+    Ok(nos)
+}
+
+/// Erros padrão dos nós pela linearização `∂ŷ/∂κ_j = -δ_j·1(x > κ_j)`
+///
+/// Regredir os resíduos sobre o jacobiano completo devolve, na solução,
+/// coeficientes nulos e os erros padrão de Gauss–Newton.
+fn erros_padrao_nos(x: &[f64], nos: &[f64], deltas: &[f64], residuos: &[f64]) -> Vec<f64> {
+    let mut jacobiano = colunas(x, nos);
+    for (no, delta) in nos.iter().zip(deltas) {
+        jacobiano.push(x.iter().map(|v| if v > no { -delta } else { 0.0 }).collect());
+    }
+    match ajustar_mmq(&jacobiano, residuos) {
+        Ok(ajuste) => ajuste.erros_padrao[2 + nos.len()..].to_vec(),
+        Err(_) => vec![f64::NAN; nos.len()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teste_util::Gerador;
+
+    fn quebrada(t: f64) -> f64 {
+        if t < 30.0 { 5.0 + 2.0 * t } else if t < 70.0 { 65.0 - 0.5 * (t - 30.0) } else { 45.0 + 1.0 * (t - 70.0) }
+    }
+
+    #[test]
+    fn test_nos_fixos_exatos() {
+        let y: Vec<f64> = (0..100).map(|t| quebrada(t as f64)).collect();
+        let ajuste = regressao_segmentada(&y, Nos::Fixos(vec![30.0, 70.0])).unwrap();
+
+        assert!((ajuste.intercepto - 5.0).abs() < 1e-9);
+        for (a, b) in ajuste.inclinacoes.iter().zip([2.0, -0.5, 1.0]) {
+            assert!((a - b).abs() < 1e-9);
+        }
+        assert_eq!(ajuste.erros_padrao_nos, vec![0.0, 0.0]);
+        assert_eq!(ajuste.graus_liberdade, 96);
+        // Continuidade: as previsões prolongam o último segmento
+        let previsao = ajuste.prever_proximos_periodos(100, 3);
+        assert!((previsao[2] - quebrada(102.0)).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_nos_otimizados_com_intervalos() {
+        let mut gerador = Gerador::new(21);
+        let y: Vec<f64> = (0..100).map(|t| quebrada(t as f64) + gerador.normal()).collect();
+        let ajuste = regressao_segmentada(&y, Nos::Otimizados(2)).unwrap();
+
+        assert!((ajuste.nos[0] - 30.0).abs() < 1.5);
+        assert!((ajuste.nos[1] - 70.0).abs() < 1.5);
+        assert!((ajuste.inclinacoes[1] + 0.5).abs() < 0.1);
+        assert_eq!(ajuste.graus_liberdade, 94);
+
+        let intervalos = ajuste.intervalos_nos(0.95).unwrap();
+        for ((baixo, alto), verdadeiro) in intervalos.iter().zip([30.0, 70.0]) {
+            assert!(baixo < alto);
+            assert!(*baixo < verdadeiro + 1.0 && *alto > verdadeiro - 1.0);
+            assert!(alto - baixo < 5.0);
+        }
+    }
+
+    #[test]
+    fn test_um_no_xy_irregular() {
+        let mut gerador = Gerador::new(4);
+        let x: Vec<f64> = (0..80).map(|_| 20.0 * gerador.uniforme()).collect();
+        let y: Vec<f64> = x.iter().map(|&v| if v < 12.0 { 1.0 + 0.5 * v } else { 7.0 + 3.0 * (v - 12.0) }).collect();
+        let ajuste = regressao_segmentada_xy(&x, &y, Nos::Otimizados(1)).unwrap();
+
+        assert!((ajuste.nos[0] - 12.0).abs() < 1e-4);
+        assert!((ajuste.inclinacoes[1] - 3.0).abs() < 1e-4);
+        assert!(ajuste.r_quadrado > 1.0 - 1e-9);
+        assert!((ajuste.prever(&[25.0])[0] - 46.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_segmentada_erros() {
+        let y: Vec<f64> = (0..10).map(|t| t as f64).collect();
+        assert!(matches!(regressao_segmentada(&y, Nos::Fixos(vec![5.0, 3.0])), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(regressao_segmentada(&y, Nos::Fixos(vec![9.0])), Err(RegressaoError::ParametroInvalido)));
+        assert!(matches!(regressao_segmentada(&y[..5], Nos::Otimizados(2)), Err(RegressaoError::DadosInsuficientes)));
+        assert!(matches!(regressao_segmentada(&[], Nos::Otimizados(1)), Err(RegressaoError::DadosVazios)));
+
+        let ajuste = regressao_segmentada(&y, Nos::Fixos(vec![4.5])).unwrap();
+        assert!(matches!(ajuste.intervalos_nos(1.0), Err(RegressaoError::ParametroInvalido)));
+    }
+}
@@ -0,0 +1,408 @@
+//! # Séries temporais com datas
+//!
+//! [`SerieTemporal`] associa instantes reais (segundos desde a época Unix,
+//! UTC) a valores. O ajuste de tendência usa o tempo decorrido de fato, de
+//! modo que lacunas e intervalos irregulares não distorcem a inclinação, e as
+//! previsões são datadas segundo uma [`Frequencia`] escolhida.
+
+use std::fmt;
+
+use crate::{analise_completa_xy, RegressaoError, ResultadoRegressao, Resultado};
+
+const SEGUNDOS_POR_DIA: i64 = 86_400;
+
+/// Passo entre observações consecutivas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequencia {
+    /// Intervalo fixo em segundos
+    Segundos(i64),
+    Minuto,
+    Hora,
+    Dia,
+    Semana,
+    /// Mês de calendário (dias além do fim do mês são ajustados ao último dia)
+    Mes,
+    Ano,
+}
+
+impl Frequencia {
+    /// Duração fixa em segundos, ou `None` para meses e anos
+    pub fn duracao_segundos(&self) -> Option<i64> {
+        match *self {
+            Frequencia::Segundos(s) => Some(s),
+            Frequencia::Minuto => Some(60),
+            Frequencia::Hora => Some(3_600),
+            Frequencia::Dia => Some(SEGUNDOS_POR_DIA),
+            Frequencia::Semana => Some(7 * SEGUNDOS_POR_DIA),
+            Frequencia::Mes | Frequencia::Ano => None,
+        }
+    }
+
+    /// Duração média em segundos (mês de 30,436875 dias, ano de 365,2425 dias)
+    pub fn duracao_media_segundos(&self) -> f64 {
+        match self {
+            Frequencia::Mes => 30.436_875 * SEGUNDOS_POR_DIA as f64,
+            Frequencia::Ano => 365.2425 * SEGUNDOS_POR_DIA as f64,
+            _ => self.duracao_segundos().unwrap_or(0) as f64,
+        }
+    }
+
+    /// Início do período que contém `instante`
+    ///
+    /// Intervalos fixos são alinhados à época Unix, semanas começam na
+    /// segunda-feira e meses e anos no primeiro dia, à meia-noite UTC.
+    pub fn truncar(&self, instante: i64) -> i64 {
+        match self {
+            Frequencia::Mes | Frequencia::Ano => {
+                let (ano, mes, _) = civil_de_dias(instante.div_euclid(SEGUNDOS_POR_DIA));
+                let mes = if *self == Frequencia::Ano { 1 } else { mes };
+                dias_de_civil(ano, mes, 1) * SEGUNDOS_POR_DIA
+            }
+            Frequencia::Semana => {
+                // 1970-01-05 foi uma segunda-feira
+                let segunda = 4 * SEGUNDOS_POR_DIA;
+                let semana = 7 * SEGUNDOS_POR_DIA;
+                (instante - segunda).div_euclid(semana) * semana + segunda
+            }
+            _ => {
+                let duracao = self.duracao_segundos().unwrap_or(1).max(1);
+                instante.div_euclid(duracao) * duracao
+            }
+        }
+    }
+
+    /// Instante `passos` períodos após `instante`
+    pub fn avancar(&self, instante: i64, passos: i64) -> i64 {
+        let meses = match self {
+            Frequencia::Mes => passos,
+            Frequencia::Ano => 12 * passos,
+            _ => return instante + passos * self.duracao_segundos().unwrap_or(0),
+        };
+
+        let dias = instante.div_euclid(SEGUNDOS_POR_DIA);
+        let segundos_do_dia = instante.rem_euclid(SEGUNDOS_POR_DIA);
+        let (ano, mes, dia) = civil_de_dias(dias);
+        let indice = ano * 12 + (mes as i64 - 1) + meses;
+        let (novo_ano, novo_mes) = (indice.div_euclid(12), (indice.rem_euclid(12) + 1) as u32);
+        let novo_dia = dia.min(dias_no_mes(novo_ano, novo_mes));
+        dias_de_civil(novo_ano, novo_mes, novo_dia) * SEGUNDOS_POR_DIA + segundos_do_dia
+    }
+}
+
+/// Série de valores indexados por instantes, ordenada cronologicamente
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerieTemporal {
+    instantes: Vec<i64>,
+    valores: Vec<f64>,
+}
+
+impl SerieTemporal {
+    /// Cria a série a partir de instantes em segundos desde a época Unix
+    ///
+    /// Os pares são ordenados por instante; instantes repetidos são mantidos
+    /// na ordem original.
+    pub fn new(instantes: Vec<i64>, valores: Vec<f64>) -> Resultado<Self> {
+        if instantes.len() != valores.len() {
+            return Err(RegressaoError::TamanhosDiferentes);
+        }
+        let mut pares: Vec<(i64, f64)> = instantes.into_iter().zip(valores).collect();
+        pares.sort_by_key(|&(t, _)| t);
+        let (instantes, valores) = pares.into_iter().unzip();
+        Ok(SerieTemporal { instantes, valores })
+    }
+
+    /// Cria a série a partir de datas ISO-8601 (ver [`analisar_iso8601`])
+    pub fn de_iso8601(datas: &[&str], valores: &[f64]) -> Resultado<Self> {
+        let instantes = datas.iter()
+            .map(|d| analisar_iso8601(d))
+            .collect::<Resultado<Vec<i64>>>()?;
+        Self::new(instantes, valores.to_vec())
+    }
+
+    /// Série regular com início em `inicio` e passo `frequencia`
+    pub fn regular(inicio: i64, frequencia: Frequencia, valores: Vec<f64>) -> Self {
+        let instantes = (0..valores.len() as i64).map(|k| frequencia.avancar(inicio, k)).collect();
+        SerieTemporal { instantes, valores }
+    }
+
+    pub fn instantes(&self) -> &[i64] {
+        &self.instantes
+    }
+
+    pub fn valores(&self) -> &[f64] {
+        &self.valores
+    }
+
+    pub fn len(&self) -> usize {
+        self.valores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.valores.is_empty()
+    }
+
+    /// Instantes formatados como `AAAA-MM-DDTHH:MM:SSZ`
+    pub fn datas_iso8601(&self) -> Vec<String> {
+        self.instantes.iter().map(|&t| formatar_iso8601(t)).collect()
+    }
+
+    /// Ajusta uma tendência linear sobre o tempo decorrido desde a primeira observação
+    ///
+    /// Em `AjusteTemporal::regressao`, `x` é medido em segundos desde `origem`.
+    pub fn ajustar_tendencia(&self) -> Resultado<AjusteTemporal> {
+        if self.is_empty() {
+            return Err(RegressaoError::DadosVazios);
+        }
+        let origem = self.instantes[0];
+        let x: Vec<f64> = self.instantes.iter().map(|&t| (t - origem) as f64).collect();
+        let regressao = analise_completa_xy(&x, &self.valores)?;
+
+        Ok(AjusteTemporal {
+            regressao,
+            origem,
+            ultimo: self.instantes[self.instantes.len() - 1],
+        })
+    }
+}
+
+impl fmt::Display for SerieTemporal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (t, v) in self.instantes.iter().zip(&self.valores) {
+            writeln!(f, "{}  {:.6}", formatar_iso8601(*t), v)?;
+        }
+        Ok(())
+    }
+}
+
+/// Tendência linear ajustada sobre o tempo real
+#[derive(Debug, Clone)]
+pub struct AjusteTemporal {
+    /// Regressão com `x` em segundos decorridos desde `origem`
+    pub regressao: ResultadoRegressao,
+    /// Instante da primeira observação
+    pub origem: i64,
+    /// Instante da última observação
+    pub ultimo: i64,
+}
+
+impl AjusteTemporal {
+    /// Variação média por período da frequência informada
+    pub fn inclinacao_por(&self, frequencia: Frequencia) -> f64 {
+        self.regressao.inclinacao * frequencia.duracao_media_segundos()
+    }
+
+    /// Valores da tendência nos instantes informados
+    pub fn prever_em(&self, instantes: &[i64]) -> Vec<f64> {
+        let x: Vec<f64> = instantes.iter().map(|&t| (t - self.origem) as f64).collect();
+        self.regressao.prever(&x)
+    }
+
+    /// Prevê os próximos `n_periodos` após a última observação, datados por `frequencia`
+    pub fn prever_proximos_periodos(&self, frequencia: Frequencia, n_periodos: usize) -> SerieTemporal {
+        let instantes: Vec<i64> = (1..=n_periodos as i64)
+            .map(|k| frequencia.avancar(self.ultimo, k))
+            .collect();
+        let valores = self.prever_em(&instantes);
+        SerieTemporal { instantes, valores }
+    }
+}
+
+/// Converte uma data ISO-8601 em segundos desde a época Unix (UTC)
+///
+/// Formatos aceitos: `AAAA-MM-DD`, `AAAA-MM-DDTHH:MM`, `AAAA-MM-DDTHH:MM:SS`
+/// (com `T` ou espaço), frações de segundo (descartadas) e fuso `Z`,
+/// `±HH:MM` ou `±HHMM`. Sem fuso, o horário é interpretado como UTC.
+pub fn analisar_iso8601(texto: &str) -> Resultado<i64> {
+    let texto = texto.trim();
+    let invalida = || RegressaoError::DataInvalida;
+    let numero = |s: &str| -> Resultado<i64> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(RegressaoError::DataInvalida);
+        }
+        s.parse().map_err(|_| RegressaoError::DataInvalida)
+    };
+
+    let (data, resto) = match texto.find(['T', 't', ' ']) {
+        Some(i) => (&texto[..i], Some(&texto[i + 1..])),
+        None => (texto, None),
+    };
+
+    let partes: Vec<&str> = data.split('-').collect();
+    if partes.len() != 3 || partes[0].len() != 4 || partes[1].len() != 2 || partes[2].len() != 2 {
+        return Err(invalida());
+    }
+    let ano = numero(partes[0])?;
+    let mes = numero(partes[1])? as u32;
+    let dia = numero(partes[2])? as u32;
+    if !(1..=12).contains(&mes) || dia == 0 || dia > dias_no_mes(ano, mes) {
+        return Err(invalida());
+    }
+
+    let mut segundos = dias_de_civil(ano, mes, dia) * SEGUNDOS_POR_DIA;
+
+    if let Some(hora_texto) = resto {
+        let (hora_texto, deslocamento) = if let Some(sem_z) = hora_texto.strip_suffix(['Z', 'z']) {
+            (sem_z, 0)
+        } else if let Some(i) = hora_texto.rfind(['+', '-']) {
+            let sinal = if hora_texto[i..].starts_with('-') { -1 } else { 1 };
+            let fuso = hora_texto[i + 1..].replace(':', "");
+            if fuso.len() != 4 || !fuso.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalida());
+            }
+            let (h, m) = (numero(&fuso[..2])?, numero(&fuso[2..])?);
+            if h > 23 || m > 59 {
+                return Err(invalida());
+            }
+            (&hora_texto[..i], sinal * (h * 3_600 + m * 60))
+        } else {
+            (hora_texto, 0)
+        };
+
+        let sem_fracao = hora_texto.split(['.', ',']).next().unwrap_or("");
+        let campos: Vec<&str> = sem_fracao.split(':').collect();
+        if !(2..=3).contains(&campos.len()) || campos.iter().any(|c| c.len() != 2) {
+            return Err(invalida());
+        }
+        let h = numero(campos[0])?;
+        let m = numero(campos[1])?;
+        let s = if campos.len() == 3 { numero(campos[2])? } else { 0 };
+        if h > 23 || m > 59 || s > 59 {
+            return Err(invalida());
+        }
+        segundos += h * 3_600 + m * 60 + s - deslocamento;
+    }
+
+    Ok(segundos)
+}
+
+/// Formata segundos desde a época Unix como `AAAA-MM-DDTHH:MM:SSZ`
+pub fn formatar_iso8601(instante: i64) -> String {
+    let (ano, mes, dia) = civil_de_dias(instante.div_euclid(SEGUNDOS_POR_DIA));
+    let s = instante.rem_euclid(SEGUNDOS_POR_DIA);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        ano, mes, dia, s / 3_600, (s % 3_600) / 60, s % 60
+    )
+}
+
+/// Dias desde 1970-01-01 no calendário gregoriano proléptico (algoritmo de Hinnant)
+pub(crate) fn dias_de_civil(ano: i64, mes: u32, dia: u32) -> i64 {
+    let a = if mes <= 2 { ano - 1 } else { ano };
+    let era = a.div_euclid(400);
+    let ano_da_era = a - era * 400;
+    let m = mes as i64;
+    let dia_do_ano = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + dia as i64 - 1;
+    let dia_da_era = ano_da_era * 365 + ano_da_era / 4 - ano_da_era / 100 + dia_do_ano;
+    era * 146_097 + dia_da_era - 719_468
+}
+
+/// Inversa de [`dias_de_civil`]: (ano, mês, dia)
+pub(crate) fn civil_de_dias(dias: i64) -> (i64, u32, u32) {
+    let z = dias + 719_468;
+    let era = z.div_euclid(146_097);
+    let dia_da_era = z - era * 146_097;
+    let ano_da_era = (dia_da_era - dia_da_era / 1_460 + dia_da_era / 36_524 - dia_da_era / 146_096) / 365;
+    let dia_do_ano = dia_da_era - (365 * ano_da_era + ano_da_era / 4 - ano_da_era / 100);
+    let mp = (5 * dia_do_ano + 2) / 153;
+    let dia = (dia_do_ano - (153 * mp + 2) / 5 + 1) as u32;
+    let mes = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let ano = ano_da_era + era * 400 + i64::from(mes <= 2);
+    (ano, mes, dia)
+}
+
+pub(crate) fn dias_no_mes(ano: i64, mes: u32) -> u32 {
+    match mes {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        _ => {
+            let bissexto = (ano % 4 == 0 && ano % 100 != 0) || ano % 400 == 0;
+            if bissexto { 29 } else { 28 }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analisar_iso8601() {
+        assert_eq!(analisar_iso8601("1970-01-01").unwrap(), 0);
+        assert_eq!(analisar_iso8601("2024-02-29T12:30:00Z").unwrap(), 1_709_209_800);
+        assert_eq!(analisar_iso8601("2024-02-29 09:30:00.250-03:00").unwrap(), 1_709_209_800);
+        assert_eq!(analisar_iso8601("1969-12-31T23:59:59").unwrap(), -1);
+
+        for invalida in ["2023-02-29", "2024-13-01", "2024-1-01", "2024-01-01T25:00", "ontem"] {
+            assert!(matches!(analisar_iso8601(invalida), Err(RegressaoError::DataInvalida)), "{}", invalida);
+        }
+    }
+
+    #[test]
+    fn test_analisar_iso8601_fuso_nao_ascii() {
+        // Quatro bytes mas não quatro dígitos: não pode cortar no meio de `é`
+        for invalida in ["2024-01-01T12:00+aéb", "2024-01-01T12:00+1é2", "2024-01-01T12:00-1:é"] {
+            assert!(matches!(analisar_iso8601(invalida), Err(RegressaoError::DataInvalida)), "{}", invalida);
+        }
+    }
+
+    #[test]
+    fn test_formatar_iso8601_ida_e_volta() {
+        for instante in [0, -1, 951_782_400, 1_709_209_800, 4_102_444_799] {
+            assert_eq!(analisar_iso8601(&formatar_iso8601(instante)).unwrap(), instante);
+        }
+        assert_eq!(formatar_iso8601(1_709_209_800), "2024-02-29T12:30:00Z");
+    }
+
+    #[test]
+    fn test_avancar_mes_calendario() {
+        let inicio = analisar_iso8601("2024-01-31T08:00:00Z").unwrap();
+        let datas: Vec<String> = (0..4).map(|k| formatar_iso8601(Frequencia::Mes.avancar(inicio, k))).collect();
+
+        assert_eq!(datas, vec![
+            "2024-01-31T08:00:00Z",
+            "2024-02-29T08:00:00Z",
+            "2024-03-31T08:00:00Z",
+            "2024-04-30T08:00:00Z",
+        ]);
+        assert_eq!(formatar_iso8601(Frequencia::Ano.avancar(inicio, -1)), "2023-01-31T08:00:00Z");
+    }
+
+    #[test]
+    fn test_truncar_periodos() {
+        let t = analisar_iso8601("2024-05-16T13:45:10Z").unwrap();
+        let truncado = |f: Frequencia| formatar_iso8601(f.truncar(t));
+
+        assert_eq!(truncado(Frequencia::Hora), "2024-05-16T13:00:00Z");
+        assert_eq!(truncado(Frequencia::Dia), "2024-05-16T00:00:00Z");
+        assert_eq!(truncado(Frequencia::Semana), "2024-05-13T00:00:00Z");
+        assert_eq!(truncado(Frequencia::Mes), "2024-05-01T00:00:00Z");
+        assert_eq!(truncado(Frequencia::Ano), "2024-01-01T00:00:00Z");
+        assert_eq!(truncado(Frequencia::Segundos(900)), "2024-05-16T13:45:00Z");
+    }
+
+    #[test]
+    fn test_tendencia_com_lacunas() {
+        // 2 unidades por dia, com dias faltando
+        let datas = ["2024-03-01", "2024-03-02", "2024-03-05", "2024-03-06", "2024-03-10"];
+        let valores = [10.0, 12.0, 18.0, 20.0, 28.0];
+        let serie = SerieTemporal::de_iso8601(&datas, &valores).unwrap();
+        let ajuste = serie.ajustar_tendencia().unwrap();
+
+        assert!((ajuste.inclinacao_por(Frequencia::Dia) - 2.0).abs() < 1e-9);
+        assert!((ajuste.regressao.r_quadrado - 1.0).abs() < 1e-12);
+
+        let previsoes = ajuste.prever_proximos_periodos(Frequencia::Dia, 2);
+        assert_eq!(previsoes.datas_iso8601(), vec!["2024-03-11T00:00:00Z", "2024-03-12T00:00:00Z"]);
+        assert!((previsoes.valores()[0] - 30.0).abs() < 1e-9);
+        assert!((previsoes.valores()[1] - 32.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_serie_ordenada_na_construcao() {
+        let serie = SerieTemporal::new(vec![30, 10, 20], vec![3.0, 1.0, 2.0]).unwrap();
+        assert_eq!(serie.instantes(), &[10, 20, 30]);
+        assert_eq!(serie.valores(), &[1.0, 2.0, 3.0]);
+
+        assert!(matches!(SerieTemporal::new(vec![1], vec![]), Err(RegressaoError::TamanhosDiferentes)));
+    }
+}
@@ -0,0 +1,193 @@
+//! # Somas compensadas
+//!
+//! Somas de ponto flutuante acumulam um erro de arredondamento que cresce com
+//! o número de parcelas e, pior, com a magnitude do total parcial: somar
+//! milhares de valores próximos de `1.7e9` (segundos desde a época, por
+//! exemplo) perde os dígitos finais de cada parcela. A soma de Neumaier (uma
+//! variante da de Kahan que também cobre parcelas maiores que o total
+//! parcial) carrega esse erro num termo de compensação e entrega um resultado
+//! com erro independente de `n`, ao custo de poucas operações por parcela.
+//!
+//! O módulo também decide quando uma soma de quadrados centrados é
+//! numericamente nula levando em conta a escala dos dados, em vez de
+//! compará-la com `f64::EPSILON` em termos absolutos.
+
+use crate::numerico::{valores, Flutuante};
+
+/// Acumulador de soma compensada de Neumaier
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SomaCompensada {
+    soma: f64,
+    compensacao: f64,
+}
+
+impl SomaCompensada {
+    /// Cria um acumulador zerado
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Acrescenta uma parcela
+    pub fn adicionar(&mut self, valor: f64) {
+        let total = self.soma + valor;
+        if self.soma.abs() >= valor.abs() {
+            self.compensacao += (self.soma - total) + valor;
+        } else {
+            self.compensacao += (valor - total) + self.soma;
+        }
+        self.soma = total;
+    }
+
+    /// Soma acumulada até aqui
+    pub fn total(&self) -> f64 {
+        self.soma + self.compensacao
+    }
+
+    /// Soma das parcelas acrescentadas depois de `anterior`, um estado
+    /// anterior do mesmo acumulador (somas prefixadas de um segmento)
+    ///
+    /// Subtrai soma e compensação separadamente, sem arredondar antes os
+    /// dois totais.
+    pub fn desde(&self, anterior: &SomaCompensada) -> f64 {
+        (self.soma - anterior.soma) + (self.compensacao - anterior.compensacao)
+    }
+}
+
+impl Extend<f64> for SomaCompensada {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, valores: I) {
+        for valor in valores {
+            self.adicionar(valor);
+        }
+    }
+}
+
+impl FromIterator<f64> for SomaCompensada {
+    fn from_iter<I: IntoIterator<Item = f64>>(valores: I) -> Self {
+        let mut soma = SomaCompensada::new();
+        soma.extend(valores);
+        soma
+    }
+}
+
+/// Soma compensada de uma sequência de valores
+pub fn soma_compensada<I: IntoIterator<Item = f64>>(valores: I) -> f64 {
+    valores.into_iter().collect::<SomaCompensada>().total()
+}
+
+/// Média com soma compensada (`NaN` para fatia vazia)
+pub(crate) fn media<T: Flutuante>(dados: &[T]) -> f64 {
+    soma_compensada(valores(dados)) / dados.len() as f64
+}
+
+/// Somas de quadrados e produtos cruzados centrados em duas passadas
+///
+/// Retorna `(media_x, media_y, sxx, sxy, syy)`. A segunda passada subtrai a
+/// correção `(Σdx)(Σdy)/n` do algoritmo de duas passadas corrigido, que
+/// elimina o erro residual da média calculada.
+pub(crate) fn somas_centradas<X: Flutuante, Y: Flutuante>(x: &[X], y: &[Y]) -> (f64, f64, f64, f64, f64) {
+    somas_centradas_iter(valores(x), valores(y))
+}
+
+/// [`somas_centradas`] sobre iteradores, para `x` gerado sem alocação
+/// (índices de uma série, por exemplo); cada iterador é percorrido duas vezes
+pub(crate) fn somas_centradas_iter<X, Y>(x: X, y: Y) -> (f64, f64, f64, f64, f64)
+where
+    X: Iterator<Item = f64> + Clone,
+    Y: Iterator<Item = f64> + Clone,
+{
+    let n = x.clone().count() as f64;
+    let media_x = soma_compensada(x.clone()) / n;
+    let media_y = soma_compensada(y.clone()) / n;
+
+    let mut dx_total = SomaCompensada::new();
+    let mut dy_total = SomaCompensada::new();
+    let mut sxx = SomaCompensada::new();
+    let mut sxy = SomaCompensada::new();
+    let mut syy = SomaCompensada::new();
+    for (xi, yi) in x.zip(y) {
+        let dx = xi - media_x;
+        let dy = yi - media_y;
+        dx_total.adicionar(dx);
+        dy_total.adicionar(dy);
+        sxx.adicionar(dx * dx);
+        sxy.adicionar(dx * dy);
+        syy.adicionar(dy * dy);
+    }
+    let (ex, ey) = (dx_total.total(), dy_total.total());
+
+    (
+        media_x,
+        media_y,
+        sxx.total() - ex * ex / n,
+        sxy.total() - ex * ey / n,
+        syy.total() - ey * ey / n,
+    )
+}
+
+/// Indica se uma soma de quadrados centrados é nula dentro do arredondamento
+///
+/// Dados constantes de magnitude `escala` (o maior valor absoluto) deixam
+/// desvios da ordem de `ε·escala` em torno da média calculada; a soma de `n`
+/// desses quadrados é o maior resíduo que ainda corresponde a variância
+/// zero. Assim `1.7e9 + i` tem variância, e `1e-12·i` também.
+pub(crate) fn variancia_desprezivel(soma_quadrados: f64, n: usize, escala: f64) -> bool {
+    let limite = 4.0 * f64::EPSILON * escala;
+    soma_quadrados <= n as f64 * limite * limite
+}
+
+/// Maior valor absoluto de uma fatia (0 para fatia vazia)
+pub(crate) fn maior_absoluto<T: Flutuante>(dados: &[T]) -> f64 {
+    valores(dados).fold(0.0, |maior: f64, v| maior.max(v.abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_soma_compensada_recupera_parcelas_pequenas() {
+        // 1 + 1e100 + 1 - 1e100 perde as parcelas unitárias na soma ingênua
+        let valores = [1.0, 1e100, 1.0, -1e100];
+        assert_eq!(valores.iter().sum::<f64>(), 0.0);
+        assert_eq!(soma_compensada(valores.iter().copied()), 2.0);
+
+        let decimos = core::iter::repeat_n(0.1, 1_000_000);
+        assert!((soma_compensada(decimos) - 100_000.0).abs() < 1e-9);
+
+        // Segmento de somas prefixadas: as parcelas unitárias sobrevivem
+        let mut soma = SomaCompensada::new();
+        soma.adicionar(1e100);
+        let anterior = soma;
+        soma.extend([1.0, 1.0]);
+        assert_eq!(soma.desde(&anterior), 2.0);
+    }
+
+    #[test]
+    fn test_media_com_deslocamento_grande() {
+        let x: Vec<f64> = (0..10_000).map(|i| 1.7e9 + 0.1 * i as f64).collect();
+        let esperada = 1.7e9 + 0.1 * 9_999.0 / 2.0;
+        assert!((media(&x) - esperada).abs() <= 1e-6);
+    }
+
+    #[test]
+    fn test_somas_centradas_e_variancia_desprezivel() {
+        let x: Vec<f64> = (0..100).map(|i| 1.7e9 + i as f64).collect();
+        let y: Vec<f64> = x.iter().map(|&xi| 3.0 - 2.0 * (xi - 1.7e9)).collect();
+        let (_, _, sxx, sxy, syy) = somas_centradas(&x, &y);
+        // Σ(i - 49.5)² para i = 0..99
+        assert!((sxx - 83_325.0).abs() < 1e-6);
+        assert!((sxy + 2.0 * 83_325.0).abs() < 1e-6);
+        assert!((syy - 4.0 * 83_325.0).abs() < 1e-5);
+        assert!(!variancia_desprezivel(sxx, 100, maior_absoluto(&x)));
+
+        let constantes = vec![1.7e9 + 0.3; 100];
+        let (_, _, sxx, _, _) = somas_centradas(&constantes, &constantes);
+        assert!(variancia_desprezivel(sxx, 100, maior_absoluto(&constantes)));
+
+        let minusculos: Vec<f64> = (0..10).map(|i| 1e-12 * i as f64).collect();
+        let (_, _, sxx, _, _) = somas_centradas(&minusculos, &minusculos);
+        assert!(!variancia_desprezivel(sxx, 10, maior_absoluto(&minusculos)));
+    }
+}
@@ -0,0 +1,373 @@
+//! # Testes não paramétricos de tendência
+//!
+//! Teste de Mann–Kendall (com correção para empates e a variante de Hamed e
+//! Rao para séries autocorrelacionadas), Mann–Kendall sazonal e o estimador
+//! de inclinação de Sen com intervalo de confiança. Não supõem normalidade
+//! nem linearidade — apenas monotonicidade — e são pouco afetados por
+//! valores extremos, ao contrário da inclinação de [`crate::regressao_linear`].
+
+use std::fmt;
+
+use crate::autocorrelacao::autocorrelacao;
+use crate::descritivas::postos;
+use crate::distribuicoes::{normal_cdf, normal_quantil};
+use crate::numerico::{para_vec_f64, valores, Flutuante};
+use crate::{RegressaoError, Resultado};
+
+/// Forma de calcular a variância de `S`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarianteMannKendall {
+    /// Variância sob independência, corrigida para empates
+    Original,
+    /// Variância inflada pela autocorrelação dos postos da série sem tendência
+    /// (Hamed e Rao, 1998), usando as defasagens significativas a 5% até
+    /// `max_defasagem`. `n - 2` reproduz o artigo original, mas as
+    /// autocorrelações amostrais espúrias de defasagens longas podem até
+    /// reduzir a variância; poucas defasagens (ex.: `n/10`) são mais estáveis.
+    HamedRao { max_defasagem: usize },
+}
+
+/// Resultado do teste de Mann–Kendall
+#[derive(Debug, Clone)]
+pub struct TesteMannKendall {
+    /// `S = Σ_{i<j} sinal(y_j - y_i)`
+    pub s: f64,
+    pub variancia_s: f64,
+    /// Estatística padronizada com correção de continuidade
+    pub z: f64,
+    /// Valor-p bilateral
+    pub valor_p: f64,
+    /// Tau de Kendall entre a série e o tempo
+    pub tau: f64,
+    pub n: usize,
+}
+
+impl TesteMannKendall {
+    /// Indica se há tendência monotônica significativa ao nível `alfa`
+    pub fn tem_tendencia(&self, alfa: f64) -> bool {
+        self.valor_p < alfa
+    }
+}
+
+impl fmt::Display for TesteMannKendall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "=== Teste de Mann–Kendall ===")?;
+        writeln!(f, "S: {:.0}", self.s)?;
+        writeln!(f, "Var(S): {:.6}", self.variancia_s)?;
+        writeln!(f, "Z: {:.6}", self.z)?;
+        writeln!(f, "Valor-p: {:.6}", self.valor_p)?;
+        writeln!(f, "Tau de Kendall: {:.6}", self.tau)?;
+        let direcao = if !self.tem_tendencia(0.05) {
+            "sem tendência"
+        } else if self.s > 0.0 {
+            "crescente"
+        } else {
+            "decrescente"
+        };
+        writeln!(f, "Conclusão (5%): {}", direcao)?;
+        Ok(())
+    }
+}
+
+/// Inclinação de Sen com intervalo de confiança
+#[derive(Debug, Clone)]
+pub struct InclinacaoSen {
+    /// Mediana das inclinações entre todos os pares de observações
+    pub inclinacao: f64,
+    /// Mediana de `y_t - inclinacao·t` (Conover)
+    pub intercepto: f64,
+    pub limite_inferior: f64,
+    pub limite_superior: f64,
+    /// Nível de confiança do intervalo (ex.: 0.95)
+    pub nivel: f64,
+}
+
+impl fmt::Display for InclinacaoSen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "=== Inclinação de Sen ===")?;
+        writeln!(f, "Inclinação: {:.6}", self.inclinacao)?;
+        writeln!(f, "Intercepto: {:.6}", self.intercepto)?;
+        writeln!(
+            f,
+            "Intervalo de {:.0}%: [{:.6}, {:.6}]",
+            self.nivel * 100.0, self.limite_inferior, self.limite_superior
+        )?;
+        Ok(())
+    }
+}
+
+/// `S` de Mann–Kendall e sua variância sob independência, corrigida para empates
+fn s_e_variancia<T: Flutuante>(y: &[T]) -> (f64, f64) {
+    let n = y.len();
+    let mut s = 0.0;
+    for i in 0..n {
+        for j in i + 1..n {
+            s += sinal(y[j].para_f64() - y[i].para_f64());
+        }
+    }
+
+    let mut ordenados = para_vec_f64(y);
+    ordenados.sort_by(|a, b| a.total_cmp(b));
+    let mut correcao = 0.0;
+    for grupo in ordenados.chunk_by(|a, b| a == b) {
+        let t = grupo.len() as f64;
+        correcao += t * (t - 1.0) * (2.0 * t + 5.0);
+    }
+
+    let n = n as f64;
+    (s, (n * (n - 1.0) * (2.0 * n + 5.0) - correcao) / 18.0)
+}
+
+fn sinal(d: f64) -> f64 {
+    if d > 0.0 {
+        1.0
+    } else if d < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+fn montar_teste(s: f64, variancia_s: f64, pares: f64, n: usize) -> Resultado<TesteMannKendall> {
+    if variancia_s <= 0.0 {
+        return Err(RegressaoError::VarianciaZero);
+    }
+    let z = if s > 0.0 {
+        (s - 1.0) / variancia_s.sqrt()
+    } else if s < 0.0 {
+        (s + 1.0) / variancia_s.sqrt()
+    } else {
+        0.0
+    };
+
+    Ok(TesteMannKendall {
+        s,
+        variancia_s,
+        z,
+        valor_p: 2.0 * (1.0 - normal_cdf(z.abs())),
+        tau: s / pares,
+        n,
+    })
+}
+
+/// Teste de Mann–Kendall para tendência monotônica
+///
+/// # Argumentos
+/// * `y` - Valores da série, em ordem temporal
+/// * `variante` - Variância original ou corrigida para autocorrelação
+pub fn teste_mann_kendall<T: Flutuante>(y: &[T], variante: VarianteMannKendall) -> Resultado<TesteMannKendall> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    let n = y.len();
+    if n < 3 {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let (s, mut variancia_s) = s_e_variancia(y);
+    if let VarianteMannKendall::HamedRao { max_defasagem } = variante {
+        if max_defasagem == 0 {
+            return Err(RegressaoError::ParametroInvalido);
+        }
+        variancia_s *= fator_hamed_rao(y, max_defasagem.min(n - 2))?;
+    }
+    let nf = n as f64;
+    montar_teste(s, variancia_s, nf * (nf - 1.0) / 2.0, n)
+}
+
+/// Fator `n/n*` de Hamed e Rao
+///
+/// `1 + 2/(n(n-1)(n-2)) Σ (n-k)(n-k-1)(n-k-2) ρ_k`, somando apenas as
+/// autocorrelações significativas dos postos da série sem a tendência de Sen.
+fn fator_hamed_rao<T: Flutuante>(y: &[T], max_defasagem: usize) -> Resultado<f64> {
+    let n = y.len();
+    let inclinacao = mediana_inclinacoes(y).0;
+    let sem_tendencia: Vec<f64> = valores(y).enumerate().map(|(t, v)| v - inclinacao * t as f64).collect();
+    let correlograma = match autocorrelacao(&postos(&sem_tendencia), max_defasagem) {
+        Ok(correlograma) => correlograma,
+        // Postos constantes: não há autocorrelação a corrigir
+        Err(RegressaoError::VarianciaZero) => return Ok(1.0),
+        Err(e) => return Err(e),
+    };
+    let limite = correlograma.limite_ruido_branco(0.95);
+
+    let nf = n as f64;
+    let mut soma = 0.0;
+    for k in 1..=max_defasagem {
+        let rho = correlograma.valores[k];
+        if rho.abs() >= limite {
+            let m = (n - k) as f64;
+            soma += m * (m - 1.0) * (m - 2.0) * rho;
+        }
+    }
+    Ok(1.0 + 2.0 * soma / (nf * (nf - 1.0) * (nf - 2.0)))
+}
+
+/// Teste de Mann–Kendall sazonal (Hirsch, Slack e Smith, 1982)
+///
+/// Aplica o teste a cada estação `t % periodo` separadamente e soma as
+/// estatísticas `S` e suas variâncias, o que remove o efeito da sazonalidade.
+pub fn teste_mann_kendall_sazonal<T: Flutuante>(y: &[T], periodo: usize) -> Resultado<TesteMannKendall> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if periodo == 0 {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    if y.len() < 2 * periodo {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let mut s = 0.0;
+    let mut variancia_s = 0.0;
+    let mut pares = 0.0;
+    for estacao in 0..periodo {
+        let da_estacao: Vec<f64> = valores(y).skip(estacao).step_by(periodo).collect();
+        let (s_estacao, variancia_estacao) = s_e_variancia(&da_estacao);
+        let m = da_estacao.len() as f64;
+        s += s_estacao;
+        variancia_s += variancia_estacao;
+        pares += m * (m - 1.0) / 2.0;
+    }
+    montar_teste(s, variancia_s, pares, y.len())
+}
+
+/// Inclinações entre todos os pares de observações, ordenadas
+fn inclinacoes_pares<T: Flutuante>(y: &[T]) -> Vec<f64> {
+    let n = y.len();
+    let mut inclinacoes = Vec::with_capacity(n * (n - 1) / 2);
+    for i in 0..n {
+        for j in i + 1..n {
+            inclinacoes.push((y[j].para_f64() - y[i].para_f64()) / (j - i) as f64);
+        }
+    }
+    inclinacoes.sort_by(|a, b| a.total_cmp(b));
+    inclinacoes
+}
+
+fn mediana_inclinacoes<T: Flutuante>(y: &[T]) -> (f64, Vec<f64>) {
+    let inclinacoes = inclinacoes_pares(y);
+    (quantil_posicao(&inclinacoes, (inclinacoes.len() - 1) as f64 / 2.0), inclinacoes)
+}
+
+/// Interpola entre as estatísticas de ordem vizinhas da posição (base 0)
+fn quantil_posicao(ordenados: &[f64], posicao: f64) -> f64 {
+    let posicao = posicao.clamp(0.0, (ordenados.len() - 1) as f64);
+    let i = posicao.floor() as usize;
+    match ordenados.get(i + 1) {
+        Some(proximo) => ordenados[i] + (posicao - i as f64) * (proximo - ordenados[i]),
+        None => ordenados[i],
+    }
+}
+
+/// Estimador de inclinação de Sen (Theil–Sen) com intervalo de confiança
+///
+/// O intervalo usa as estatísticas de ordem `(N ∓ z·√Var(S)) / 2` das `N`
+/// inclinações entre pares (Gilbert, 1987), com `Var(S)` corrigida para empates.
+///
+/// # Argumentos
+/// * `y` - Valores da série, em ordem temporal
+/// * `nivel` - Nível de confiança do intervalo, em (0, 1)
+pub fn inclinacao_sen<T: Flutuante>(y: &[T], nivel: f64) -> Resultado<InclinacaoSen> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if y.len() < 3 {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+    if !(nivel > 0.0 && nivel < 1.0) {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+
+    let (inclinacao, inclinacoes) = mediana_inclinacoes(y);
+    let (_, variancia_s) = s_e_variancia(y);
+    let c = normal_quantil(0.5 + nivel / 2.0) * variancia_s.sqrt();
+    let pares = inclinacoes.len() as f64;
+    // Posições em base 1: M1 = (N - C)/2 e M2 + 1 = (N + C)/2 + 1
+    let limite_inferior = quantil_posicao(&inclinacoes, (pares - c) / 2.0 - 1.0);
+    let limite_superior = quantil_posicao(&inclinacoes, (pares + c) / 2.0);
+
+    let mut interceptos: Vec<f64> = valores(y).enumerate().map(|(t, v)| v - inclinacao * t as f64).collect();
+    interceptos.sort_by(|a, b| a.total_cmp(b));
+    let intercepto = quantil_posicao(&interceptos, (interceptos.len() - 1) as f64 / 2.0);
+
+    Ok(InclinacaoSen { inclinacao, intercepto, limite_inferior, limite_superior, nivel })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teste_util::Gerador;
+
+    #[test]
+    fn test_mann_kendall_serie_monotona() {
+        let teste = teste_mann_kendall(&[1.0, 2.0, 3.0, 4.0, 5.0], VarianteMannKendall::Original).unwrap();
+        assert_eq!(teste.s, 10.0);
+        assert!((teste.variancia_s - 50.0 / 3.0).abs() < 1e-12);
+        assert!((teste.z - 9.0 / (50.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert!((teste.valor_p - 0.027_486_336_110_5).abs() < 1e-6);
+        assert_eq!(teste.tau, 1.0);
+    }
+
+    #[test]
+    fn test_correcao_de_empates() {
+        // Grupos empatados de tamanho 2 e 3: correção 2·1·9 + 3·2·11 = 84
+        let y = [1.0, 1.0, 2.0, 3.0, 3.0, 3.0, 4.0];
+        let teste = teste_mann_kendall(&y, VarianteMannKendall::Original).unwrap();
+        assert!((teste.variancia_s - (7.0 * 6.0 * 19.0 - 84.0) / 18.0).abs() < 1e-12);
+        assert_eq!(teste.s, 17.0);
+    }
+
+    #[test]
+    fn test_hamed_rao_infla_variancia_com_autocorrelacao() {
+        let mut gerador = Gerador::new(23);
+        let mut y = vec![0.0; 200];
+        for t in 1..200 {
+            y[t] = 0.8 * y[t - 1] + gerador.normal();
+        }
+
+        let original = teste_mann_kendall(&y, VarianteMannKendall::Original).unwrap();
+        let corrigido = teste_mann_kendall(&y, VarianteMannKendall::HamedRao { max_defasagem: 20 }).unwrap();
+        assert_eq!(original.s, corrigido.s);
+        assert!(corrigido.variancia_s > 2.0 * original.variancia_s);
+        assert!(corrigido.valor_p > original.valor_p);
+        assert!(matches!(
+            teste_mann_kendall(&y, VarianteMannKendall::HamedRao { max_defasagem: 0 }),
+            Err(RegressaoError::ParametroInvalido)
+        ));
+
+        // Ruído branco com tendência forte continua significativo
+        let tendencia: Vec<f64> = (0..100).map(|t| 0.05 * t as f64 + gerador.normal()).collect();
+        assert!(teste_mann_kendall(&tendencia, VarianteMannKendall::HamedRao { max_defasagem: 20 }).unwrap().tem_tendencia(0.01));
+    }
+
+    #[test]
+    fn test_mann_kendall_sazonal() {
+        // Sazonalidade forte esconde a tendência fraca no teste comum
+        let y: Vec<f64> = (0..48).map(|t| [0.0, 30.0, 60.0, 30.0][t % 4] + 0.3 * t as f64).collect();
+        let sazonal = teste_mann_kendall_sazonal(&y, 4).unwrap();
+
+        assert_eq!(sazonal.s, 4.0 * 66.0);
+        assert_eq!(sazonal.tau, 1.0);
+        assert!(sazonal.valor_p < 1e-10);
+        assert!(matches!(teste_mann_kendall_sazonal(&y, 0), Err(RegressaoError::ParametroInvalido)));
+    }
+
+    #[test]
+    fn test_inclinacao_sen() {
+        let mut y: Vec<f64> = (0..30).map(|t| 2.0 + 0.5 * t as f64).collect();
+        y[10] = 100.0;
+        y[20] = -50.0;
+        let sen = inclinacao_sen(&y, 0.95).unwrap();
+
+        assert!((sen.inclinacao - 0.5).abs() < 1e-12);
+        assert!((sen.intercepto - 2.0).abs() < 1e-12);
+        assert!(sen.limite_inferior <= 0.5 && sen.limite_superior >= 0.5);
+
+        let mut gerador = Gerador::new(6);
+        let ruidosa: Vec<f64> = (0..60).map(|t| 1.0 - 0.2 * t as f64 + gerador.normal()).collect();
+        let sen = inclinacao_sen(&ruidosa, 0.95).unwrap();
+        assert!(sen.limite_inferior < -0.2 && sen.limite_superior > -0.2);
+        assert!(sen.limite_superior - sen.limite_inferior < 0.1);
+    }
+}
@@ -0,0 +1,185 @@
+//! Conjuntos de regressão linear do NIST StRD com valores certificados
+//!
+//! Norris (reta simples) passa por [`crate::regressao_linear_xy`] e
+//! [`crate::calcular_r2`]; Pontius (quadrático) e Wampler1–5 (polinômios de
+//! grau 5 em `x = 0..20`, mal condicionados) passam por [`crate::mmq`]. A
+//! acurácia é medida em dígitos corretos, `-log10(|calculado - certificado|
+//! / |certificado|)`, como no relatório do NIST.
+
+use crate::mmq::ajustar_mmq;
+use crate::{calcular_r2, regressao_linear_xy};
+
+/// Dígitos corretos de `calculado` em relação ao valor certificado
+fn digitos(calculado: f64, certificado: f64) -> f64 {
+    let erro = (calculado - certificado).abs();
+    if erro == 0.0 {
+        return 15.0;
+    }
+    if certificado == 0.0 {
+        -erro.log10()
+    } else {
+        -(erro / certificado.abs()).log10()
+    }
+}
+
+fn assert_digitos(nome: &str, calculado: f64, certificado: f64, minimo: f64) {
+    let d = digitos(calculado, certificado);
+    assert!(
+        d >= minimo,
+        "{}: {} contra certificado {} ({:.1} dígitos, mínimo {})",
+        nome, calculado, certificado, d, minimo
+    );
+}
+
+/// Colunas `1, x, x², ..., x^grau`
+fn colunas_polinomiais(x: &[f64], grau: i32) -> Vec<Vec<f64>> {
+    (0..=grau).map(|p| x.iter().map(|v| v.powi(p)).collect()).collect()
+}
+
+/// Confere coeficientes, erros padrão e desvio padrão residual de um ajuste
+/// polinomial contra os valores certificados
+fn conferir_polinomio(
+    nome: &str,
+    x: &[f64],
+    y: &[f64],
+    coeficientes: &[f64],
+    erros_padrao: &[f64],
+    desvio_residual: f64,
+    minimo: f64,
+) {
+    let grau = coeficientes.len() as i32 - 1;
+    let ajuste = ajustar_mmq(&colunas_polinomiais(x, grau), y).unwrap();
+    for (j, (&b, &certificado)) in ajuste.coeficientes.iter().zip(coeficientes).enumerate() {
+        assert_digitos(&format!("{} B{}", nome, j), b, certificado, minimo);
+    }
+    let gl = (y.len() - coeficientes.len()) as f64;
+    let residual = (ajuste.soma_quadrados_residuos / gl).sqrt();
+    if desvio_residual == 0.0 {
+        // Ajuste exato: resíduo no nível do arredondamento de y
+        let escala = y.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+        assert!(residual <= 1e-12 * escala, "{}: resíduo {}", nome, residual);
+        return;
+    }
+    assert_digitos(&format!("{} desvio residual", nome), residual, desvio_residual, minimo);
+    for (j, (&ep, &certificado)) in ajuste.erros_padrao.iter().zip(erros_padrao).enumerate() {
+        assert_digitos(&format!("{} EP(B{})", nome, j), ep, certificado, minimo);
+    }
+}
+
+fn x_wampler() -> Vec<f64> {
+    (0..=20).map(|i| i as f64).collect()
+}
+
+#[test]
+fn test_norris() {
+    // Dificuldade baixa; (y, x) na ordem do arquivo do NIST
+    let pares = [
+        (0.1, 0.2), (338.8, 337.4), (118.1, 118.2), (888.0, 884.6),
+        (9.2, 10.1), (228.1, 226.5), (668.5, 666.3), (998.5, 996.3),
+        (449.1, 448.6), (778.9, 777.0), (559.2, 558.2), (0.3, 0.4),
+        (0.1, 0.6), (778.1, 775.5), (668.8, 666.9), (339.3, 338.0),
+        (448.9, 447.5), (10.8, 11.6), (557.7, 556.0), (228.3, 228.1),
+        (998.0, 995.8), (888.8, 887.6), (119.6, 120.2), (0.3, 0.3),
+        (0.6, 0.3), (557.6, 556.8), (339.3, 339.1), (888.0, 887.2),
+        (998.5, 999.0), (778.9, 779.0), (10.2, 11.1), (117.6, 118.3),
+        (228.9, 229.2), (668.4, 669.1), (449.2, 448.9), (0.2, 0.5),
+    ];
+    let y: Vec<f64> = pares.iter().map(|p| p.0).collect();
+    let x: Vec<f64> = pares.iter().map(|p| p.1).collect();
+
+    let (inclinacao, intercepto) = regressao_linear_xy(&x, &y).unwrap();
+    assert_digitos("Norris B0", intercepto, -0.262323073774029, 12.0);
+    assert_digitos("Norris B1", inclinacao, 1.00211681802045, 12.0);
+
+    let previstos: Vec<f64> = x.iter().map(|&v| inclinacao * v + intercepto).collect();
+    let r2 = calcular_r2(&y, &previstos).unwrap();
+    assert_digitos("Norris R²", r2, 0.999993745883712, 12.0);
+
+    conferir_polinomio(
+        "Norris",
+        &x,
+        &y,
+        &[-0.262323073774029, 1.00211681802045],
+        &[0.232818234301152, 0.429796848199937e-3],
+        0.884796396144373,
+        12.0,
+    );
+}
+
+#[test]
+fn test_pontius() {
+    // Dificuldade baixa: deflexão contra carga, y = B0 + B1·x + B2·x²
+    let y = [
+        0.11019, 0.21956, 0.32949, 0.43899, 0.54803, 0.65694, 0.76562, 0.87487, 0.98292, 1.09146,
+        1.20001, 1.30822, 1.41599, 1.52399, 1.63194, 1.73947, 1.84646, 1.95392, 2.06128, 2.16844,
+        0.11052, 0.22018, 0.32939, 0.43886, 0.54798, 0.65739, 0.76596, 0.87474, 0.98300, 1.09150,
+        1.20004, 1.30818, 1.41613, 1.52408, 1.63159, 1.73965, 1.84696, 1.95445, 2.06177, 2.16829,
+    ];
+    let x: Vec<f64> = (0..40).map(|i| 150_000.0 * (i % 20 + 1) as f64).collect();
+
+    conferir_polinomio(
+        "Pontius",
+        &x,
+        &y,
+        &[0.673565789473684e-3, 0.732059160401003e-6, -0.316081871345029e-14],
+        &[0.107938612033077e-3, 0.157817399981659e-9, 0.486652849992036e-16],
+        0.205177424076185e-3,
+        11.0,
+    );
+}
+
+#[test]
+fn test_wampler1_e_wampler2() {
+    // Dificuldade alta: ajustes exatos de polinômios de grau 5
+    let x = x_wampler();
+    let y1: Vec<f64> = x.iter().map(|&v| (0..=5).map(|p| v.powi(p)).sum()).collect();
+    conferir_polinomio("Wampler1", &x, &y1, &[1.0; 6], &[0.0; 6], 0.0, 9.0);
+
+    let y2: Vec<f64> = x.iter().map(|&v| (0..=5).map(|p| 10f64.powi(-p) * v.powi(p)).sum()).collect();
+    conferir_polinomio(
+        "Wampler2",
+        &x,
+        &y2,
+        &[1.0, 0.1, 0.01, 0.001, 0.0001, 0.00001],
+        &[0.0; 6],
+        0.0,
+        12.0,
+    );
+}
+
+#[test]
+fn test_wampler3_4_5() {
+    // Dificuldade alta: Wampler1 mais ruído crescente, ortogonal ao polinômio
+    // (os coeficientes certificados continuam todos iguais a 1)
+    let x = x_wampler();
+    let y3 = [
+        760.0, -2042.0, 2111.0, -1684.0, 3888.0, 1858.0, 11379.0, 17560.0, 39287.0, 64382.0,
+        113159.0, 175108.0, 273291.0, 400186.0, 581243.0, 811568.0, 1121004.0, 1506550.0,
+        2002767.0, 2611612.0, 3369180.0,
+    ];
+    let y4 = [
+        75901.0, -204794.0, 204863.0, -204436.0, 253665.0, -200894.0, 214131.0, -185192.0,
+        221249.0, -138370.0, 315911.0, -27644.0, 455253.0, 197434.0, 783995.0, 608816.0,
+        1370781.0, 1303798.0, 2205519.0, 2408860.0, 3444321.0,
+    ];
+    let y5 = [
+        7590001.0, -20479994.0, 20480063.0, -20479636.0, 25231365.0, -20476094.0, 20489331.0,
+        -20460392.0, 18417449.0, -20413570.0, 20591111.0, -20302844.0, 18651453.0, -20077766.0,
+        21059195.0, -19666384.0, 26348481.0, -18971402.0, 22480719.0, -17866340.0, 10958421.0,
+    ];
+    let erros_padrao = [
+        2152.32624678170, 2363.55173469681, 779.343524331583,
+        101.475507550350, 5.64566512170752, 0.112324854679312,
+    ];
+    // O ruído cresce 100 vezes a cada conjunto e o erro dos coeficientes
+    // acompanha (número de condição de X ~ 1e7 vezes resíduo relativo): o
+    // mínimo de dígitos cai de acordo, como nos resultados de referência
+    for (nome, y, escala, minimo) in [
+        ("Wampler3", &y3, 1.0, 9.0),
+        ("Wampler4", &y4, 100.0, 7.5),
+        ("Wampler5", &y5, 10_000.0, 5.5),
+    ] {
+        let ep: Vec<f64> = erros_padrao.iter().map(|e| e * escala).collect();
+        conferir_polinomio(nome, &x, y, &[1.0; 6], &ep, 2360.14502379268 * escala, minimo);
+    }
+}
@@ -0,0 +1,33 @@
+//! Utilidades compartilhadas pelos testes unitários
+
+/// Gerador pseudoaleatório determinístico (xorshift64*) para os testes
+pub(crate) struct Gerador {
+    estado: u64,
+}
+
+impl Gerador {
+    pub(crate) fn new(semente: u64) -> Self {
+        Gerador { estado: semente.max(1) }
+    }
+
+    /// Amostra uniforme em (0, 1)
+    pub(crate) fn uniforme(&mut self) -> f64 {
+        self.estado ^= self.estado >> 12;
+        self.estado ^= self.estado << 25;
+        self.estado ^= self.estado >> 27;
+        let bits = self.estado.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11;
+        (bits as f64 + 0.5) / (1u64 << 53) as f64
+    }
+
+    /// Amostra da normal padrão (Box–Muller)
+    pub(crate) fn normal(&mut self) -> f64 {
+        let u1 = self.uniforme();
+        let u2 = self.uniforme();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+
+    /// Vetor de `n` ruídos normais com desvio padrão `sigma`
+    pub(crate) fn ruido(&mut self, n: usize, sigma: f64) -> Vec<f64> {
+        (0..n).map(|_| sigma * self.normal()).collect()
+    }
+}
@@ -0,0 +1,278 @@
+//! # Transformações de séries temporais
+//!
+//! Diferenciação simples e sazonal, defasagens e avanços, logaritmo e
+//! Box–Cox. Cada transformação tem inversa exata, de modo que previsões
+//! feitas na escala transformada (por exemplo, com [`crate::prever_valores`])
+//! possam ser devolvidas à escala original.
+
+use crate::otimizacao::secao_aurea;
+use crate::{RegressaoError, Resultado};
+
+/// Série diferenciada com os valores necessários para desfazer a diferenciação
+#[derive(Debug, Clone)]
+pub struct SerieDiferenciada {
+    /// Série após `ordem` diferenciações de defasagem `periodo`
+    pub valores: Vec<f64>,
+    pub ordem: usize,
+    pub periodo: usize,
+    /// Primeiros `periodo` valores de cada estágio (para reconstruir a série)
+    iniciais: Vec<Vec<f64>>,
+    /// Últimos `periodo` valores de cada estágio (para integrar previsões)
+    finais: Vec<Vec<f64>>,
+}
+
+/// Aplica `ordem` diferenças simples: `Δy_t = y_t - y_{t-1}`
+pub fn diferenciar(y: &[f64], ordem: usize) -> Resultado<SerieDiferenciada> {
+    diferenciar_sazonal(y, 1, ordem)
+}
+
+/// Aplica `ordem` diferenças sazonais: `Δ_s y_t = y_t - y_{t-s}`
+///
+/// Com `periodo = 1` equivale a [`diferenciar`]. Para combinar diferenças
+/// simples e sazonais, encadeie as chamadas e desfaça na ordem inversa.
+pub fn diferenciar_sazonal(y: &[f64], periodo: usize, ordem: usize) -> Resultado<SerieDiferenciada> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if periodo == 0 {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    if y.len() <= periodo * ordem {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let mut valores = y.to_vec();
+    let mut iniciais = Vec::with_capacity(ordem);
+    let mut finais = Vec::with_capacity(ordem);
+    for _ in 0..ordem {
+        iniciais.push(valores[..periodo].to_vec());
+        finais.push(valores[valores.len() - periodo..].to_vec());
+        valores = (periodo..valores.len()).map(|t| valores[t] - valores[t - periodo]).collect();
+    }
+
+    Ok(SerieDiferenciada { valores, ordem, periodo, iniciais, finais })
+}
+
+impl SerieDiferenciada {
+    /// Reconstrói a série original a partir de diferenças (inversa exata)
+    ///
+    /// Aplicado a `self.valores`, devolve a série que foi diferenciada; aplicado
+    /// a valores ajustados na escala diferenciada, devolve-os na escala original.
+    pub fn integrar(&self, diferencas: &[f64]) -> Vec<f64> {
+        let mut nivel = diferencas.to_vec();
+        for iniciais in self.iniciais.iter().rev() {
+            let mut anterior = iniciais.clone();
+            for (t, d) in nivel.iter().enumerate() {
+                anterior.push(anterior[t] + d);
+            }
+            nivel = anterior;
+        }
+        nivel
+    }
+
+    /// Converte previsões da série diferenciada em previsões da série original,
+    /// continuando a partir do fim da série diferenciada
+    pub fn integrar_previsoes(&self, previsoes: &[f64]) -> Vec<f64> {
+        let mut nivel = previsoes.to_vec();
+        for finais in self.finais.iter().rev() {
+            let mut estendido = finais.clone();
+            for (h, d) in nivel.iter().enumerate() {
+                estendido.push(estendido[h] + d);
+            }
+            nivel = estendido[finais.len()..].to_vec();
+        }
+        nivel
+    }
+}
+
+/// Defasa a série em `k` períodos: `resultado[t] = y[t - k]`
+///
+/// As primeiras `k` posições, sem valor correspondente, são `None`.
+pub fn defasar(y: &[f64], k: usize) -> Vec<Option<f64>> {
+    (0..y.len()).map(|t| t.checked_sub(k).map(|i| y[i])).collect()
+}
+
+/// Avança a série em `k` períodos: `resultado[t] = y[t + k]`
+///
+/// As últimas `k` posições, sem valor correspondente, são `None`.
+pub fn avancar(y: &[f64], k: usize) -> Vec<Option<f64>> {
+    (0..y.len()).map(|t| y.get(t + k).copied()).collect()
+}
+
+/// Monta as defasagens `1..=max_defasagem` alinhadas com a resposta
+///
+/// # Retorna
+/// * `Ok((colunas, resposta))` - `colunas[k - 1][t] = y[t + max_defasagem - k]`
+///   e `resposta[t] = y[t + max_defasagem]`, descartando as linhas incompletas
+pub fn matriz_defasagens(y: &[f64], max_defasagem: usize) -> Resultado<(Vec<Vec<f64>>, Vec<f64>)> {
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    if y.len() <= max_defasagem {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let colunas = (1..=max_defasagem)
+        .map(|k| y[max_defasagem - k..y.len() - k].to_vec())
+        .collect();
+    Ok((colunas, y[max_defasagem..].to_vec()))
+}
+
+/// Transformação de Box–Cox; `lambda = 0` corresponde ao logaritmo natural
+///
+/// `z = (y^λ - 1) / λ` para `λ ≠ 0` e `z = ln y` para `λ = 0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxCox {
+    pub lambda: f64,
+}
+
+impl BoxCox {
+    pub fn new(lambda: f64) -> Self {
+        BoxCox { lambda }
+    }
+
+    /// Transformação logarítmica
+    pub fn log() -> Self {
+        BoxCox { lambda: 0.0 }
+    }
+
+    /// Estima `λ` em [-2, 2] por máxima verossimilhança perfilada
+    ///
+    /// Maximiza `-n/2 ln σ²(λ) + (λ - 1) Σ ln y`, onde `σ²(λ)` é a variância
+    /// da série transformada.
+    pub fn estimar(y: &[f64]) -> Resultado<Self> {
+        if y.is_empty() {
+            return Err(RegressaoError::DadosVazios);
+        }
+        if y.len() < 2 {
+            return Err(RegressaoError::DadosInsuficientes);
+        }
+        if y.iter().any(|&v| v <= 0.0) {
+            return Err(RegressaoError::ValoresNaoPositivos);
+        }
+
+        let n = y.len() as f64;
+        let soma_log: f64 = y.iter().map(|v| v.ln()).sum();
+        if y.iter().all(|&v| v == y[0]) {
+            return Err(RegressaoError::VarianciaZero);
+        }
+
+        let menos_verossimilhanca = |lambda: f64| {
+            let z: Vec<f64> = y.iter().map(|&v| box_cox_valor(v, lambda)).collect();
+            let media = z.iter().sum::<f64>() / n;
+            let variancia = z.iter().map(|v| (v - media).powi(2)).sum::<f64>() / n;
+            n / 2.0 * variancia.ln() - (lambda - 1.0) * soma_log
+        };
+
+        Ok(BoxCox { lambda: secao_aurea(menos_verossimilhanca, -2.0, 2.0, 1e-8) })
+    }
+
+    /// Aplica a transformação; exige valores estritamente positivos
+    pub fn transformar(&self, y: &[f64]) -> Resultado<Vec<f64>> {
+        if y.iter().any(|&v| v <= 0.0) {
+            return Err(RegressaoError::ValoresNaoPositivos);
+        }
+        Ok(y.iter().map(|&v| box_cox_valor(v, self.lambda)).collect())
+    }
+
+    /// Inversa exata: `y = (λz + 1)^{1/λ}` (ou `exp(z)` para `λ = 0`)
+    ///
+    /// Valores fora do domínio da inversa (`λz + 1 ≤ 0`) resultam em `NaN`.
+    pub fn inverter(&self, z: &[f64]) -> Vec<f64> {
+        z.iter()
+            .map(|&v| {
+                if self.lambda == 0.0 {
+                    v.exp()
+                } else {
+                    let base = self.lambda * v + 1.0;
+                    if base > 0.0 { base.powf(1.0 / self.lambda) } else { f64::NAN }
+                }
+            })
+            .collect()
+    }
+}
+
+fn box_cox_valor(y: f64, lambda: f64) -> f64 {
+    if lambda == 0.0 {
+        y.ln()
+    } else {
+        (y.powf(lambda) - 1.0) / lambda
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teste_util::Gerador;
+    use crate::{prever_valores, regressao_linear};
+
+    #[test]
+    fn test_diferenciar_e_integrar() {
+        let y = vec![1.0, 4.0, 9.0, 16.0, 25.0, 36.0];
+        let d2 = diferenciar(&y, 2).unwrap();
+
+        assert_eq!(d2.valores, vec![2.0, 2.0, 2.0, 2.0]);
+        assert_eq!(d2.integrar(&d2.valores), y);
+        // Segunda diferença constante continua a sequência de quadrados
+        assert_eq!(d2.integrar_previsoes(&[2.0, 2.0]), vec![49.0, 64.0]);
+    }
+
+    #[test]
+    fn test_diferenciacao_sazonal() {
+        let y = vec![10.0, 20.0, 30.0, 12.0, 22.0, 32.0, 14.0, 24.0, 34.0];
+        let ds = diferenciar_sazonal(&y, 3, 1).unwrap();
+
+        assert_eq!(ds.valores, vec![2.0; 6]);
+        assert_eq!(ds.integrar(&ds.valores), y);
+        assert_eq!(ds.integrar_previsoes(&[2.0, 2.0, 2.0, 2.0]), vec![16.0, 26.0, 36.0, 18.0]);
+    }
+
+    #[test]
+    fn test_previsao_na_escala_diferenciada() {
+        // Tendência quadrática: a primeira diferença é linear
+        let y: Vec<f64> = (0..10).map(|t| (t * t) as f64).collect();
+        let d1 = diferenciar(&y, 1).unwrap();
+        let (a, b) = regressao_linear(&d1.valores).unwrap();
+        let previsoes = d1.integrar_previsoes(&prever_valores(d1.valores.len(), 3, a, b));
+
+        assert_eq!(previsoes.len(), 3);
+        for (h, p) in previsoes.iter().enumerate() {
+            let t = (10 + h) as f64;
+            assert!((p - t * t).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_defasagens_e_avancos() {
+        let y = vec![1.0, 2.0, 3.0, 4.0];
+        assert_eq!(defasar(&y, 1), vec![None, Some(1.0), Some(2.0), Some(3.0)]);
+        assert_eq!(avancar(&y, 2), vec![Some(3.0), Some(4.0), None, None]);
+
+        let (colunas, resposta) = matriz_defasagens(&y, 2).unwrap();
+        assert_eq!(resposta, vec![3.0, 4.0]);
+        assert_eq!(colunas, vec![vec![2.0, 3.0], vec![1.0, 2.0]]);
+    }
+
+    #[test]
+    fn test_box_cox_inversa_exata() {
+        let y = vec![0.5, 1.0, 2.5, 10.0, 100.0];
+        for lambda in [-1.0, 0.0, 0.5, 2.0] {
+            let bc = BoxCox::new(lambda);
+            let volta = bc.inverter(&bc.transformar(&y).unwrap());
+            for (a, b) in y.iter().zip(&volta) {
+                assert!((a - b).abs() < 1e-10 * a.max(1.0));
+            }
+        }
+        assert!(matches!(BoxCox::log().transformar(&[1.0, 0.0]), Err(RegressaoError::ValoresNaoPositivos)));
+    }
+
+    #[test]
+    fn test_box_cox_estimacao_lambda() {
+        let mut gerador = Gerador::new(17);
+        let lognormal: Vec<f64> = (0..500).map(|_| (0.5 * gerador.normal()).exp()).collect();
+        let quadrados: Vec<f64> = (0..500).map(|_| (4.0 + gerador.normal()).powi(2)).collect();
+
+        assert!(BoxCox::estimar(&lognormal).unwrap().lambda.abs() < 0.2);
+        assert!((BoxCox::estimar(&quadrados).unwrap().lambda - 0.5).abs() < 0.3);
+    }
+}
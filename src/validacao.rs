@@ -0,0 +1,252 @@
+//! # Validação cruzada de séries temporais
+//!
+//! Avaliação fora da amostra por origem móvel (walk-forward): em cada
+//! origem o modelo é reajustado apenas com o passado e suas previsões para
+//! os próximos `horizonte` períodos são comparadas com os valores reais.
+//! Os erros são agregados por horizonte com [`crate::calcular_mse`],
+//! [`crate::calcular_mae`] ou qualquer outra métrica com a mesma assinatura.
+
+use std::fmt;
+
+use crate::{analise_completa, calcular_mae, calcular_mse, RegressaoError, Resultado};
+
+/// Como a janela de treino acompanha a origem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Origem {
+    /// Janela de treino de tamanho fixo, que desliza com a origem
+    Deslizante { janela: usize },
+    /// Treino desde o início da série; a primeira origem tem `minimo` observações
+    Expansiva { minimo: usize },
+}
+
+/// Previsões feitas a partir de uma origem
+#[derive(Debug, Clone)]
+pub struct Dobra {
+    /// Índice da primeira observação prevista (fim exclusivo do treino)
+    pub origem: usize,
+    /// Número de observações usadas no treino
+    pub tamanho_treino: usize,
+    pub previsoes: Vec<f64>,
+    pub reais: Vec<f64>,
+}
+
+/// Resultado da validação cruzada
+#[derive(Debug, Clone)]
+pub struct ResultadoValidacao {
+    pub dobras: Vec<Dobra>,
+    /// MSE fora da amostra para cada horizonte `1..=h`
+    pub mse_por_horizonte: Vec<f64>,
+    /// MAE fora da amostra para cada horizonte `1..=h`
+    pub mae_por_horizonte: Vec<f64>,
+    /// MSE sobre todas as previsões de todas as origens
+    pub mse: f64,
+    /// MAE sobre todas as previsões de todas as origens
+    pub mae: f64,
+}
+
+impl ResultadoValidacao {
+    /// Aplica `metrica(reais, previstos)` às previsões de cada horizonte
+    ///
+    /// Permite agregar qualquer métrica com a assinatura de
+    /// [`crate::calcular_mse`]; a posição `h - 1` do resultado corresponde ao
+    /// horizonte `h`.
+    pub fn por_horizonte<F>(&self, metrica: F) -> Resultado<Vec<f64>>
+    where
+        F: Fn(&[f64], &[f64]) -> Resultado<f64>,
+    {
+        let horizonte = self.dobras[0].previsoes.len();
+        (0..horizonte)
+            .map(|h| {
+                let reais: Vec<f64> = self.dobras.iter().map(|d| d.reais[h]).collect();
+                let previstos: Vec<f64> = self.dobras.iter().map(|d| d.previsoes[h]).collect();
+                metrica(&reais, &previstos)
+            })
+            .collect()
+    }
+
+    /// Aplica `metrica(reais, previstos)` a todas as previsões de todas as origens
+    pub fn agregado<F>(&self, metrica: F) -> Resultado<f64>
+    where
+        F: Fn(&[f64], &[f64]) -> Resultado<f64>,
+    {
+        let reais: Vec<f64> = self.dobras.iter().flat_map(|d| d.reais.iter().copied()).collect();
+        let previstos: Vec<f64> = self.dobras.iter().flat_map(|d| d.previsoes.iter().copied()).collect();
+        metrica(&reais, &previstos)
+    }
+}
+
+impl fmt::Display for ResultadoValidacao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "=== Validação Cruzada ({} origens) ===", self.dobras.len())?;
+        for (h, (mse, mae)) in self.mse_por_horizonte.iter().zip(&self.mae_por_horizonte).enumerate() {
+            writeln!(f, "Horizonte {}: MSE {:.6}, RMSE {:.6}, MAE {:.6}", h + 1, mse, mse.sqrt(), mae)?;
+        }
+        writeln!(f, "MSE global: {:.6}", self.mse)?;
+        writeln!(f, "MAE global: {:.6}", self.mae)?;
+        Ok(())
+    }
+}
+
+/// Validação cruzada por origem móvel para qualquer modelo
+///
+/// As origens começam após a primeira janela de treino e avançam de `passo`
+/// em `passo` enquanto houver `horizonte` observações reais à frente.
+///
+/// # Argumentos
+/// * `y` - Valores da série
+/// * `origem` - Janela de treino deslizante ou expansiva
+/// * `horizonte` - Número de períodos previstos a partir de cada origem
+/// * `passo` - Distância entre origens consecutivas
+/// * `modelo` - Recebe o treino e o horizonte e devolve `horizonte` previsões
+///   (índice 0 = período logo após o treino)
+pub fn validacao_cruzada<F>(
+    y: &[f64],
+    origem: Origem,
+    horizonte: usize,
+    passo: usize,
+    mut modelo: F,
+) -> Resultado<ResultadoValidacao>
+where
+    F: FnMut(&[f64], usize) -> Resultado<Vec<f64>>,
+{
+    if y.is_empty() {
+        return Err(RegressaoError::DadosVazios);
+    }
+    let primeira = match origem {
+        Origem::Deslizante { janela } => janela,
+        Origem::Expansiva { minimo } => minimo,
+    };
+    if primeira == 0 || horizonte == 0 || passo == 0 {
+        return Err(RegressaoError::ParametroInvalido);
+    }
+    if primeira + horizonte > y.len() {
+        return Err(RegressaoError::DadosInsuficientes);
+    }
+
+    let mut dobras = Vec::new();
+    for t in (primeira..=y.len() - horizonte).step_by(passo) {
+        let treino = match origem {
+            Origem::Deslizante { janela } => &y[t - janela..t],
+            Origem::Expansiva { .. } => &y[..t],
+        };
+        let previsoes = modelo(treino, horizonte)?;
+        if previsoes.len() != horizonte {
+            return Err(RegressaoError::TamanhosDiferentes);
+        }
+        dobras.push(Dobra {
+            origem: t,
+            tamanho_treino: treino.len(),
+            previsoes,
+            reais: y[t..t + horizonte].to_vec(),
+        });
+    }
+
+    let mut resultado = ResultadoValidacao {
+        dobras,
+        mse_por_horizonte: Vec::new(),
+        mae_por_horizonte: Vec::new(),
+        mse: 0.0,
+        mae: 0.0,
+    };
+    resultado.mse_por_horizonte = resultado.por_horizonte(calcular_mse)?;
+    resultado.mae_por_horizonte = resultado.por_horizonte(calcular_mae)?;
+    resultado.mse = resultado.agregado(calcular_mse)?;
+    resultado.mae = resultado.agregado(calcular_mae)?;
+    Ok(resultado)
+}
+
+/// Modelo de tendência linear para [`validacao_cruzada`]
+///
+/// Ajusta [`analise_completa`] ao treino e prevê com
+/// `prever_proximos_periodos(treino.len(), horizonte)`.
+pub fn previsor_tendencia_linear(treino: &[f64], horizonte: usize) -> Resultado<Vec<f64>> {
+    let resultado = analise_completa(treino)?;
+    Ok(resultado.prever_proximos_periodos(treino.len(), horizonte))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arima::{ajustar_arima, EspecificacaoArima, MetodoEstimacao};
+    use crate::teste_util::Gerador;
+
+    #[test]
+    fn test_origens_deslizante_e_expansiva() {
+        let y: Vec<f64> = (0..20).map(|t| t as f64).collect();
+
+        let deslizante = validacao_cruzada(&y, Origem::Deslizante { janela: 5 }, 3, 4, previsor_tendencia_linear).unwrap();
+        let origens: Vec<usize> = deslizante.dobras.iter().map(|d| d.origem).collect();
+        assert_eq!(origens, vec![5, 9, 13, 17]);
+        assert!(deslizante.dobras.iter().all(|d| d.tamanho_treino == 5));
+        assert_eq!(deslizante.dobras[3].reais, vec![17.0, 18.0, 19.0]);
+
+        let expansiva = validacao_cruzada(&y, Origem::Expansiva { minimo: 5 }, 3, 4, previsor_tendencia_linear).unwrap();
+        let tamanhos: Vec<usize> = expansiva.dobras.iter().map(|d| d.tamanho_treino).collect();
+        assert_eq!(tamanhos, vec![5, 9, 13, 17]);
+        assert!(expansiva.mse < 1e-20);
+    }
+
+    #[test]
+    fn test_erro_cresce_com_horizonte_em_passeio_aleatorio() {
+        let mut gerador = Gerador::new(8);
+        let mut nivel = 0.0;
+        let y: Vec<f64> = (0..400)
+            .map(|_| {
+                nivel += gerador.normal();
+                nivel
+            })
+            .collect();
+
+        // Previsão ingênua: repetir o último valor; o MSE no horizonte h é ≈ h
+        let ingenuo = |treino: &[f64], h: usize| Ok(vec![treino[treino.len() - 1]; h]);
+        let resultado = validacao_cruzada(&y, Origem::Expansiva { minimo: 50 }, 4, 1, ingenuo).unwrap();
+
+        assert_eq!(resultado.dobras.len(), 347);
+        let mse = &resultado.mse_por_horizonte;
+        assert!(mse[0] < mse[1] && mse[1] < mse[3]);
+        assert!((mse[0] - 1.0).abs() < 0.3);
+        let rmse = resultado.por_horizonte(|a, b| calcular_mse(a, b).map(f64::sqrt)).unwrap();
+        assert!((rmse[3] - mse[3].sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_validacao_com_arima() {
+        let mut gerador = Gerador::new(31);
+        let ruido = gerador.ruido(150, 1.0);
+        let mut y = vec![0.0; 150];
+        for t in 1..150 {
+            y[t] = 0.7 * y[t - 1] + ruido[t];
+        }
+
+        let arima = |treino: &[f64], h: usize| {
+            let modelo = ajustar_arima(treino, &EspecificacaoArima::ar(1), MetodoEstimacao::SomaQuadradosCondicional)?;
+            Ok(modelo.prever(h, 0.95)?.previsoes)
+        };
+        let resultado = validacao_cruzada(&y, Origem::Deslizante { janela: 60 }, 2, 10, arima).unwrap();
+        assert_eq!(resultado.dobras.len(), 9);
+        assert!(resultado.mse_por_horizonte[0] < 2.0);
+    }
+
+    #[test]
+    fn test_validacao_erros() {
+        let y = vec![1.0, 2.0, 3.0, 4.0];
+        assert!(matches!(
+            validacao_cruzada(&y, Origem::Expansiva { minimo: 3 }, 2, 1, previsor_tendencia_linear),
+            Err(RegressaoError::DadosInsuficientes)
+        ));
+        assert!(matches!(
+            validacao_cruzada(&y, Origem::Deslizante { janela: 2 }, 1, 0, previsor_tendencia_linear),
+            Err(RegressaoError::ParametroInvalido)
+        ));
+        let curto = |_: &[f64], _: usize| Ok(vec![0.0]);
+        assert!(matches!(
+            validacao_cruzada(&y, Origem::Expansiva { minimo: 2 }, 2, 1, curto),
+            Err(RegressaoError::TamanhosDiferentes)
+        ));
+        // Erros do modelo são propagados
+        assert!(matches!(
+            validacao_cruzada(&y, Origem::Deslizante { janela: 1 }, 1, 1, previsor_tendencia_linear),
+            Err(RegressaoError::DadosInsuficientes)
+        ));
+    }
+}